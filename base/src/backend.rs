@@ -1,39 +1,59 @@
-use super::entity::{
-    channel::{
-        AttachmentRepository, CategoryChannelRepository, GroupRepository, MessageRepository,
-        PrivateChannelRepository, TextChannelRepository, VoiceChannelRepository,
+use super::{
+    entity::{
+        channel::{
+            AttachmentRepository, CategoryChannelRepository, GroupRepository, MessageRepository,
+            PrivateChannelRepository, TextChannelRepository, VoiceChannelRepository,
+            WebhookRepository,
+        },
+        gateway::{PresenceRepository, TypingRepository},
+        guild::{
+            BanRepository, EmojiRepository, GuildRepository, MemberRepository, RoleRepository,
+        },
+        invite::InviteRepository,
+        user::{CurrentUserRepository, UserRepository},
+        voice::VoiceStateRepository,
     },
-    gateway::PresenceRepository,
-    guild::{EmojiRepository, GuildRepository, MemberRepository, RoleRepository},
-    user::UserRepository,
-    voice::VoiceStateRepository,
+    repository::NoopRepository,
 };
+use std::convert::Infallible;
 
 pub trait Backend: Send + Sized + 'static {
     type Error: Send + 'static;
     type AttachmentRepository: AttachmentRepository<Self> + Send + Sync;
+    type BanRepository: BanRepository<Self> + Send + Sync;
     type CategoryChannelRepository: CategoryChannelRepository<Self> + Send + Sync;
+    type CurrentUserRepository: CurrentUserRepository<Self> + Send + Sync;
     type EmojiRepository: EmojiRepository<Self> + Send + Sync;
     type GroupRepository: GroupRepository<Self> + Send + Sync;
     type GuildRepository: GuildRepository<Self> + Send + Sync;
+    type InviteRepository: InviteRepository<Self> + Send + Sync;
     type MemberRepository: MemberRepository<Self> + Send + Sync;
     type MessageRepository: MessageRepository<Self> + Send + Sync;
     type PresenceRepository: PresenceRepository<Self> + Send + Sync;
     type PrivateChannelRepository: PrivateChannelRepository<Self> + Send + Sync;
     type RoleRepository: RoleRepository<Self> + Send + Sync;
     type TextChannelRepository: TextChannelRepository<Self> + Send + Sync;
+    type TypingRepository: TypingRepository<Self> + Send + Sync;
     type UserRepository: UserRepository<Self> + Send + Sync;
     type VoiceChannelRepository: VoiceChannelRepository<Self> + Send + Sync;
     type VoiceStateRepository: VoiceStateRepository<Self> + Send + Sync;
+    type WebhookRepository: WebhookRepository<Self> + Send + Sync;
 
     /// Return a new instance of the backend's attachment repository
     /// implementation.
     fn attachments(&self) -> Self::AttachmentRepository;
 
+    /// Return a new instance of the backend's ban repository implementation.
+    fn bans(&self) -> Self::BanRepository;
+
     /// Return a new instance of the backend's guild category channel repository
     /// implementation.
     fn category_channels(&self) -> Self::CategoryChannelRepository;
 
+    /// Return a new instance of the backend's current user repository
+    /// implementation.
+    fn current_user(&self) -> Self::CurrentUserRepository;
+
     /// Return a new instance of the backend's emoji repository implementation.
     fn emojis(&self) -> Self::EmojiRepository;
 
@@ -43,6 +63,10 @@ pub trait Backend: Send + Sized + 'static {
     /// Return a new instance of the backend's guild repository implementation.
     fn guilds(&self) -> Self::GuildRepository;
 
+    /// Return a new instance of the backend's invite repository
+    /// implementation.
+    fn invites(&self) -> Self::InviteRepository;
+
     /// Return a new instance of the backend's member repository implementation.
     fn members(&self) -> Self::MemberRepository;
 
@@ -65,6 +89,10 @@ pub trait Backend: Send + Sized + 'static {
     /// implementation.
     fn text_channels(&self) -> Self::TextChannelRepository;
 
+    /// Return a new instance of the backend's typing repository
+    /// implementation.
+    fn typing(&self) -> Self::TypingRepository;
+
     /// Return a new instance of the backend's user repository implementation.
     fn users(&self) -> Self::UserRepository;
 
@@ -75,4 +103,118 @@ pub trait Backend: Send + Sized + 'static {
     /// Return a new instance of the backend's voice state repository
     /// implementation.
     fn voice_states(&self) -> Self::VoiceStateRepository;
+
+    /// Return a new instance of the backend's webhook repository
+    /// implementation.
+    fn webhooks(&self) -> Self::WebhookRepository;
+}
+
+/// Backend that caches nothing, with every repository being a
+/// [`NoopRepository`].
+///
+/// This is useful for using the cache's event processing without storing any
+/// entities, and as a baseline for testing code that is generic over
+/// backends.
+///
+/// [`NoopRepository`]: repository/struct.NoopRepository.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopBackend;
+
+impl Backend for NoopBackend {
+    type Error = Infallible;
+    type AttachmentRepository = NoopRepository<Self>;
+    type BanRepository = NoopRepository<Self>;
+    type CategoryChannelRepository = NoopRepository<Self>;
+    type CurrentUserRepository = NoopRepository<Self>;
+    type EmojiRepository = NoopRepository<Self>;
+    type GroupRepository = NoopRepository<Self>;
+    type GuildRepository = NoopRepository<Self>;
+    type InviteRepository = NoopRepository<Self>;
+    type MemberRepository = NoopRepository<Self>;
+    type MessageRepository = NoopRepository<Self>;
+    type PresenceRepository = NoopRepository<Self>;
+    type PrivateChannelRepository = NoopRepository<Self>;
+    type RoleRepository = NoopRepository<Self>;
+    type TextChannelRepository = NoopRepository<Self>;
+    type TypingRepository = NoopRepository<Self>;
+    type UserRepository = NoopRepository<Self>;
+    type VoiceChannelRepository = NoopRepository<Self>;
+    type VoiceStateRepository = NoopRepository<Self>;
+    type WebhookRepository = NoopRepository<Self>;
+
+    fn attachments(&self) -> Self::AttachmentRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn bans(&self) -> Self::BanRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn category_channels(&self) -> Self::CategoryChannelRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn current_user(&self) -> Self::CurrentUserRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn emojis(&self) -> Self::EmojiRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn groups(&self) -> Self::GroupRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn guilds(&self) -> Self::GuildRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn invites(&self) -> Self::InviteRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn members(&self) -> Self::MemberRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn messages(&self) -> Self::MessageRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn presences(&self) -> Self::PresenceRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn private_channels(&self) -> Self::PrivateChannelRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn roles(&self) -> Self::RoleRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn text_channels(&self) -> Self::TextChannelRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn typing(&self) -> Self::TypingRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn users(&self) -> Self::UserRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn voice_channels(&self) -> Self::VoiceChannelRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn voice_states(&self) -> Self::VoiceStateRepository {
+        NoopRepository::new(*self)
+    }
+
+    fn webhooks(&self) -> Self::WebhookRepository {
+        NoopRepository::new(*self)
+    }
 }
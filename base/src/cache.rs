@@ -1,18 +1,39 @@
 use super::{
+    config::Config,
     entity::{
         channel::{
-            CategoryChannelEntity, GroupEntity, PrivateChannelEntity, TextChannelEntity,
-            VoiceChannelEntity,
+            AttachmentEntity, CategoryChannelEntity, GroupEntity, GroupField, MessageEntities,
+            MessageField, MessageRepository, PrivateChannelEntity, PrivateChannelField,
+            TextChannelEntity, TextChannelField, TextChannelRepository, VoiceChannelEntity,
+            WebhookRepository,
         },
-        guild::MemberEntity,
+        gateway::{PresenceEntity, TypingEntity},
+        guild::{
+            BanEntity, EmojiEntity, GuildEntity, GuildRepository, MemberEntity, MemberField,
+            RoleEntity,
+        },
+        invite::InviteEntity,
+        user::{CurrentUserEntity, CurrentUserRepository, UserEntity},
+        voice::VoiceStateEntity,
     },
-    Backend, Repository,
+    permission, Backend, Repository,
 };
-use futures_util::future;
-use std::sync::Arc;
+use futures_util::{future, stream::TryStreamExt};
+use std::{collections::HashMap, sync::Arc};
 use twilight_model::{
-    channel::{Channel, GuildChannel},
-    gateway::event::Event,
+    channel::{Channel, GuildChannel, Reaction, ReactionType},
+    gateway::{
+        event::Event,
+        payload::{
+            ChannelPinsUpdate, GuildEmojisUpdate, MemberUpdate, MessageUpdate, PresenceUpdate,
+            Ready,
+        },
+        presence::{Status, UserOrId},
+    },
+    guild::{Guild, GuildStatus, PartialGuild, Permissions},
+    id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    user::User,
+    voice::VoiceState,
 };
 
 /// The cache, a container over a backend that allows you to retrieve and work
@@ -20,16 +41,23 @@ use twilight_model::{
 #[derive(Clone, Debug, Default)]
 pub struct Cache<T: Backend> {
     backend: Arc<T>,
+    config: Config,
     /// Repository for working with attachments.
     pub attachments: T::AttachmentRepository,
+    /// Repository for working with bans.
+    pub bans: T::BanRepository,
     /// Repository for working with category channels.
     pub category_channels: T::CategoryChannelRepository,
+    /// Repository for working with the current user.
+    pub current_user: T::CurrentUserRepository,
     /// Repository for working with emojis.
     pub emojis: T::EmojiRepository,
     /// Repository for working with groups.
     pub groups: T::GroupRepository,
     /// Repository for working with guilds.
     pub guilds: T::GuildRepository,
+    /// Repository for working with invites.
+    pub invites: T::InviteRepository,
     /// Repository for working with members.
     pub members: T::MemberRepository,
     /// Repository for working with messages.
@@ -42,12 +70,16 @@ pub struct Cache<T: Backend> {
     pub roles: T::RoleRepository,
     /// Repository for working with text channels.
     pub text_channels: T::TextChannelRepository,
+    /// Repository for working with users typing in channels.
+    pub typing: T::TypingRepository,
     /// Repository for working with users.
     pub users: T::UserRepository,
     /// Repository for working with users.
     pub voice_channels: T::VoiceChannelRepository,
     /// Repository for working with voice state.
     pub voice_states: T::VoiceStateRepository,
+    /// Repository for working with webhooks.
+    pub webhooks: T::WebhookRepository,
 }
 
 impl<T: Backend + Default> Cache<T> {
@@ -59,39 +91,74 @@ impl<T: Backend + Default> Cache<T> {
 
 impl<T: Backend> Cache<T> {
     /// Create a new cache with a provided instance of the backend.
+    ///
+    /// The cache uses the default [`Config`].
+    ///
+    /// [`Config`]: config/struct.Config.html
     pub fn with_backend(backend: impl Into<Arc<T>>) -> Self {
+        Self::with_config(backend, Config::default())
+    }
+
+    /// Create a new cache with a provided instance of the backend and a
+    /// configuration for how events are processed.
+    ///
+    /// # Examples
+    ///
+    /// Create a cache that doesn't store the presences of offline users:
+    ///
+    /// ```
+    /// use rarity_cache::{config::Config, Cache};
+    /// use rarity_cache_inmemory::InMemoryBackend;
+    ///
+    /// let mut config = Config::default();
+    /// *config.evict_offline_presences_mut() = true;
+    ///
+    /// let cache = Cache::with_config(InMemoryBackend::new(), config);
+    /// ```
+    pub fn with_config(backend: impl Into<Arc<T>>, config: Config) -> Self {
         let backend = backend.into();
         let attachments = backend.attachments();
+        let bans = backend.bans();
         let category_channels = backend.category_channels();
+        let current_user = backend.current_user();
         let emojis = backend.emojis();
         let groups = backend.groups();
         let guilds = backend.guilds();
+        let invites = backend.invites();
         let members = backend.members();
         let messages = backend.messages();
         let presences = backend.presences();
         let private_channels = backend.private_channels();
         let roles = backend.roles();
         let text_channels = backend.text_channels();
+        let typing = backend.typing();
         let users = backend.users();
         let voice_channels = backend.voice_channels();
         let voice_states = backend.voice_states();
+        let webhooks = backend.webhooks();
 
         Self {
             attachments,
             backend,
+            bans,
             category_channels,
+            config,
+            current_user,
             emojis,
             groups,
             guilds,
+            invites,
             members,
             messages,
             presences,
             private_channels,
             roles,
             text_channels,
+            typing,
             users,
             voice_channels,
             voice_states,
+            webhooks,
         }
     }
 
@@ -100,6 +167,157 @@ impl<T: Backend> Cache<T> {
         &self.backend
     }
 
+    /// Return an immutable reference to the configuration.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Calculate the permissions of a member in a guild.
+    ///
+    /// Returns `None` if the guild or the member isn't in the cache. Roles
+    /// that aren't in the cache are treated as having no permissions.
+    ///
+    /// Refer to [`permission::guild`] for how permissions are calculated.
+    ///
+    /// # Examples
+    ///
+    /// Check whether a member can kick other members:
+    ///
+    /// ```no_run
+    /// use rarity_cache::Cache;
+    /// use rarity_cache_inmemory::InMemoryBackend;
+    /// use twilight_model::{
+    ///     guild::Permissions,
+    ///     id::{GuildId, UserId},
+    /// };
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache: Cache<InMemoryBackend> = Cache::new();
+    ///
+    /// if let Some(permissions) = cache.guild_permissions(GuildId(1), UserId(2)).await? {
+    ///     println!("can kick: {}", permissions.contains(Permissions::KICK_MEMBERS));
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// [`permission::guild`]: permission/fn.guild.html
+    pub async fn guild_permissions(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<Permissions>, T::Error> {
+        let Some(guild) = self.guilds.get(guild_id).await? else {
+            return Ok(None);
+        };
+
+        if guild.owner_id == user_id {
+            return Ok(Some(Permissions::all()));
+        }
+
+        let Some(member) = self.members.get((guild_id, user_id)).await? else {
+            return Ok(None);
+        };
+
+        let roles = self.member_roles(&member).await?;
+
+        Ok(Some(permission::guild(&guild, &member, &roles)))
+    }
+
+    /// Calculate the permissions of a member in a guild channel.
+    ///
+    /// The channel may be a text, voice, or category channel. Returns `None` if
+    /// the channel, its guild, or the member isn't in the cache. Roles that
+    /// aren't in the cache are treated as having no permissions.
+    ///
+    /// Refer to [`permission::channel`] for how permissions are calculated.
+    ///
+    /// # Examples
+    ///
+    /// Check whether a member can send messages in a channel:
+    ///
+    /// ```no_run
+    /// use rarity_cache::Cache;
+    /// use rarity_cache_inmemory::InMemoryBackend;
+    /// use twilight_model::{
+    ///     guild::Permissions,
+    ///     id::{ChannelId, UserId},
+    /// };
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache: Cache<InMemoryBackend> = Cache::new();
+    ///
+    /// let permissions = cache.channel_permissions(ChannelId(1), UserId(2)).await?;
+    ///
+    /// if permissions.map_or(false, |p| p.contains(Permissions::SEND_MESSAGES)) {
+    ///     println!("the member can send messages");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// [`permission::channel`]: permission/fn.channel.html
+    pub async fn channel_permissions(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<Option<Permissions>, T::Error> {
+        let (guild_id, kind, overwrites) =
+            if let Some(channel) = self.text_channels.get(channel_id).await? {
+                (
+                    channel.guild_id,
+                    channel.kind,
+                    channel.permission_overwrites,
+                )
+            } else if let Some(channel) = self.voice_channels.get(channel_id).await? {
+                (
+                    channel.guild_id,
+                    channel.kind,
+                    channel.permission_overwrites,
+                )
+            } else if let Some(channel) = self.category_channels.get(channel_id).await? {
+                (
+                    channel.guild_id,
+                    channel.kind,
+                    channel.permission_overwrites,
+                )
+            } else {
+                return Ok(None);
+            };
+
+        let Some(guild_id) = guild_id else {
+            return Ok(None);
+        };
+
+        let Some(guild) = self.guilds.get(guild_id).await? else {
+            return Ok(None);
+        };
+
+        if guild.owner_id == user_id {
+            return Ok(Some(Permissions::all()));
+        }
+
+        let Some(member) = self.members.get((guild_id, user_id)).await? else {
+            return Ok(None);
+        };
+
+        let roles = self.member_roles(&member).await?;
+
+        Ok(Some(permission::channel(
+            &guild,
+            &member,
+            &roles,
+            kind,
+            &overwrites,
+        )))
+    }
+
     /// Update the cache with an event.
     ///
     /// # Examples
@@ -131,10 +349,22 @@ impl<T: Backend> Cache<T> {
     /// # Errors
     ///
     /// Returns a backend error if a backend repository operation errors.
+    #[allow(clippy::too_many_lines)]
     pub async fn update(&self, event: &Event) -> Result<(), T::Error> {
         match event {
-            Event::BanAdd(_) => {}
-            Event::BanRemove(_) => {}
+            Event::BanAdd(ban) => {
+                let entity = BanEntity {
+                    guild_id: ban.guild_id,
+                    reason: None,
+                    user_id: ban.user.id,
+                };
+
+                future::try_join(self.bans.upsert(entity), self.upsert_user(ban.user.clone()))
+                    .await?;
+            }
+            Event::BanRemove(ban) => {
+                self.bans.remove((ban.guild_id, ban.user.id)).await?;
+            }
             Event::ChannelCreate(channel) => match &channel.0 {
                 Channel::Group(group) => {
                     let entity = GroupEntity::from(group.clone());
@@ -170,7 +400,7 @@ impl<T: Backend> Cache<T> {
                     self.category_channels.remove(c.id).await?;
                 }
                 Channel::Guild(GuildChannel::Text(c)) => {
-                    self.text_channels.remove(c.id).await?;
+                    self.text_channels.remove_cascade(c.id).await?;
                 }
                 Channel::Guild(GuildChannel::Voice(c)) => {
                     self.voice_channels.remove(c.id).await?;
@@ -179,18 +409,12 @@ impl<T: Backend> Cache<T> {
                     self.private_channels.remove(c.id).await?;
                 }
             },
-            //     Event::ChannelPinsUpdate(pins) => {
-            //         let id = EntityId::Primary(pins.channel_id.0);
-            //         let record = Record::new(id, ResourceType::Channel);
-            //         let field = Field::Message(MessageField::LastPinTimestamp(pins.last_pin_timestamp.as_deref()));
-
-            //         self.backend.upsert_field(FieldUpdate::new(record, field)).await?;
-            //     },
-            //     Event::ChannelUpdate(channel) => {
-            //         self.backend.upsert(ResourceUpsert::Channel(ChannelUpsert {
-            //             inner: channel,
-            //         })).await?;
-            //     },
+            Event::ChannelPinsUpdate(pins) => {
+                self.update_channel_pins(pins).await?;
+            }
+            Event::ChannelUpdate(channel) => {
+                self.update_channel(&channel.0).await?;
+            }
             // Ignore non-dispatch gateway events.
             Event::GatewayHeartbeat(_) => {}
             Event::GatewayHeartbeatAck => {}
@@ -198,49 +422,147 @@ impl<T: Backend> Cache<T> {
             Event::GatewayInvalidateSession(_) => {}
             Event::GatewayReconnect => {}
             Event::GiftCodeUpdate => {}
-            Event::InviteCreate(_) => {}
-            Event::InviteDelete(_) => {}
+            Event::GuildCreate(guild) => {
+                self.create_guild(guild.0.clone()).await?;
+            }
+            Event::GuildDelete(guild) => {
+                if guild.unavailable {
+                    self.mark_guild_unavailable(guild.id).await?;
+                } else {
+                    self.guilds.remove_cascade(guild.id).await?;
+                }
+            }
+            Event::GuildEmojisUpdate(update) => {
+                self.update_emojis(update).await?;
+            }
+            // Integrations aren't cached.
+            Event::GuildIntegrationsUpdate(_) => {}
+            Event::GuildUpdate(guild) => {
+                self.update_guild(guild.0.clone()).await?;
+            }
+            Event::InviteCreate(invite) => {
+                let entity = InviteEntity::from(invite.as_ref().clone());
+
+                if let Some(inviter) = invite.inviter.clone() {
+                    future::try_join(self.invites.upsert(entity), self.upsert_user(inviter))
+                        .await?;
+                } else {
+                    self.invites.upsert(entity).await?;
+                }
+            }
+            Event::InviteDelete(invite) => {
+                self.invites.remove(invite.code.clone()).await?;
+            }
             Event::MemberAdd(member) => {
                 let entity = MemberEntity::from(member.0.clone());
+                let user = UserEntity::from(member.user.clone());
 
-                self.members.upsert(entity);
+                future::try_join(self.members.upsert(entity), self.users.upsert(user)).await?;
             }
             Event::MemberChunk(chunk) => {
-                future::try_join_all(chunk.members.values().map(|member| {
-                    let entity = MemberEntity::from(member.clone());
+                let members = chunk
+                    .members
+                    .values()
+                    .cloned()
+                    .map(MemberEntity::from)
+                    .collect::<Vec<_>>();
+                let users = chunk
+                    .members
+                    .values()
+                    .map(|member| UserEntity::from(member.user.clone()))
+                    .collect::<Vec<_>>();
 
-                    self.members.upsert(entity)
-                }))
+                future::try_join(
+                    self.members.upsert_bulk(members.into_iter()),
+                    self.users.upsert_bulk(users.into_iter()),
+                )
                 .await?;
             }
-            // Event::MemberUpdate(member) => {
-            //     let entity = MemberEntity::from(&member.0);
-
-            //     self.members.upsert(entity);
-            // },
-            //     // Event::MessageCreate(message) => {
-            //     //     self.backend.upsert(ResourceUpsert::Message(MessageUpsert {
-            //     //         inner: message,
-            //     //     })).await?;
-            //     // },
-            //     Event::MessageDelete(message) => {
-            //         let id = EntityId::Primary(message.id.0);
-            //         let record = Record::new(id, ResourceType::Message);
-
-            //         self.backend.delete(record).await?;
-            //     },
-            //     Event::MessageDeleteBulk(bulk) => {
-            //         self.backend.delete_bulk(bulk.ids.iter().map(|id| {
-            //             Record::new(EntityId::Primary(id.0), ResourceType::Message)
-            //         })).await?;
-            //     },
-            //     Event::MessageUpdate(message) => {
-            //         self.backend.upsert(ResourceUpsert::Message(MessageUpsert {
-            //             inner: message,
-            //         })).await?;
-            //     },
-            Event::Ready(_) => {}
+            Event::MemberRemove(member) => {
+                self.members
+                    .remove((member.guild_id, member.user.id))
+                    .await?;
+            }
+            Event::MemberUpdate(update) => {
+                self.update_member(update).await?;
+            }
+            Event::MessageCreate(message) => {
+                let entities = MessageEntities::from(message.0.clone());
+                let users = Some(entities.author)
+                    .into_iter()
+                    .chain(entities.mentions)
+                    .collect::<Vec<_>>();
+
+                // Sending a message ends the author's typing state.
+                let typing_id = (message.channel_id, message.author.id);
+
+                future::try_join4(
+                    self.users.upsert_bulk(users.into_iter()),
+                    self.attachments
+                        .upsert_bulk(entities.attachments.into_iter()),
+                    self.messages.upsert(entities.message),
+                    self.typing.remove(typing_id),
+                )
+                .await?;
+            }
+            Event::MessageDelete(message) => {
+                self.messages.remove_cascade(message.id).await?;
+            }
+            Event::MessageDeleteBulk(bulk) => {
+                self.messages
+                    .remove_cascade_bulk(bulk.ids.iter().copied())
+                    .await?;
+            }
+            Event::MessageUpdate(update) => {
+                self.update_message(update).await?;
+            }
+            Event::PresenceUpdate(presence) => {
+                self.update_presence(presence).await?;
+            }
+            // The payload doesn't contain any presences in this version of the
+            // gateway models.
+            Event::PresencesReplace => {}
+            Event::ReactionAdd(reaction) => {
+                self.update_reaction(&reaction.0, true).await?;
+            }
+            Event::ReactionRemove(reaction) => {
+                self.update_reaction(&reaction.0, false).await?;
+            }
+            Event::ReactionRemoveAll(reactions) => {
+                self.remove_reactions(reactions.message_id, None).await?;
+            }
+            Event::ReactionRemoveEmoji(reactions) => {
+                let emoji = match reactions.emoji.id {
+                    Some(id) => ReactionType::Custom {
+                        animated: false,
+                        id,
+                        name: Some(reactions.emoji.name.clone()),
+                    },
+                    None => ReactionType::Unicode {
+                        name: reactions.emoji.name.clone(),
+                    },
+                };
+
+                self.remove_reactions(reactions.message_id, Some(emoji))
+                    .await?;
+            }
+            Event::Ready(ready) => {
+                self.ready(ready).await?;
+            }
             Event::Resumed => {}
+            Event::RoleCreate(role) => {
+                let entity = RoleEntity::from((role.guild_id, role.role.clone()));
+
+                self.roles.upsert(entity).await?;
+            }
+            Event::RoleDelete(role) => {
+                self.delete_role(role.guild_id, role.role_id).await?;
+            }
+            Event::RoleUpdate(role) => {
+                let entity = RoleEntity::from((role.guild_id, role.role.clone()));
+
+                self.roles.upsert(entity).await?;
+            }
             // Ignore shard events.
             Event::ShardConnected(_) => {}
             Event::ShardConnecting(_) => {}
@@ -249,23 +571,665 @@ impl<T: Backend> Cache<T> {
             Event::ShardPayload(_) => {}
             Event::ShardReconnecting(_) => {}
             Event::ShardResuming(_) => {}
-            Event::TypingStart(_) => {}
-            Event::UnavailableGuild(_) => {}
-            //     Event::UserUpdate(current_user) => {
-            //         self.backend.upsert(ResourceUpsert::UserCurrent(UserCurrentUpsert {
-            //             inner: current_user,
-            //         })).await?;
-            //     },
-            //     Event::VoiceServerUpdate(_) => {},
-            //     Event::VoiceStateUpdate(voice_state) => {
-            //         self.backend.upsert(ResourceUpsert::VoiceState(VoiceStateUpsert {
-            //             inner: &voice_state.0,
-            //         })).await?;
-            //     }
-            //     Event::WebhooksUpdate(_) => {},
-            _ => {}
+            Event::TypingStart(typing) => {
+                let entity = TypingEntity::from(typing.as_ref().clone());
+
+                if !entity.is_expired() {
+                    self.typing.upsert(entity).await?;
+                }
+            }
+            Event::UnavailableGuild(guild) => {
+                self.mark_guild_unavailable(guild.id).await?;
+            }
+            Event::UserUpdate(update) => {
+                let entity = CurrentUserEntity::from(update.0.clone());
+
+                self.current_user.upsert(entity).await?;
+            }
+            Event::VoiceServerUpdate(_) => {}
+            Event::VoiceStateUpdate(state) => {
+                self.update_voice_state(&state.0).await?;
+            }
+            Event::WebhooksUpdate(update) => {
+                // Staleness is only tracked for channels that are cached.
+                if self.text_channels.get(update.channel_id).await?.is_some() {
+                    self.webhooks.set_stale(update.channel_id, true).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Upsert a guild and fan out all of the entities contained within it.
+    async fn create_guild(&self, guild: Guild) -> Result<(), T::Error> {
+        let guild_id = guild.id;
+        let mut users = HashMap::new();
+
+        let mut category_channels = Vec::new();
+        let mut text_channels = Vec::new();
+        let mut voice_channels = Vec::new();
+
+        for channel in guild.channels.values().cloned() {
+            match channel {
+                GuildChannel::Category(mut c) => {
+                    c.guild_id.replace(guild_id);
+                    category_channels.push(CategoryChannelEntity::from(c));
+                }
+                GuildChannel::Text(mut c) => {
+                    c.guild_id.replace(guild_id);
+                    text_channels.push(TextChannelEntity::from(c));
+                }
+                GuildChannel::Voice(mut c) => {
+                    c.guild_id.replace(guild_id);
+                    voice_channels.push(VoiceChannelEntity::from(c));
+                }
+            }
         }
 
+        let emojis = guild
+            .emojis
+            .values()
+            .cloned()
+            .map(|emoji| {
+                if let Some(user) = emoji.user.clone() {
+                    users.insert(user.id, UserEntity::from(user));
+                }
+
+                EmojiEntity::from((guild_id, emoji))
+            })
+            .collect::<Vec<_>>();
+
+        let members = guild
+            .members
+            .values()
+            .cloned()
+            .map(|member| {
+                users.insert(member.user.id, UserEntity::from(member.user.clone()));
+
+                MemberEntity::from(member)
+            })
+            .collect::<Vec<_>>();
+
+        let evict_offline = self.config.evict_offline_presences();
+        let presences = guild
+            .presences
+            .values()
+            .filter(|presence| !(evict_offline && presence.status == Status::Offline))
+            .cloned()
+            .map(|presence| {
+                if let UserOrId::User(user) = &presence.user {
+                    users
+                        .entry(user.id)
+                        .or_insert_with(|| UserEntity::from(user.clone()));
+                }
+
+                PresenceEntity::from(presence)
+            })
+            .collect::<Vec<_>>();
+
+        let roles = guild
+            .roles
+            .values()
+            .cloned()
+            .map(|role| RoleEntity::from((guild_id, role)))
+            .collect::<Vec<_>>();
+
+        let voice_states = guild
+            .voice_states
+            .values()
+            .cloned()
+            .map(|state| VoiceStateEntity::from((guild_id, state)))
+            .collect::<Vec<_>>();
+
+        let entity = GuildEntity::from(guild);
+
+        future::try_join_all(vec![
+            self.guilds.upsert(entity),
+            self.category_channels
+                .upsert_bulk(category_channels.into_iter()),
+            self.text_channels.upsert_bulk(text_channels.into_iter()),
+            self.voice_channels.upsert_bulk(voice_channels.into_iter()),
+            self.emojis.upsert_bulk(emojis.into_iter()),
+            self.users.upsert_bulk(users.into_values()),
+            self.members.upsert_bulk(members.into_iter()),
+            self.presences.upsert_bulk(presences.into_iter()),
+            self.roles.upsert_bulk(roles.into_iter()),
+            self.voice_states.upsert_bulk(voice_states.into_iter()),
+        ])
+        .await?;
+
         Ok(())
     }
+
+    /// Remove a role and strip its ID from the cached members and emojis of
+    /// its guild.
+    async fn delete_role(&self, guild_id: GuildId, role_id: RoleId) -> Result<(), T::Error> {
+        let user_ids = self
+            .guilds
+            .members(guild_id)
+            .await?
+            .try_filter_map(|member| {
+                let had_role = member.role_ids.contains(&role_id);
+
+                future::ok(Some(member.user_id).filter(|_| had_role))
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        let emojis = self
+            .guilds
+            .emojis(guild_id)
+            .await?
+            .try_filter_map(|mut emoji| {
+                let had_role = emoji.role_ids.contains(&role_id);
+
+                emoji.role_ids.retain(|id| *id != role_id);
+
+                future::ok(Some(emoji).filter(|_| had_role))
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        future::try_join3(
+            future::try_join_all(user_ids.into_iter().map(|user_id| {
+                let field = MemberField::RemoveRoleId(role_id);
+
+                self.members.update_field((guild_id, user_id), field)
+            })),
+            self.emojis.upsert_bulk(emojis.into_iter()),
+            self.roles.remove(role_id),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a cached guild as unavailable, such as during an outage.
+    ///
+    /// The guild's entities are kept, since the guild will become available
+    /// again later.
+    async fn mark_guild_unavailable(&self, guild_id: GuildId) -> Result<(), T::Error> {
+        let Some(mut guild) = self.guilds.get(guild_id).await? else {
+            return Ok(());
+        };
+
+        guild.unavailable = true;
+
+        self.guilds.upsert(guild).await
+    }
+
+    /// Retrieve the cached @everyone role of a member's guild and the cached
+    /// roles of the member.
+    async fn member_roles(&self, member: &MemberEntity) -> Result<Vec<RoleEntity>, T::Error> {
+        let everyone_id = RoleId(member.guild_id.0);
+
+        let roles = future::try_join_all(
+            std::iter::once(everyone_id)
+                .chain(member.role_ids.iter().copied())
+                .map(|role_id| self.roles.get(role_id)),
+        )
+        .await?;
+
+        Ok(roles.into_iter().flatten().collect())
+    }
+
+    /// Upsert the current user and the guilds that the current user is in.
+    ///
+    /// Available guilds are created in full. Unavailable guilds that aren't
+    /// cached yet are stored as placeholders, while cached ones are marked as
+    /// unavailable until their `GuildCreate` event is received.
+    ///
+    /// The payload doesn't contain private channels in this version of the
+    /// gateway models, so they're cached as their `ChannelCreate` events are
+    /// received.
+    async fn ready(&self, ready: &Ready) -> Result<(), T::Error> {
+        let entity = CurrentUserEntity::from(ready.user.clone());
+        self.current_user.upsert(entity).await?;
+
+        for status in ready.guilds.values() {
+            match status {
+                GuildStatus::Online(guild) => self.create_guild(guild.clone()).await?,
+                GuildStatus::Offline(guild) => {
+                    if self.guilds.get(guild.id).await?.is_some() {
+                        self.mark_guild_unavailable(guild.id).await?;
+                    } else {
+                        self.guilds.upsert(GuildEntity::from(guild.clone())).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the reactions of a message, either all of them or only those
+    /// with an emoji.
+    async fn remove_reactions(
+        &self,
+        message_id: MessageId,
+        emoji: Option<ReactionType>,
+    ) -> Result<(), T::Error> {
+        let field = match emoji {
+            Some(emoji) => MessageField::RemoveReactionEmoji(emoji),
+            None => MessageField::RemoveReactions,
+        };
+
+        self.messages.update_field(message_id, field).await?;
+
+        Ok(())
+    }
+
+    /// Upsert an updated channel.
+    ///
+    /// Guild channels are removed from the repositories of the other kinds of
+    /// guild channels before being upserted, so that a channel whose kind
+    /// changed is only in the repository of its new kind and stays indexed
+    /// in its guild. Text channels are removed along with their messages and
+    /// webhooks.
+    async fn update_channel(&self, channel: &Channel) -> Result<(), T::Error> {
+        match channel {
+            Channel::Group(group) => {
+                self.groups.upsert(GroupEntity::from(group.clone())).await?;
+            }
+            Channel::Guild(GuildChannel::Category(c)) => {
+                future::try_join(
+                    self.text_channels.remove_cascade(c.id),
+                    self.voice_channels.remove(c.id),
+                )
+                .await?;
+
+                self.category_channels
+                    .upsert(CategoryChannelEntity::from(c.clone()))
+                    .await?;
+            }
+            Channel::Guild(GuildChannel::Text(c)) => {
+                future::try_join(
+                    self.category_channels.remove(c.id),
+                    self.voice_channels.remove(c.id),
+                )
+                .await?;
+
+                self.text_channels
+                    .upsert(TextChannelEntity::from(c.clone()))
+                    .await?;
+            }
+            Channel::Guild(GuildChannel::Voice(c)) => {
+                future::try_join(
+                    self.category_channels.remove(c.id),
+                    self.text_channels.remove_cascade(c.id),
+                )
+                .await?;
+
+                self.voice_channels
+                    .upsert(VoiceChannelEntity::from(c.clone()))
+                    .await?;
+            }
+            Channel::Private(c) => {
+                self.private_channels
+                    .upsert(PrivateChannelEntity::from(c.clone()))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update the last pin timestamp of a cached text, private, or group
+    /// channel.
+    ///
+    /// Updates for channels that aren't in the cache are ignored.
+    async fn update_channel_pins(&self, pins: &ChannelPinsUpdate) -> Result<(), T::Error> {
+        let last_pin_timestamp = pins.last_pin_timestamp.clone();
+        let field = TextChannelField::LastPinTimestamp(last_pin_timestamp.clone());
+
+        // Only text channels can be in guilds.
+        if self
+            .text_channels
+            .update_field(pins.channel_id, field)
+            .await?
+            || pins.guild_id.is_some()
+        {
+            return Ok(());
+        }
+
+        let field = PrivateChannelField::LastPinTimestamp(last_pin_timestamp.clone());
+
+        if self
+            .private_channels
+            .update_field(pins.channel_id, field)
+            .await?
+        {
+            return Ok(());
+        }
+
+        let field = GroupField::LastPinTimestamp(last_pin_timestamp);
+        self.groups.update_field(pins.channel_id, field).await?;
+
+        Ok(())
+    }
+
+    /// Replace the cached emojis of a guild with the updated emoji list.
+    ///
+    /// Cached emojis that aren't in the list anymore have been deleted, so
+    /// they're removed.
+    async fn update_emojis(&self, update: &GuildEmojisUpdate) -> Result<(), T::Error> {
+        let removed = self
+            .guilds
+            .emoji_ids(update.guild_id)
+            .await?
+            .try_filter(|id| future::ready(!update.emojis.contains_key(id)))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut users = HashMap::new();
+        let emojis = update
+            .emojis
+            .values()
+            .cloned()
+            .map(|emoji| {
+                if let Some(user) = emoji.user.clone() {
+                    users.insert(user.id, UserEntity::from(user));
+                }
+
+                EmojiEntity::from((update.guild_id, emoji))
+            })
+            .collect::<Vec<_>>();
+
+        future::try_join3(
+            self.emojis.remove_bulk(removed.into_iter()),
+            self.emojis.upsert_bulk(emojis.into_iter()),
+            self.users.upsert_bulk(users.into_values()),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Merge a partial guild update into the cached guild.
+    ///
+    /// Fields that aren't included in the partial guild, such as when the
+    /// current user joined, are preserved. The guild's roles and emojis are
+    /// upserted even if the guild itself isn't cached, and cached roles and
+    /// emojis that are missing from the update are removed.
+    async fn update_guild(&self, partial: PartialGuild) -> Result<(), T::Error> {
+        let guild_id = partial.id;
+
+        let removed_emojis = self
+            .guilds
+            .emoji_ids(guild_id)
+            .await?
+            .try_filter(|id| future::ready(!partial.emojis.contains_key(id)))
+            .try_collect::<Vec<_>>()
+            .await?;
+        let removed_roles = self
+            .guilds
+            .role_ids(guild_id)
+            .await?
+            .try_filter(|id| future::ready(!partial.roles.contains_key(id)))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let emojis = partial
+            .emojis
+            .values()
+            .cloned()
+            .map(|emoji| EmojiEntity::from((guild_id, emoji)))
+            .collect::<Vec<_>>();
+        let roles = partial
+            .roles
+            .values()
+            .cloned()
+            .map(|role| RoleEntity::from((guild_id, role)))
+            .collect::<Vec<_>>();
+
+        future::try_join4(
+            self.emojis.remove_bulk(removed_emojis.into_iter()),
+            self.emojis.upsert_bulk(emojis.into_iter()),
+            self.roles.remove_bulk(removed_roles.into_iter()),
+            self.roles.upsert_bulk(roles.into_iter()),
+        )
+        .await?;
+
+        let Some(mut guild) = self.guilds.get(guild_id).await? else {
+            return Ok(());
+        };
+
+        guild.afk_channel_id = partial.afk_channel_id;
+        guild.afk_timeout = partial.afk_timeout;
+        guild.application_id = partial.application_id;
+        guild.banner = partial.banner;
+        guild.default_message_notifications = partial.default_message_notifications;
+        guild.description = partial.description;
+        guild.discovery_splash = partial.discovery_splash;
+        guild.embed_channel_id = partial.embed_channel_id;
+        guild.embed_enabled.replace(partial.embed_enabled);
+        guild.explicit_content_filter = partial.explicit_content_filter;
+        guild.features = partial.features;
+        guild.icon = partial.icon;
+        guild.max_members = partial.max_members;
+        guild.max_presences = partial.max_presences;
+        guild.member_count = partial.member_count.or(guild.member_count);
+        guild.mfa_level = partial.mfa_level;
+        guild.name = partial.name;
+        guild.owner_id = partial.owner_id;
+        guild.owner = partial.owner;
+        guild.permissions = partial.permissions;
+        guild.preferred_locale = partial.preferred_locale;
+        guild.premium_subscription_count = partial.premium_subscription_count;
+        guild.premium_tier = partial.premium_tier;
+        guild.region = partial.region;
+        guild.rules_channel_id = partial.rules_channel_id;
+        guild.splash = partial.splash;
+        guild.system_channel_flags = partial.system_channel_flags;
+        guild.system_channel_id = partial.system_channel_id;
+        guild.vanity_url_code = partial.vanity_url_code;
+        guild.verification_level = partial.verification_level;
+        guild.widget_channel_id = partial.widget_channel_id;
+        guild.widget_enabled = partial.widget_enabled;
+
+        self.guilds.upsert(guild).await
+    }
+
+    /// Merge a member update into the cached member and upsert the member's
+    /// user.
+    ///
+    /// Updates for members that aren't in the cache are ignored, since the
+    /// payload doesn't contain whether the member is deafened or muted.
+    async fn update_member(&self, update: &MemberUpdate) -> Result<(), T::Error> {
+        let user = UserEntity::from(update.user.clone());
+        let fields = vec![
+            MemberField::Nick(update.nick.clone()),
+            MemberField::PremiumSince(update.premium_since.clone()),
+            MemberField::RoleIds(update.roles.clone()),
+        ];
+
+        future::try_join(
+            self.members
+                .update_field((update.guild_id, update.user.id), fields),
+            self.users.upsert(user),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Merge a partial message update into the cached message.
+    ///
+    /// Updates for messages that aren't in the cache are ignored, since the
+    /// payload doesn't contain enough information to create a full entity.
+    async fn update_message(&self, update: &MessageUpdate) -> Result<(), T::Error> {
+        let Some(mut message) = self.messages.get(update.id).await? else {
+            return Ok(());
+        };
+
+        if let Some(attachments) = &update.attachments {
+            let removed = message
+                .attachments
+                .iter()
+                .filter(|id| !attachments.iter().any(|attachment| attachment.id == **id))
+                .copied()
+                .collect::<Vec<_>>();
+            let entities = attachments
+                .iter()
+                .cloned()
+                .map(|attachment| AttachmentEntity::from((update.id, attachment)))
+                .collect::<Vec<_>>();
+
+            message.attachments = attachments.iter().map(|attachment| attachment.id).collect();
+
+            self.attachments.remove_bulk(removed.into_iter()).await?;
+            self.attachments.upsert_bulk(entities.into_iter()).await?;
+        }
+
+        if let Some(author) = &update.author {
+            message.author_id = author.id;
+
+            self.users.upsert(UserEntity::from(author.clone())).await?;
+        }
+
+        if let Some(content) = &update.content {
+            message.content = content.clone();
+        }
+
+        if let Some(edited_timestamp) = &update.edited_timestamp {
+            message.edited_timestamp.replace(edited_timestamp.clone());
+        }
+
+        if let Some(embeds) = &update.embeds {
+            message.embeds = embeds.clone();
+        }
+
+        if let Some(kind) = update.kind {
+            message.kind = kind;
+        }
+
+        if let Some(mention_everyone) = update.mention_everyone {
+            message.mention_everyone = mention_everyone;
+        }
+
+        if let Some(mention_roles) = &update.mention_roles {
+            message.mention_roles = mention_roles.clone();
+        }
+
+        if let Some(mentions) = &update.mentions {
+            message.mentions = mentions.iter().map(|user| user.id).collect();
+        }
+
+        if let Some(pinned) = update.pinned {
+            message.pinned = pinned;
+        }
+
+        if let Some(timestamp) = &update.timestamp {
+            message.timestamp = timestamp.clone();
+        }
+
+        if let Some(tts) = update.tts {
+            message.tts = tts;
+        }
+
+        self.messages.upsert(message).await
+    }
+
+    /// Upsert an updated presence and merge the user it carries into the
+    /// cached user.
+    ///
+    /// If the user is offline and offline presences are evicted, the presence
+    /// is removed instead.
+    async fn update_presence(&self, presence: &PresenceUpdate) -> Result<(), T::Error> {
+        let user_id = match &presence.user {
+            UserOrId::User(user) => {
+                self.upsert_user(user.clone()).await?;
+
+                user.id
+            }
+            UserOrId::UserId { id } => *id,
+        };
+
+        if self.config.evict_offline_presences() && presence.status == Status::Offline {
+            return self.presences.remove((presence.guild_id, user_id)).await;
+        }
+
+        self.presences
+            .upsert(PresenceEntity {
+                activities: presence.activities.clone(),
+                client_status: presence.client_status.clone(),
+                game: presence.game.clone(),
+                guild_id: presence.guild_id,
+                nick: presence.nick.clone(),
+                status: presence.status,
+                user_id,
+            })
+            .await
+    }
+
+    /// Add or remove a user's reaction on a cached message.
+    ///
+    /// Whether the reaction is the current user's is determined by the cached
+    /// current user.
+    async fn update_reaction(&self, reaction: &Reaction, added: bool) -> Result<(), T::Error> {
+        let emoji = reaction.emoji.clone();
+        let me = self
+            .current_user
+            .current()
+            .await?
+            .is_some_and(|user| user.id == reaction.user_id);
+        let user_id = reaction.user_id;
+
+        let field = if added {
+            MessageField::AddReaction { emoji, me, user_id }
+        } else {
+            MessageField::RemoveReaction { emoji, me, user_id }
+        };
+
+        self.messages
+            .update_field(reaction.message_id, field)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Upsert a voice state, or remove it if the user disconnected from voice.
+    ///
+    /// The member carried by the voice state is upserted along with its user.
+    /// Voice states outside of guilds aren't cached.
+    async fn update_voice_state(&self, state: &VoiceState) -> Result<(), T::Error> {
+        let Some(guild_id) = state.guild_id else {
+            return Ok(());
+        };
+
+        if let Some(member) = &state.member {
+            future::try_join(
+                self.members.upsert(MemberEntity::from(member.clone())),
+                self.users.upsert(UserEntity::from(member.user.clone())),
+            )
+            .await?;
+        }
+
+        if state.channel_id.is_none() {
+            return self.voice_states.remove((guild_id, state.user_id)).await;
+        }
+
+        self.voice_states
+            .upsert(VoiceStateEntity::from((guild_id, state.clone())))
+            .await
+    }
+
+    /// Upsert a user, keeping the cached values of optional fields that the
+    /// user doesn't include, such as when the user is from a presence.
+    async fn upsert_user(&self, user: User) -> Result<(), T::Error> {
+        let mut entity = UserEntity::from(user);
+
+        if let Some(cached) = self.users.get(entity.id).await? {
+            entity.email = entity.email.or(cached.email);
+            entity.flags = entity.flags.or(cached.flags);
+            entity.locale = entity.locale.or(cached.locale);
+            entity.mfa_enabled = entity.mfa_enabled.or(cached.mfa_enabled);
+            entity.premium_type = entity.premium_type.or(cached.premium_type);
+            entity.public_flags = entity.public_flags.or(cached.public_flags);
+            entity.system = entity.system.or(cached.system);
+            entity.verified = entity.verified.or(cached.verified);
+        }
+
+        self.users.upsert(entity).await
+    }
 }
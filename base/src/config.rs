@@ -0,0 +1,33 @@
+//! Configuration for how the [`Cache`] processes events.
+//!
+//! [`Cache`]: ../struct.Cache.html
+
+/// Configuration for how the cache processes events, independent of the
+/// backend.
+///
+/// Refer to each setter method to know the default value.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    evict_offline_presences: bool,
+}
+
+impl Config {
+    /// Returns whether presences of offline users are removed instead of
+    /// stored.
+    pub fn evict_offline_presences(&self) -> bool {
+        self.evict_offline_presences
+    }
+
+    /// Returns a mutable reference to whether presences of offline users are
+    /// removed instead of stored.
+    ///
+    /// Enabling this reduces the number of presences stored in large guilds,
+    /// where most members are usually offline. The presence of a user that
+    /// goes offline is removed, so the user has no cached presence rather than
+    /// a presence with an offline status.
+    ///
+    /// Defaults to `false`.
+    pub fn evict_offline_presences_mut(&mut self) -> &mut bool {
+        &mut self.evict_offline_presences
+    }
+}
@@ -0,0 +1,835 @@
+//! Conformance suite checking that a backend implements the repository
+//! semantics the cache relies on.
+//!
+//! Backends are free to store entities however they like, but the [`Cache`]
+//! and users of it expect every backend to behave the same way: retrieving or
+//! removing an entity that isn't cached isn't an error, removing an entity
+//! removes it from the indexes of related entities, and so on. The [`Suite`]
+//! runs a standard battery of scenarios against a backend to check this.
+//!
+//! This module is only available with the `conformance` feature, which
+//! backend implementations should enable in their `dev-dependencies`.
+//!
+//! # Examples
+//!
+//! Run the suite against the in-memory backend in a test:
+//!
+//! ```rust,no_run
+//! use rarity_cache::conformance::Suite;
+//! use rarity_cache_inmemory::InMemoryBackend;
+//!
+//! # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! Suite::new(InMemoryBackend::new).run().await?;
+//! # Ok(()) }
+//! ```
+//!
+//! [`Cache`]: ../struct.Cache.html
+//! [`Suite`]: struct.Suite.html
+
+use crate::{
+    entity::{
+        channel::{
+            MessageEntity, MessageRepository, TextChannelEntity, TextChannelField,
+            TextChannelRepository,
+        },
+        guild::{
+            EmojiEntity, GuildEntity, GuildRepository, MemberEntity, MemberField, RoleEntity,
+            RoleRepository,
+        },
+        user::UserEntity,
+    },
+    repository::ListEntitiesFuture,
+    Backend, Cache, Entity, Repository,
+};
+use futures_util::stream::TryStreamExt;
+use std::{collections::HashMap, fmt::Debug};
+use twilight_model::{
+    channel::{
+        message::{Message, MessageType},
+        Channel, ChannelType, GuildChannel, TextChannel,
+    },
+    gateway::{
+        event::Event,
+        payload::{ChannelDelete, GuildCreate, GuildDelete, MemberRemove, MessageCreate},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, Emoji, ExplicitContentFilter, Guild, Member, MfaLevel,
+        Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId},
+    user::User,
+};
+
+/// Suite of scenarios to run against a backend.
+///
+/// Each scenario is run against a new instance of the backend created by the
+/// factory, so scenarios don't see the entities of other scenarios.
+///
+/// Assertions failing will panic, so the suite is meant to be run within a
+/// test.
+#[derive(Clone, Debug)]
+pub struct Suite<F> {
+    factory: F,
+    stores_entities: bool,
+}
+
+impl<B: Backend, F: Fn() -> B> Suite<F> {
+    /// Create a new suite with a factory returning new instances of the
+    /// backend.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            stores_entities: true,
+        }
+    }
+
+    /// Set whether the backend stores the entities given to it.
+    ///
+    /// Backends that don't, such as the [`NoopBackend`], are expected to
+    /// never return an entity.
+    ///
+    /// Defaults to true.
+    ///
+    /// [`NoopBackend`]: ../struct.NoopBackend.html
+    pub fn stores_entities(&mut self, stores_entities: bool) -> &mut Self {
+        self.stores_entities = stores_entities;
+
+        self
+    }
+
+    /// Run all of the scenarios.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn run(&self) -> Result<(), B::Error> {
+        self.crud().await?;
+        self.bulk().await?;
+        self.relationships().await?;
+        self.cascade().await?;
+        self.field_updates().await?;
+        self.events().await
+    }
+
+    /// Run the scenario of retrieving, listing, upserting and removing
+    /// single entities.
+    ///
+    /// Retrieving or removing an entity that isn't cached must succeed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn crud(&self) -> Result<(), B::Error> {
+        let backend = (self.factory)();
+
+        let user = UserEntity::from(user(4));
+        let updated = UserEntity {
+            name: "updated".to_owned(),
+            ..user.clone()
+        };
+        self.crud_entity(backend.users(), user, updated).await?;
+
+        let guild = GuildEntity::from(guild());
+        let updated = GuildEntity {
+            name: "updated".to_owned(),
+            ..guild.clone()
+        };
+        self.crud_entity(backend.guilds(), guild, updated).await?;
+
+        let role = RoleEntity::from((GuildId(1), role(1)));
+        let updated = RoleEntity {
+            name: "updated".to_owned(),
+            ..role.clone()
+        };
+        self.crud_entity(backend.roles(), role, updated).await?;
+
+        let member = MemberEntity::from(member(4));
+        let updated = MemberEntity {
+            nick: Some("updated".to_owned()),
+            ..member.clone()
+        };
+        self.crud_entity(backend.members(), member, updated).await?;
+
+        let channel = TextChannelEntity::from(text_channel());
+        let updated = TextChannelEntity {
+            name: "updated".to_owned(),
+            ..channel.clone()
+        };
+        self.crud_entity(backend.text_channels(), channel, updated)
+            .await?;
+
+        let message = MessageEntity::from(message(5));
+        let updated = MessageEntity {
+            content: "updated".to_owned(),
+            ..message.clone()
+        };
+        self.crud_entity(backend.messages(), message, updated).await
+    }
+
+    /// Run the scenario of upserting and removing multiple entities at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn bulk(&self) -> Result<(), B::Error> {
+        let backend = (self.factory)();
+        let guilds = backend.guilds();
+        let roles = backend.roles();
+
+        let entities = (1..=3).map(|id| RoleEntity::from((GuildId(1), role(id))));
+        roles.upsert_bulk(entities).await?;
+
+        let role_ids = self.stored_all(vec![RoleId(1), RoleId(2), RoleId(3)]);
+        assert_eq!(role_ids, entity_ids(roles.list()).await?, "bulk upsert");
+        assert_eq!(
+            role_ids,
+            ids(guilds.role_ids(GuildId(1))).await?,
+            "bulk index"
+        );
+
+        roles
+            .remove_bulk(vec![RoleId(1), RoleId(2)].into_iter())
+            .await?;
+
+        let role_ids = self.stored_all(vec![RoleId(3)]);
+        assert_eq!(role_ids, entity_ids(roles.list()).await?, "bulk remove");
+        assert_eq!(
+            role_ids,
+            ids(guilds.role_ids(GuildId(1))).await?,
+            "bulk remove index",
+        );
+
+        Ok(())
+    }
+
+    /// Run the scenario of retrieving the related entities of entities, and
+    /// of relationships being updated when entities are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn relationships(&self) -> Result<(), B::Error> {
+        let backend = (self.factory)();
+        let guilds = backend.guilds();
+        let members = backend.members();
+        let messages = backend.messages();
+        let roles = backend.roles();
+        let text_channels = backend.text_channels();
+
+        self.seed(&backend).await?;
+
+        let guild = GuildEntity::from(guild());
+        let author = UserEntity::from(user(4));
+
+        assert_eq!(
+            self.stored_all(vec![ChannelId(2)]),
+            ids(guilds.channel_ids(GuildId(1))).await?,
+            "guild channels",
+        );
+        assert_eq!(
+            self.stored_all(vec![EmojiId(3)]),
+            ids(guilds.emoji_ids(GuildId(1))).await?,
+            "guild emojis",
+        );
+        assert_eq!(
+            self.stored_all(vec![UserId(4)]),
+            ids(guilds.member_ids(GuildId(1))).await?,
+            "guild members",
+        );
+        assert_eq!(
+            self.stored_all(vec![RoleId(1)]),
+            ids(guilds.role_ids(GuildId(1))).await?,
+            "guild roles",
+        );
+        assert_eq!(
+            self.stored_all(vec![MessageId(5)]),
+            ids(text_channels.message_ids(ChannelId(2))).await?,
+            "channel messages",
+        );
+        assert_eq!(
+            self.stored(guild.clone()),
+            roles.guild(RoleId(1)).await?,
+            "role guild",
+        );
+        assert_eq!(
+            self.stored(guild),
+            TextChannelRepository::guild(&text_channels, ChannelId(2)).await?,
+            "channel guild",
+        );
+        assert_eq!(
+            self.stored(author),
+            messages.author(MessageId(5)).await?,
+            "message author",
+        );
+
+        members.remove((GuildId(1), UserId(4))).await?;
+        messages.remove(MessageId(5)).await?;
+
+        assert!(
+            ids(guilds.member_ids(GuildId(1))).await?.is_empty(),
+            "removed member index",
+        );
+        assert!(
+            ids(text_channels.message_ids(ChannelId(2)))
+                .await?
+                .is_empty(),
+            "removed message index",
+        );
+
+        Ok(())
+    }
+
+    /// Run the scenario of removing a guild along with the entities it owns.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn cascade(&self) -> Result<(), B::Error> {
+        let backend = (self.factory)();
+        let guilds = backend.guilds();
+        self.seed(&backend).await?;
+
+        guilds.remove_cascade(GuildId(1)).await?;
+
+        assert!(guilds.get(GuildId(1)).await?.is_none(), "cascade guild");
+        assert!(
+            backend.text_channels().get(ChannelId(2)).await?.is_none(),
+            "cascade channel",
+        );
+        assert!(
+            backend.emojis().get(EmojiId(3)).await?.is_none(),
+            "cascade emoji",
+        );
+        assert!(
+            backend
+                .members()
+                .get((GuildId(1), UserId(4)))
+                .await?
+                .is_none(),
+            "cascade member",
+        );
+        assert!(
+            backend.messages().get(MessageId(5)).await?.is_none(),
+            "cascade message",
+        );
+        assert!(
+            backend.roles().get(RoleId(1)).await?.is_none(),
+            "cascade role"
+        );
+        assert!(
+            ids(guilds.channel_ids(GuildId(1))).await?.is_empty(),
+            "cascade channel index",
+        );
+        assert!(
+            ids(guilds.member_ids(GuildId(1))).await?.is_empty(),
+            "cascade member index",
+        );
+        assert!(
+            ids(guilds.role_ids(GuildId(1))).await?.is_empty(),
+            "cascade role index",
+        );
+
+        Ok(())
+    }
+
+    /// Run the scenario of updating single fields of entities.
+    ///
+    /// Updating a field of an entity that isn't cached must leave it
+    /// uncached.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn field_updates(&self) -> Result<(), B::Error> {
+        let backend = (self.factory)();
+        let members = backend.members();
+        let text_channels = backend.text_channels();
+
+        let member = MemberEntity::from(member(4));
+        members.upsert(member.clone()).await?;
+
+        let nick = MemberField::Nick(Some("nick".to_owned()));
+        assert_eq!(
+            self.stores_entities,
+            members.update_field(member.id(), nick).await?,
+            "member field",
+        );
+
+        let mut updated = member;
+        updated.nick = Some("nick".to_owned());
+        assert_eq!(
+            self.stored(updated),
+            members.get((GuildId(1), UserId(4))).await?,
+            "updated member",
+        );
+
+        let missing_id = (GuildId(1), UserId(5));
+        let nick = MemberField::Nick(Some("nick".to_owned()));
+        assert!(
+            !members.update_field(missing_id, nick).await?,
+            "missing member field",
+        );
+        assert!(members.get(missing_id).await?.is_none(), "missing member",);
+
+        let channel = TextChannelEntity::from(text_channel());
+        text_channels.upsert(channel.clone()).await?;
+
+        let fields = vec![
+            TextChannelField::Topic(Some("topic".to_owned())),
+            TextChannelField::Nsfw(true),
+        ];
+        assert_eq!(
+            self.stores_entities,
+            text_channels.update_field(channel.id, fields).await?,
+            "channel fields",
+        );
+
+        let mut updated = channel;
+        updated.nsfw = true;
+        updated.topic = Some("topic".to_owned());
+        assert_eq!(
+            self.stored(updated),
+            text_channels.get(ChannelId(2)).await?,
+            "updated channel",
+        );
+
+        Ok(())
+    }
+
+    /// Run the scenario of updating the cache with gateway events.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the backend doesn't behave as expected.
+    pub async fn events(&self) -> Result<(), B::Error> {
+        let cache = Cache::with_backend((self.factory)());
+
+        cache
+            .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+            .await?;
+
+        assert_eq!(
+            self.stored(GuildEntity::from(guild())),
+            cache.guilds.get(GuildId(1)).await?,
+            "guild create",
+        );
+        assert_eq!(
+            self.stored(UserEntity::from(user(4))),
+            cache.users.get(UserId(4)).await?,
+            "guild create member user",
+        );
+        assert_eq!(
+            self.stored_all(vec![ChannelId(2)]),
+            ids(cache.guilds.channel_ids(GuildId(1))).await?,
+            "guild create channels",
+        );
+        assert_eq!(
+            self.stored_all(vec![EmojiId(3)]),
+            ids(cache.guilds.emoji_ids(GuildId(1))).await?,
+            "guild create emojis",
+        );
+        assert_eq!(
+            self.stored_all(vec![UserId(4)]),
+            ids(cache.guilds.member_ids(GuildId(1))).await?,
+            "guild create members",
+        );
+        assert_eq!(
+            self.stored_all(vec![RoleId(1)]),
+            ids(cache.guilds.role_ids(GuildId(1))).await?,
+            "guild create roles",
+        );
+
+        cache
+            .update(&Event::MessageCreate(Box::new(MessageCreate(message(5)))))
+            .await?;
+
+        assert_eq!(
+            self.stored(MessageEntity::from(message(5))),
+            cache.messages.get(MessageId(5)).await?,
+            "message create",
+        );
+        assert_eq!(
+            self.stored_all(vec![MessageId(5)]),
+            ids(cache.text_channels.message_ids(ChannelId(2))).await?,
+            "message create index",
+        );
+
+        cache
+            .update(&Event::MemberRemove(MemberRemove {
+                guild_id: GuildId(1),
+                user: user(4),
+            }))
+            .await?;
+
+        assert!(
+            cache.members.get((GuildId(1), UserId(4))).await?.is_none(),
+            "member remove",
+        );
+        assert!(
+            ids(cache.guilds.member_ids(GuildId(1))).await?.is_empty(),
+            "member remove index",
+        );
+
+        cache
+            .update(&Event::ChannelDelete(ChannelDelete(Channel::Guild(
+                GuildChannel::Text(text_channel()),
+            ))))
+            .await?;
+
+        assert!(
+            cache.text_channels.get(ChannelId(2)).await?.is_none(),
+            "channel delete",
+        );
+        assert!(
+            cache.messages.get(MessageId(5)).await?.is_none(),
+            "channel delete message",
+        );
+        assert!(
+            ids(cache.guilds.channel_ids(GuildId(1))).await?.is_empty(),
+            "channel delete index",
+        );
+
+        cache
+            .update(&Event::GuildDelete(Box::new(GuildDelete {
+                id: GuildId(1),
+                unavailable: false,
+            })))
+            .await?;
+
+        assert!(
+            cache.guilds.get(GuildId(1)).await?.is_none(),
+            "guild delete",
+        );
+        assert!(
+            cache.roles.get(RoleId(1)).await?.is_none(),
+            "guild delete role",
+        );
+        assert!(
+            ids(cache.guilds.role_ids(GuildId(1))).await?.is_empty(),
+            "guild delete role index",
+        );
+
+        Ok(())
+    }
+
+    /// Check the basic operations of a repository with an entity and an
+    /// updated version of it.
+    async fn crud_entity<E, R>(&self, repo: R, entity: E, updated: E) -> Result<(), B::Error>
+    where
+        E: Entity + Clone + Debug + PartialEq + 'static,
+        E::Id: Clone,
+        R: Repository<E, B>,
+    {
+        let id = entity.id();
+
+        assert!(repo.get(id.clone()).await?.is_none(), "get missing");
+        repo.remove(id.clone()).await?;
+
+        repo.upsert(entity.clone()).await?;
+        assert_eq!(
+            self.stored(entity.clone()),
+            repo.get(id.clone()).await?,
+            "get"
+        );
+        assert_eq!(
+            self.stored_all(vec![entity]),
+            entities(repo.list()).await?,
+            "list"
+        );
+
+        repo.upsert(updated.clone()).await?;
+        assert_eq!(
+            self.stored(updated.clone()),
+            repo.get(id.clone()).await?,
+            "get updated"
+        );
+        assert_eq!(
+            self.stored_all(vec![updated]),
+            entities(repo.list()).await?,
+            "list updated",
+        );
+
+        repo.remove(id.clone()).await?;
+        assert!(repo.get(id).await?.is_none(), "get removed");
+        assert!(entities(repo.list()).await?.is_empty(), "list removed");
+
+        Ok(())
+    }
+
+    /// Upsert a guild along with a channel, emoji, member, message and role
+    /// in it.
+    async fn seed(&self, backend: &B) -> Result<(), B::Error> {
+        backend.guilds().upsert(GuildEntity::from(guild())).await?;
+        backend.users().upsert(UserEntity::from(user(4))).await?;
+        backend
+            .emojis()
+            .upsert(EmojiEntity::from((GuildId(1), emoji(3))))
+            .await?;
+        backend
+            .members()
+            .upsert(MemberEntity::from(member(4)))
+            .await?;
+        backend
+            .roles()
+            .upsert(RoleEntity::from((GuildId(1), role(1))))
+            .await?;
+        backend
+            .text_channels()
+            .upsert(TextChannelEntity::from(text_channel()))
+            .await?;
+        backend
+            .messages()
+            .upsert(MessageEntity::from(message(5)))
+            .await
+    }
+
+    /// Return what a backend is expected to return when retrieving a value
+    /// that was upserted.
+    fn stored<T>(&self, value: T) -> Option<T> {
+        if self.stores_entities {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Return what a backend is expected to return when listing values that
+    /// were upserted.
+    fn stored_all<T>(&self, values: Vec<T>) -> Vec<T> {
+        if self.stores_entities {
+            values
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+async fn entities<T, E>(future: ListEntitiesFuture<'_, T, E>) -> Result<Vec<T>, E> {
+    future.await?.try_collect().await
+}
+
+async fn entity_ids<T: Entity, E>(future: ListEntitiesFuture<'_, T, E>) -> Result<Vec<T::Id>, E>
+where
+    T::Id: Ord,
+{
+    let mut ids = future
+        .await?
+        .map_ok(|entity| entity.id())
+        .try_collect::<Vec<_>>()
+        .await?;
+    ids.sort();
+
+    Ok(ids)
+}
+
+async fn ids<T: Ord, E>(future: ListEntitiesFuture<'_, T, E>) -> Result<Vec<T>, E> {
+    let mut ids = entities(future).await?;
+    ids.sort();
+
+    Ok(ids)
+}
+
+fn emoji(id: u64) -> Emoji {
+    Emoji {
+        animated: false,
+        available: true,
+        id: EmojiId(id),
+        managed: false,
+        name: "emoji".to_owned(),
+        require_colons: true,
+        roles: Vec::new(),
+        user: None,
+    }
+}
+
+fn guild() -> Guild {
+    let channels = Some((ChannelId(2), GuildChannel::Text(text_channel())))
+        .into_iter()
+        .collect();
+    let emojis = Some((EmojiId(3), emoji(3))).into_iter().collect();
+    let members = Some((UserId(4), member(4))).into_iter().collect();
+    let roles = Some((RoleId(1), role(1))).into_iter().collect();
+
+    Guild {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        channels,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        emojis,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(1),
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: Some(1),
+        members,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(4),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        presences: HashMap::new(),
+        region: "us-east".to_owned(),
+        roles,
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        voice_states: HashMap::new(),
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn member(user_id: u64) -> Member {
+    Member {
+        deaf: false,
+        guild_id: GuildId(1),
+        hoisted_role: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        roles: vec![RoleId(1)],
+        user: user(user_id),
+    }
+}
+
+fn message(id: u64) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: Vec::new(),
+        author: user(4),
+        channel_id: ChannelId(2),
+        content: "hello".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: Some(GuildId(1)),
+        id: MessageId(id),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+fn role(id: u64) -> Role {
+    Role {
+        color: 0,
+        hoist: false,
+        id: RoleId(id),
+        managed: false,
+        mentionable: false,
+        name: "role".to_owned(),
+        permissions: Permissions::SEND_MESSAGES,
+        position: 0,
+    }
+}
+
+fn text_channel() -> TextChannel {
+    TextChannel {
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(2),
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "general".to_owned(),
+        nsfw: false,
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    }
+}
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Suite;
+    use crate::NoopBackend;
+
+    #[tokio::test]
+    async fn test_noop_backend() {
+        Suite::new(NoopBackend::default)
+            .stores_entities(false)
+            .run()
+            .await
+            .unwrap();
+    }
+}
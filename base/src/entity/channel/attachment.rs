@@ -3,7 +3,10 @@ use crate::{
     repository::{GetEntityFuture, Repository},
     utils, Backend, Entity,
 };
-use twilight_model::id::{AttachmentId, MessageId};
+use twilight_model::{
+    channel::Attachment,
+    id::{AttachmentId, MessageId},
+};
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -18,6 +21,35 @@ pub struct AttachmentEntity {
     pub width: Option<u64>,
 }
 
+impl From<(MessageId, Attachment)> for AttachmentEntity {
+    fn from((message_id, attachment): (MessageId, Attachment)) -> Self {
+        Self {
+            filename: attachment.filename,
+            height: attachment.height,
+            id: attachment.id,
+            message_id,
+            proxy_url: attachment.proxy_url,
+            size: attachment.size,
+            url: attachment.url,
+            width: attachment.width,
+        }
+    }
+}
+
+impl From<AttachmentEntity> for Attachment {
+    fn from(attachment: AttachmentEntity) -> Self {
+        Self {
+            filename: attachment.filename,
+            height: attachment.height,
+            id: attachment.id,
+            proxy_url: attachment.proxy_url,
+            size: attachment.size,
+            url: attachment.url,
+            width: attachment.width,
+        }
+    }
+}
+
 impl Entity for AttachmentEntity {
     type Id = AttachmentId;
 
@@ -37,3 +69,32 @@ pub trait AttachmentRepository<B: Backend>: Repository<AttachmentEntity, B> + Se
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AttachmentEntity;
+    use twilight_model::{
+        channel::Attachment,
+        id::{AttachmentId, MessageId},
+    };
+
+    #[test]
+    fn test_attachment_entity_from_attachment() {
+        let attachment = Attachment {
+            filename: "image.png".to_owned(),
+            height: Some(64),
+            id: AttachmentId(1),
+            proxy_url: "https://media.example.com/image.png".to_owned(),
+            size: 1024,
+            url: "https://cdn.example.com/image.png".to_owned(),
+            width: Some(128),
+        };
+
+        let entity = AttachmentEntity::from((MessageId(2), attachment));
+        assert_eq!(AttachmentId(1), entity.id);
+        assert_eq!(MessageId(2), entity.message_id);
+        assert_eq!("image.png", entity.filename);
+        assert_eq!((Some(64), Some(128)), (entity.height, entity.width));
+        assert_eq!(1024, entity.size);
+    }
+}
@@ -32,6 +32,19 @@ impl From<CategoryChannel> for CategoryChannelEntity {
     }
 }
 
+impl From<CategoryChannelEntity> for CategoryChannel {
+    fn from(channel: CategoryChannelEntity) -> Self {
+        Self {
+            guild_id: channel.guild_id,
+            id: channel.id,
+            kind: channel.kind,
+            name: channel.name,
+            permission_overwrites: channel.permission_overwrites,
+            position: channel.position,
+        }
+    }
+}
+
 impl Entity for CategoryChannelEntity {
     type Id = ChannelId;
 
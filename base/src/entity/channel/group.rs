@@ -1,5 +1,6 @@
 use super::{super::user::UserEntity, MessageEntity};
 use crate::{
+    entity::EntityField,
     repository::{GetEntityFuture, ListEntitiesFuture, Repository},
     utils, Backend, Entity,
 };
@@ -47,6 +48,29 @@ impl Entity for GroupEntity {
     }
 }
 
+/// Update to a single field of a group.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum GroupField {
+    Icon(Option<String>),
+    LastMessageId(Option<MessageId>),
+    LastPinTimestamp(Option<String>),
+    Name(Option<String>),
+}
+
+impl EntityField<GroupEntity> for GroupField {
+    fn apply(self, group: &mut GroupEntity) {
+        match self {
+            Self::Icon(icon) => group.icon = icon,
+            Self::LastMessageId(last_message_id) => group.last_message_id = last_message_id,
+            Self::LastPinTimestamp(last_pin_timestamp) => {
+                group.last_pin_timestamp = last_pin_timestamp;
+            }
+            Self::Name(name) => group.name = name,
+        }
+    }
+}
+
 pub trait GroupRepository<B: Backend>: Repository<GroupEntity, B> {
     /// Retrieve the last message of a group.
     fn last_message(&self, group_id: ChannelId) -> GetEntityFuture<'_, MessageEntity, B::Error> {
@@ -3,24 +3,34 @@ use super::{
         guild::{GuildEntity, RoleEntity},
         user::UserEntity,
     },
-    AttachmentEntity, ChannelEntity, GuildChannelEntity, TextChannelEntity,
+    AttachmentEntity, ChannelEntity, GuildChannelEntity, TextChannelEntity, WebhookEntity,
 };
 use crate::{
-    repository::{GetEntityFuture, ListEntitiesFuture, Repository},
+    entity::EntityField,
+    repository::{
+        cascade, GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntitiesFuture,
+        RemoveEntityFuture, Repository,
+    },
     utils, Backend, Entity,
 };
+use futures_util::{
+    future,
+    stream::{self, StreamExt},
+};
 use twilight_model::{
     channel::{
         embed::Embed,
-        message::{MessageFlags, MessageReaction, MessageType},
+        message::{Message, MessageFlags, MessageReaction, MessageType},
+        ChannelMention, ReactionType,
     },
+    guild::PartialMember,
     id::{ApplicationId, AttachmentId, ChannelId, GuildId, MessageId, RoleId, UserId, WebhookId},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MessageEntity {
-    pub application_id: ApplicationId,
+    pub application_id: Option<ApplicationId>,
     pub attachments: Vec<AttachmentId>,
     pub author_id: UserId,
     pub channel_id: ChannelId,
@@ -37,11 +47,208 @@ pub struct MessageEntity {
     pub mentions: Vec<UserId>,
     pub pinned: bool,
     pub reactions: Vec<MessageReaction>,
+    /// IDs of the users that reacted with each emoji while the message was
+    /// cached.
+    ///
+    /// Reactions that were already on the message when it was cached only
+    /// count towards [`reactions`], so an emoji's list of users may be
+    /// shorter than its count.
+    ///
+    /// [`reactions`]: #structfield.reactions
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reaction_users: Vec<MessageReactionUsers>,
     pub timestamp: String,
     pub tts: bool,
     pub webhook_id: Option<WebhookId>,
 }
 
+impl From<Message> for MessageEntity {
+    fn from(message: Message) -> Self {
+        Self {
+            application_id: message.application.map(|application| application.id),
+            attachments: message
+                .attachments
+                .into_iter()
+                .map(|attachment| attachment.id)
+                .collect(),
+            author_id: message.author.id,
+            channel_id: message.channel_id,
+            content: message.content,
+            edited_timestamp: message.edited_timestamp,
+            embeds: message.embeds,
+            flags: message.flags,
+            guild_id: message.guild_id,
+            id: message.id,
+            kind: message.kind,
+            mention_channels: message
+                .mention_channels
+                .into_iter()
+                .map(|mention| mention.id)
+                .collect(),
+            mention_everyone: message.mention_everyone,
+            mention_roles: message.mention_roles,
+            mentions: message.mentions.into_keys().collect(),
+            pinned: message.pinned,
+            reactions: message.reactions,
+            reaction_users: Vec::new(),
+            timestamp: message.timestamp,
+            tts: message.tts,
+            webhook_id: message.webhook_id,
+        }
+    }
+}
+
+impl MessageEntity {
+    /// Add a user's reaction to the message.
+    ///
+    /// Increments the count of the emoji's reaction, or adds a reaction if
+    /// it's the first one with the emoji. `me` is whether the user is the
+    /// current user. A reaction by a user that has already been recorded with
+    /// the emoji is ignored.
+    pub fn add_reaction(&mut self, emoji: ReactionType, user_id: UserId, me: bool) {
+        match self
+            .reaction_users
+            .iter_mut()
+            .find(|users| emoji_eq(&users.emoji, &emoji))
+        {
+            Some(users) if users.user_ids.contains(&user_id) => return,
+            Some(users) => users.user_ids.push(user_id),
+            None => self.reaction_users.push(MessageReactionUsers {
+                emoji: emoji.clone(),
+                user_ids: vec![user_id],
+            }),
+        }
+
+        if let Some(reaction) = self
+            .reactions
+            .iter_mut()
+            .find(|reaction| emoji_eq(&reaction.emoji, &emoji))
+        {
+            reaction.count += 1;
+            reaction.me |= me;
+        } else {
+            self.reactions.push(MessageReaction {
+                count: 1,
+                emoji,
+                me,
+            });
+        }
+    }
+
+    /// IDs of the users that reacted to the message with an emoji while it
+    /// was cached.
+    pub fn reaction_user_ids(&self, emoji: &ReactionType) -> &[UserId] {
+        self.reaction_users
+            .iter()
+            .find(|users| emoji_eq(&users.emoji, emoji))
+            .map_or(&[], |users| users.user_ids.as_slice())
+    }
+
+    /// Remove a user's reaction from the message.
+    ///
+    /// Decrements the count of the emoji's reaction, removing the reaction
+    /// once nobody has reacted with the emoji anymore. `me` is whether the
+    /// user is the current user.
+    pub fn remove_reaction(&mut self, emoji: &ReactionType, user_id: UserId, me: bool) {
+        if let Some(users) = self
+            .reaction_users
+            .iter_mut()
+            .find(|users| emoji_eq(&users.emoji, emoji))
+        {
+            users.user_ids.retain(|id| *id != user_id);
+        }
+
+        self.reaction_users
+            .retain(|users| !users.user_ids.is_empty());
+
+        if let Some(reaction) = self
+            .reactions
+            .iter_mut()
+            .find(|reaction| emoji_eq(&reaction.emoji, emoji))
+        {
+            reaction.count = reaction.count.saturating_sub(1);
+
+            if me {
+                reaction.me = false;
+            }
+        }
+
+        self.reactions.retain(|reaction| reaction.count > 0);
+    }
+
+    /// Remove all of the reactions with an emoji from the message.
+    pub fn remove_reaction_emoji(&mut self, emoji: &ReactionType) {
+        self.reactions
+            .retain(|reaction| !emoji_eq(&reaction.emoji, emoji));
+        self.reaction_users
+            .retain(|users| !emoji_eq(&users.emoji, emoji));
+    }
+
+    /// Remove all of the reactions from the message.
+    pub fn remove_reactions(&mut self) {
+        self.reactions.clear();
+        self.reaction_users.clear();
+    }
+}
+
+/// IDs of the users that reacted to a message with an emoji.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageReactionUsers {
+    pub emoji: ReactionType,
+    pub user_ids: Vec<UserId>,
+}
+
+/// Whether two reaction emojis are the same emoji.
+///
+/// Custom emojis are compared by ID since their name and animated flag aren't
+/// always included, such as in reaction removal events.
+fn emoji_eq(a: &ReactionType, b: &ReactionType) -> bool {
+    match (a, b) {
+        (ReactionType::Custom { id: a, .. }, ReactionType::Custom { id: b, .. }) => a == b,
+        (ReactionType::Unicode { name: a }, ReactionType::Unicode { name: b }) => a == b,
+        _ => false,
+    }
+}
+
+/// A message decomposed into the entities that it is cached as.
+///
+/// The message itself only refers to its attachments, author and mentioned
+/// users by ID, so caching a message in full means caching each of these
+/// entities as well.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageEntities {
+    pub attachments: Vec<AttachmentEntity>,
+    pub author: UserEntity,
+    pub mentions: Vec<UserEntity>,
+    pub message: MessageEntity,
+}
+
+impl From<Message> for MessageEntities {
+    fn from(message: Message) -> Self {
+        let attachments = message
+            .attachments
+            .iter()
+            .cloned()
+            .map(|attachment| AttachmentEntity::from((message.id, attachment)))
+            .collect();
+        let author = UserEntity::from(message.author.clone());
+        let mentions = message
+            .mentions
+            .values()
+            .cloned()
+            .map(UserEntity::from)
+            .collect();
+
+        Self {
+            attachments,
+            author,
+            mentions,
+            message: MessageEntity::from(message),
+        }
+    }
+}
+
 impl Entity for MessageEntity {
     type Id = MessageId;
 
@@ -51,6 +258,55 @@ impl Entity for MessageEntity {
     }
 }
 
+/// Update to a single field of a message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MessageField {
+    /// Add a user's reaction to the message.
+    ///
+    /// Refer to [`MessageEntity::add_reaction`] for how it's recorded.
+    ///
+    /// [`MessageEntity::add_reaction`]: struct.MessageEntity.html#method.add_reaction
+    AddReaction {
+        emoji: ReactionType,
+        me: bool,
+        user_id: UserId,
+    },
+    Content(String),
+    EditedTimestamp(Option<String>),
+    Pinned(bool),
+    /// Remove a user's reaction from the message.
+    ///
+    /// Refer to [`MessageEntity::remove_reaction`] for how it's recorded.
+    ///
+    /// [`MessageEntity::remove_reaction`]: struct.MessageEntity.html#method.remove_reaction
+    RemoveReaction {
+        emoji: ReactionType,
+        me: bool,
+        user_id: UserId,
+    },
+    /// Remove all of the reactions with an emoji from the message.
+    RemoveReactionEmoji(ReactionType),
+    /// Remove all of the reactions from the message.
+    RemoveReactions,
+}
+
+impl EntityField<MessageEntity> for MessageField {
+    fn apply(self, message: &mut MessageEntity) {
+        match self {
+            Self::AddReaction { emoji, me, user_id } => message.add_reaction(emoji, user_id, me),
+            Self::Content(content) => message.content = content,
+            Self::EditedTimestamp(edited_timestamp) => message.edited_timestamp = edited_timestamp,
+            Self::Pinned(pinned) => message.pinned = pinned,
+            Self::RemoveReaction { emoji, me, user_id } => {
+                message.remove_reaction(&emoji, user_id, me);
+            }
+            Self::RemoveReactionEmoji(emoji) => message.remove_reaction_emoji(&emoji),
+            Self::RemoveReactions => message.remove_reactions(),
+        }
+    }
+}
+
 pub trait MessageRepository<B: Backend>: Repository<MessageEntity, B> + Send {
     fn attachments(
         &self,
@@ -118,6 +374,105 @@ pub trait MessageRepository<B: Backend>: Repository<MessageEntity, B> + Send {
         )
     }
 
+    /// Retrieve a message along with its author, attachments and mentions as
+    /// a full twilight [`Message`].
+    ///
+    /// Returns `None` if either the message or its author is not in the
+    /// cache. Attachments, mentioned users and mentioned channels that aren't
+    /// cached are left out. The message's activity, application, reference
+    /// and stickers aren't cached, so they're always empty.
+    ///
+    /// [`Message`]: https://docs.rs/twilight-model/*/twilight_model/channel/message/struct.Message.html
+    fn hydrate(&self, message_id: MessageId) -> GetEntityFuture<'_, Message, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let messages = backend.messages();
+
+            let Some(message) = messages.get(message_id).await? else {
+                return Ok(None);
+            };
+
+            let users = backend.users();
+
+            let Some(author) = users.get(message.author_id).await? else {
+                return Ok(None);
+            };
+
+            let attachments = backend.attachments();
+            let text_channels = backend.text_channels();
+
+            let (attachments, mentions, mention_channels) = future::try_join3(
+                future::try_join_all(message.attachments.iter().map(|id| attachments.get(*id))),
+                future::try_join_all(message.mentions.iter().map(|id| users.get(*id))),
+                future::try_join_all(
+                    message
+                        .mention_channels
+                        .iter()
+                        .map(|id| text_channels.get(*id)),
+                ),
+            )
+            .await?;
+
+            let member = match message.guild_id {
+                Some(guild_id) => backend
+                    .members()
+                    .get((guild_id, message.author_id))
+                    .await?
+                    .map(|member| PartialMember {
+                        deaf: member.deaf,
+                        joined_at: member.joined_at,
+                        mute: member.mute,
+                        nick: member.nick,
+                        roles: member.role_ids,
+                    }),
+                None => None,
+            };
+
+            Ok(Some(Message {
+                activity: None,
+                application: None,
+                attachments: attachments.into_iter().flatten().map(From::from).collect(),
+                author: author.into(),
+                channel_id: message.channel_id,
+                content: message.content,
+                edited_timestamp: message.edited_timestamp,
+                embeds: message.embeds,
+                flags: message.flags,
+                guild_id: message.guild_id,
+                id: message.id,
+                kind: message.kind,
+                member,
+                mention_channels: mention_channels
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|channel| {
+                        Some(ChannelMention {
+                            guild_id: channel.guild_id?,
+                            id: channel.id,
+                            kind: channel.kind,
+                            name: channel.name,
+                        })
+                    })
+                    .collect(),
+                mention_everyone: message.mention_everyone,
+                mention_roles: message.mention_roles,
+                mentions: mentions
+                    .into_iter()
+                    .flatten()
+                    .map(|user| (user.id, user.into()))
+                    .collect(),
+                pinned: message.pinned,
+                reactions: message.reactions,
+                reference: None,
+                stickers: Vec::new(),
+                timestamp: message.timestamp,
+                tts: message.tts,
+                webhook_id: message.webhook_id,
+            }))
+        })
+    }
+
     fn mention_channels(
         &self,
         message_id: MessageId,
@@ -147,4 +502,184 @@ pub trait MessageRepository<B: Backend>: Repository<MessageEntity, B> + Send {
             |message| message.mentions.into_iter(),
         )
     }
+
+    /// Retrieve a stream of the reactions on a message.
+    fn reactions(
+        &self,
+        message_id: MessageId,
+    ) -> ListEntitiesFuture<'_, MessageReaction, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let messages = backend.messages();
+
+            let reactions = messages
+                .get(message_id)
+                .await?
+                .map(|message| message.reactions)
+                .unwrap_or_default();
+
+            Ok(stream::iter(reactions.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve a stream of the IDs of users that reacted to a message with an
+    /// emoji.
+    ///
+    /// Only reactions received while the message was cached are known, refer
+    /// to [`MessageEntity::reaction_users`] for more information.
+    ///
+    /// [`MessageEntity::reaction_users`]: struct.MessageEntity.html#structfield.reaction_users
+    fn reaction_user_ids(
+        &self,
+        message_id: MessageId,
+        emoji: ReactionType,
+    ) -> ListEntityIdsFuture<'_, UserId, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let messages = backend.messages();
+
+            let user_ids = messages
+                .get(message_id)
+                .await?
+                .map(|message| message.reaction_user_ids(&emoji).to_vec())
+                .unwrap_or_default();
+
+            Ok(stream::iter(user_ids.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Remove a message along with its attachments.
+    ///
+    /// **Backend implementations**: a default implementation is provided
+    /// that removes the attachments of the cached message, as described by
+    /// [`cascade::message`]. Backends with a more efficient native way may
+    /// override it.
+    ///
+    /// [`cascade::message`]: ../../../repository/cascade/fn.message.html
+    fn remove_cascade(&self, message_id: MessageId) -> RemoveEntityFuture<'_, B::Error> {
+        cascade::message(self.backend(), message_id)
+    }
+
+    /// Bulk remove messages along with their attachments.
+    ///
+    /// **Backend implementations**: a default implementation is provided
+    /// that retrieves the messages and then bulk removes their attachments
+    /// and the messages, as described by [`cascade::messages`]. Backends with
+    /// a more efficient native way may override it.
+    ///
+    /// [`cascade::messages`]: ../../../repository/cascade/fn.messages.html
+    fn remove_cascade_bulk<T: Iterator<Item = MessageId>>(
+        &self,
+        message_ids: T,
+    ) -> RemoveEntitiesFuture<'_, B::Error> {
+        cascade::messages(self.backend(), message_ids.collect())
+    }
+
+    /// Retrieve the webhook that sent a message.
+    ///
+    /// Backend implementations should return `None` if the message wasn't
+    /// sent by a webhook or the webhook is not present in the cache.
+    fn webhook(&self, message_id: MessageId) -> GetEntityFuture<'_, WebhookEntity, B::Error> {
+        utils::relation_and_then(
+            self.backend().messages(),
+            self.backend().webhooks(),
+            message_id,
+            |message| message.webhook_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MessageEntities, MessageEntity};
+    use std::collections::HashMap;
+    use twilight_model::{
+        channel::{
+            message::{Message, MessageType},
+            Attachment,
+        },
+        id::{AttachmentId, ChannelId, MessageId, UserId},
+        user::User,
+    };
+
+    fn user(id: u64) -> User {
+        User {
+            avatar: None,
+            bot: false,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(id),
+            locale: None,
+            mfa_enabled: None,
+            name: "test".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }
+    }
+
+    fn message() -> Message {
+        let mut mentions = HashMap::new();
+        mentions.insert(UserId(2), user(2));
+
+        Message {
+            activity: None,
+            application: None,
+            attachments: vec![Attachment {
+                filename: "image.png".to_owned(),
+                height: None,
+                id: AttachmentId(4),
+                proxy_url: "https://media.example.com/image.png".to_owned(),
+                size: 1024,
+                url: "https://cdn.example.com/image.png".to_owned(),
+                width: None,
+            }],
+            author: user(1),
+            channel_id: ChannelId(3),
+            content: "ping".to_owned(),
+            edited_timestamp: None,
+            embeds: Vec::new(),
+            flags: None,
+            guild_id: None,
+            id: MessageId(5),
+            kind: MessageType::Regular,
+            member: None,
+            mention_channels: Vec::new(),
+            mention_everyone: false,
+            mention_roles: Vec::new(),
+            mentions,
+            pinned: false,
+            reactions: Vec::new(),
+            reference: None,
+            stickers: Vec::new(),
+            timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+            tts: false,
+            webhook_id: None,
+        }
+    }
+
+    #[test]
+    fn test_message_entity_from_message() {
+        let entity = MessageEntity::from(message());
+        assert_eq!(vec![AttachmentId(4)], entity.attachments);
+        assert_eq!(UserId(1), entity.author_id);
+        assert_eq!(ChannelId(3), entity.channel_id);
+        assert_eq!(vec![UserId(2)], entity.mentions);
+    }
+
+    #[test]
+    fn test_message_entities_from_message() {
+        let entities = MessageEntities::from(message());
+        assert_eq!(MessageEntity::from(message()), entities.message);
+        assert_eq!(UserId(1), entities.author.id);
+        assert_eq!(1, entities.attachments.len());
+        assert_eq!(AttachmentId(4), entities.attachments[0].id);
+        assert_eq!(MessageId(5), entities.attachments[0].message_id);
+        assert_eq!(1, entities.mentions.len());
+        assert_eq!(UserId(2), entities.mentions[0].id);
+    }
 }
@@ -7,17 +7,23 @@ pub mod message;
 pub mod private_channel;
 pub mod text_channel;
 pub mod voice_channel;
+pub mod webhook;
 
 pub use self::{
     attachment::{AttachmentEntity, AttachmentRepository},
     category_channel::{CategoryChannelEntity, CategoryChannelRepository},
-    group::{GroupEntity, GroupRepository},
-    message::{MessageEntity, MessageRepository},
-    private_channel::{PrivateChannelEntity, PrivateChannelRepository},
-    text_channel::{TextChannelEntity, TextChannelRepository},
+    group::{GroupEntity, GroupField, GroupRepository},
+    message::{
+        MessageEntities, MessageEntity, MessageField, MessageReactionUsers, MessageRepository,
+    },
+    private_channel::{PrivateChannelEntity, PrivateChannelField, PrivateChannelRepository},
+    text_channel::{TextChannelEntity, TextChannelField, TextChannelRepository},
     voice_channel::{VoiceChannelEntity, VoiceChannelRepository},
+    webhook::{WebhookEntity, WebhookRepository},
 };
 
+use twilight_model::channel::GuildChannel;
+
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -41,3 +47,13 @@ pub enum GuildChannelEntity {
     Text(TextChannelEntity),
     Voice(VoiceChannelEntity),
 }
+
+impl From<GuildChannelEntity> for GuildChannel {
+    fn from(channel: GuildChannelEntity) -> Self {
+        match channel {
+            GuildChannelEntity::Category(channel) => Self::Category(channel.into()),
+            GuildChannelEntity::Text(channel) => Self::Text(channel.into()),
+            GuildChannelEntity::Voice(channel) => Self::Voice(channel.into()),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::{super::user::UserEntity, MessageEntity};
 use crate::{
+    entity::EntityField,
     repository::{GetEntityFuture, Repository},
     utils, Backend, Entity,
 };
@@ -40,6 +41,25 @@ impl Entity for PrivateChannelEntity {
 }
 
 /// Repository to work with guild channels and their associated entities.
+/// Update to a single field of a private channel.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PrivateChannelField {
+    LastMessageId(Option<MessageId>),
+    LastPinTimestamp(Option<String>),
+}
+
+impl EntityField<PrivateChannelEntity> for PrivateChannelField {
+    fn apply(self, channel: &mut PrivateChannelEntity) {
+        match self {
+            Self::LastMessageId(last_message_id) => channel.last_message_id = last_message_id,
+            Self::LastPinTimestamp(last_pin_timestamp) => {
+                channel.last_pin_timestamp = last_pin_timestamp;
+            }
+        }
+    }
+}
+
 pub trait PrivateChannelRepository<B: Backend>: Repository<PrivateChannelEntity, B> {
     /// Retrieve the last message of a private channel.
     fn last_message(&self, channel_id: ChannelId) -> GetEntityFuture<'_, MessageEntity, B::Error> {
@@ -1,6 +1,10 @@
 use super::{super::guild::GuildEntity, CategoryChannelEntity, MessageEntity};
 use crate::{
-    repository::{GetEntityFuture, Repository},
+    entity::EntityField,
+    repository::{
+        cascade, GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture,
+        Repository,
+    },
     utils, Backend, Entity,
 };
 use twilight_model::{
@@ -44,6 +48,25 @@ impl From<TextChannel> for TextChannelEntity {
     }
 }
 
+impl From<TextChannelEntity> for TextChannel {
+    fn from(channel: TextChannelEntity) -> Self {
+        Self {
+            guild_id: channel.guild_id,
+            id: channel.id,
+            kind: channel.kind,
+            last_message_id: channel.last_message_id,
+            last_pin_timestamp: channel.last_pin_timestamp,
+            name: channel.name,
+            nsfw: channel.nsfw,
+            permission_overwrites: channel.permission_overwrites,
+            parent_id: channel.parent_id,
+            position: channel.position,
+            rate_limit_per_user: channel.rate_limit_per_user,
+            topic: channel.topic,
+        }
+    }
+}
+
 impl Entity for TextChannelEntity {
     type Id = ChannelId;
 
@@ -54,6 +77,37 @@ impl Entity for TextChannelEntity {
 }
 
 /// Repository to work with guild text channels and their associated entities.
+/// Update to a single field of a text channel.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TextChannelField {
+    LastMessageId(Option<MessageId>),
+    LastPinTimestamp(Option<String>),
+    Name(String),
+    Nsfw(bool),
+    Position(i64),
+    RateLimitPerUser(Option<u64>),
+    Topic(Option<String>),
+}
+
+impl EntityField<TextChannelEntity> for TextChannelField {
+    fn apply(self, channel: &mut TextChannelEntity) {
+        match self {
+            Self::LastMessageId(last_message_id) => channel.last_message_id = last_message_id,
+            Self::LastPinTimestamp(last_pin_timestamp) => {
+                channel.last_pin_timestamp = last_pin_timestamp;
+            }
+            Self::Name(name) => channel.name = name,
+            Self::Nsfw(nsfw) => channel.nsfw = nsfw,
+            Self::Position(position) => channel.position = position,
+            Self::RateLimitPerUser(rate_limit_per_user) => {
+                channel.rate_limit_per_user = rate_limit_per_user;
+            }
+            Self::Topic(topic) => channel.topic = topic,
+        }
+    }
+}
+
 pub trait TextChannelRepository<B: Backend>: Repository<TextChannelEntity, B> {
     /// Retrieve the guild associated with a guild text channel.
     fn guild(&self, channel_id: ChannelId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
@@ -75,6 +129,17 @@ pub trait TextChannelRepository<B: Backend>: Repository<TextChannelEntity, B> {
         )
     }
 
+    /// Retrieve a stream of the IDs of messages within a text channel.
+    fn message_ids(&self, channel_id: ChannelId) -> ListEntityIdsFuture<'_, MessageId, B::Error>;
+
+    /// Retrieve a stream of messages within a text channel.
+    ///
+    /// Backend implementations aren't obligated to return messages in any
+    /// particular order.
+    fn messages(&self, channel_id: ChannelId) -> ListEntitiesFuture<'_, MessageEntity, B::Error> {
+        utils::stream_ids(self.message_ids(channel_id), self.backend().messages())
+    }
+
     /// Retrieve the parent category channel of the voice channel.
     fn parent(
         &self,
@@ -87,4 +152,17 @@ pub trait TextChannelRepository<B: Backend>: Repository<TextChannelEntity, B> {
             |channel| channel.parent_id,
         )
     }
+
+    /// Remove a text channel along with its messages and webhooks.
+    ///
+    /// **Backend implementations**: a default implementation is provided
+    /// that removes the messages found through [`message_ids`] and the
+    /// channel's webhooks, as described by [`cascade::text_channel`].
+    /// Backends with a more efficient native way may override it.
+    ///
+    /// [`cascade::text_channel`]: ../../../repository/cascade/fn.text_channel.html
+    /// [`message_ids`]: #tymethod.message_ids
+    fn remove_cascade(&self, channel_id: ChannelId) -> RemoveEntityFuture<'_, B::Error> {
+        cascade::text_channel(self.backend(), channel_id)
+    }
 }
@@ -1,8 +1,18 @@
-use super::{super::guild::GuildEntity, CategoryChannelEntity};
+use super::{
+    super::{
+        guild::{GuildEntity, GuildRepository, MemberEntity},
+        voice::VoiceStateEntity,
+    },
+    CategoryChannelEntity,
+};
 use crate::{
-    repository::{GetEntityFuture, Repository},
+    repository::{GetEntityFuture, ListEntitiesFuture, Repository},
     utils, Backend, Entity,
 };
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
 use twilight_model::{
     channel::{permission_overwrite::PermissionOverwrite, ChannelType, VoiceChannel},
     id::{ChannelId, GuildId},
@@ -38,6 +48,22 @@ impl From<VoiceChannel> for VoiceChannelEntity {
     }
 }
 
+impl From<VoiceChannelEntity> for VoiceChannel {
+    fn from(channel: VoiceChannelEntity) -> Self {
+        Self {
+            bitrate: channel.bitrate,
+            guild_id: channel.guild_id,
+            id: channel.id,
+            kind: channel.kind,
+            name: channel.name,
+            permission_overwrites: channel.permission_overwrites,
+            parent_id: channel.parent_id,
+            position: channel.position,
+            user_limit: channel.user_limit,
+        }
+    }
+}
+
 impl Entity for VoiceChannelEntity {
     type Id = ChannelId;
 
@@ -71,4 +97,59 @@ pub trait VoiceChannelRepository<B: Backend>: Repository<VoiceChannelEntity, B>
             |channel| channel.parent_id,
         )
     }
+
+    /// Retrieve a stream of members connected to a guild voice channel.
+    ///
+    /// Members of connected users that aren't in the cache are skipped.
+    ///
+    /// Backend implementations aren't obligated to return members in any
+    /// particular order.
+    fn members(&self, channel_id: ChannelId) -> ListEntitiesFuture<'_, MemberEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let channels = backend.voice_channels();
+            let members = backend.members();
+
+            let states = channels.voice_states(channel_id).await?;
+            let states = states.try_collect::<Vec<_>>().await?;
+            let members = future::try_join_all(
+                states
+                    .into_iter()
+                    .map(|state| members.get((state.guild_id, state.user_id))),
+            )
+            .await?;
+
+            Ok(stream::iter(members.into_iter().flatten().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve a stream of voice states of users connected to a guild voice
+    /// channel.
+    ///
+    /// Backend implementations aren't obligated to return voice states in any
+    /// particular order.
+    fn voice_states(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, VoiceStateEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let channel = backend.voice_channels().get(channel_id).await?;
+
+            let Some(guild_id) = channel.and_then(|channel| channel.guild_id) else {
+                return Ok(stream::empty().boxed());
+            };
+
+            let guilds = backend.guilds();
+            let states = guilds.voice_states(guild_id).await?;
+            let states = states
+                .try_filter(|state| future::ready(state.channel_id == Some(channel_id)))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(states.into_iter().map(Ok)).boxed())
+        })
+    }
 }
@@ -0,0 +1,204 @@
+use super::{
+    super::{guild::GuildEntity, user::UserEntity},
+    TextChannelEntity,
+};
+use crate::{
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, Repository, UpsertEntitiesFuture,
+        UpsertEntityFuture,
+    },
+    utils, Backend, Entity,
+};
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use twilight_model::{
+    channel::{Webhook, WebhookType},
+    id::{ChannelId, GuildId, UserId, WebhookId},
+};
+
+/// Cachable version of a webhook.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebhookEntity {
+    pub avatar: Option<String>,
+    pub channel_id: ChannelId,
+    pub guild_id: Option<GuildId>,
+    pub id: WebhookId,
+    pub kind: WebhookType,
+    pub name: Option<String>,
+    pub token: Option<String>,
+    pub user_id: Option<UserId>,
+}
+
+impl From<Webhook> for WebhookEntity {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            avatar: webhook.avatar,
+            channel_id: webhook.channel_id,
+            guild_id: webhook.guild_id,
+            id: webhook.id,
+            kind: webhook.kind,
+            name: webhook.name,
+            token: webhook.token,
+            user_id: webhook.user.map(|user| user.id),
+        }
+    }
+}
+
+impl Entity for WebhookEntity {
+    type Id = WebhookId;
+
+    /// Return the webhook's ID.
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+/// Repository to work with webhooks and their related entities.
+///
+/// The gateway doesn't send webhooks themselves, only a notice that a
+/// channel's webhooks have changed. When this happens the channel's webhooks
+/// are marked as stale, and callers should refetch them over the HTTP API and
+/// pass them to [`refresh`].
+///
+/// [`refresh`]: #method.refresh
+pub trait WebhookRepository<B: Backend>: Repository<WebhookEntity, B> {
+    /// Retrieve the channel associated with a webhook.
+    fn channel(&self, webhook_id: WebhookId) -> GetEntityFuture<'_, TextChannelEntity, B::Error> {
+        utils::relation_map(
+            self.backend().webhooks(),
+            self.backend().text_channels(),
+            webhook_id,
+            |webhook| webhook.channel_id,
+        )
+    }
+
+    /// Retrieve a stream of the webhooks of a channel.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the webhooks in [`Repository::list`], so backends with an index of
+    /// webhooks by channel should override this.
+    ///
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.webhooks();
+
+            let webhooks = repo
+                .list()
+                .await?
+                .try_filter(|webhook| future::ready(webhook.channel_id == channel_id))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(webhooks.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve the guild associated with a webhook.
+    fn guild(&self, webhook_id: WebhookId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
+        utils::relation_and_then(
+            self.backend().webhooks(),
+            self.backend().guilds(),
+            webhook_id,
+            |webhook| webhook.guild_id,
+        )
+    }
+
+    /// Retrieve a stream of the webhooks within a guild.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the webhooks in [`Repository::list`], so backends with an index of
+    /// webhooks by guild should override this.
+    ///
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn guild_webhooks(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, WebhookEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.webhooks();
+
+            let webhooks = repo
+                .list()
+                .await?
+                .try_filter(|webhook| future::ready(webhook.guild_id == Some(guild_id)))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(webhooks.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve whether the webhooks of a channel are stale and need to be
+    /// refetched.
+    ///
+    /// Returns `None` if the channel isn't in the cache.
+    fn is_stale(&self, channel_id: ChannelId) -> GetEntityFuture<'_, bool, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            if backend.text_channels().get(channel_id).await?.is_none() {
+                return Ok(None);
+            }
+
+            let stale = backend
+                .webhooks()
+                .stale_channel_ids()
+                .await?
+                .try_filter(|id| future::ready(*id == channel_id))
+                .try_next()
+                .await?;
+
+            Ok(Some(stale.is_some()))
+        })
+    }
+
+    /// Replace the cached webhooks of a channel with a freshly fetched set,
+    /// marking the channel's webhooks as no longer stale.
+    fn refresh(
+        &self,
+        channel_id: ChannelId,
+        webhooks: Vec<WebhookEntity>,
+    ) -> UpsertEntitiesFuture<'_, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.webhooks();
+
+            let old_ids = repo
+                .channel_webhooks(channel_id)
+                .await?
+                .map_ok(|webhook| webhook.id)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            repo.remove_bulk(old_ids.into_iter()).await?;
+            repo.upsert_bulk(webhooks.into_iter()).await?;
+
+            repo.set_stale(channel_id, false).await
+        })
+    }
+
+    /// Set whether the webhooks of a channel are stale.
+    fn set_stale(&self, channel_id: ChannelId, stale: bool) -> UpsertEntityFuture<'_, B::Error>;
+
+    /// Retrieve a stream of the IDs of channels whose webhooks are stale.
+    fn stale_channel_ids(&self) -> ListEntityIdsFuture<'_, ChannelId, B::Error>;
+
+    /// Retrieve the user who created a webhook.
+    fn user(&self, webhook_id: WebhookId) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        utils::relation_and_then(
+            self.backend().webhooks(),
+            self.backend().users(),
+            webhook_id,
+            |webhook| webhook.user_id,
+        )
+    }
+}
@@ -1,5 +1,9 @@
 //! Entities related to the gateway API.
 
 pub mod presence;
+pub mod typing;
 
-pub use self::presence::{PresenceEntity, PresenceRepository};
+pub use self::{
+    presence::{PresenceEntity, PresenceRepository},
+    typing::{TypingEntity, TypingRepository},
+};
@@ -1,6 +1,12 @@
-use crate::{Backend, Entity, Repository};
+use crate::{
+    entity::guild::GuildRepository, repository::ListEntitiesFuture, Backend, Entity, Repository,
+};
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
 use twilight_model::{
-    gateway::presence::{Activity, ClientStatus, Status},
+    gateway::presence::{Activity, ClientStatus, Presence, Status, UserOrId},
     id::{GuildId, UserId},
 };
 
@@ -16,6 +22,41 @@ pub struct PresenceEntity {
     pub user_id: UserId,
 }
 
+impl From<Presence> for PresenceEntity {
+    fn from(presence: Presence) -> Self {
+        let user_id = match presence.user {
+            UserOrId::User(user) => user.id,
+            UserOrId::UserId { id } => id,
+        };
+
+        Self {
+            activities: presence.activities,
+            client_status: presence.client_status,
+            game: presence.game,
+            guild_id: presence.guild_id,
+            nick: presence.nick,
+            status: presence.status,
+            user_id,
+        }
+    }
+}
+
+impl From<PresenceEntity> for Presence {
+    fn from(presence: PresenceEntity) -> Self {
+        Self {
+            activities: presence.activities,
+            client_status: presence.client_status,
+            game: presence.game,
+            guild_id: presence.guild_id,
+            nick: presence.nick,
+            status: presence.status,
+            user: UserOrId::UserId {
+                id: presence.user_id,
+            },
+        }
+    }
+}
+
 impl Entity for PresenceEntity {
     type Id = (GuildId, UserId);
 
@@ -25,4 +66,101 @@ impl Entity for PresenceEntity {
     }
 }
 
-pub trait PresenceRepository<B: Backend>: Repository<PresenceEntity, B> {}
+impl PresenceEntity {
+    /// Whether the user is online, which includes being idle or in do not
+    /// disturb mode.
+    ///
+    /// Invisible users appear offline to others, so they aren't online.
+    pub fn is_online(&self) -> bool {
+        matches!(
+            self.status,
+            Status::DoNotDisturb | Status::Idle | Status::Online
+        )
+    }
+
+    /// Whether the user has an activity with the given name, such as the name
+    /// of the game being played.
+    pub fn has_activity(&self, name: &str) -> bool {
+        self.activities
+            .iter()
+            .chain(self.game.as_ref())
+            .any(|activity| activity.name == name)
+    }
+}
+
+pub trait PresenceRepository<B: Backend>: Repository<PresenceEntity, B> {
+    /// Retrieve a stream of presences in a guild of users who are online.
+    ///
+    /// Refer to [`PresenceEntity::is_online`] for which users are online.
+    ///
+    /// Backend implementations aren't obligated to return presences in any
+    /// particular order.
+    ///
+    /// [`PresenceEntity::is_online`]: struct.PresenceEntity.html#method.is_online
+    fn online(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, PresenceEntity, B::Error> {
+        filter_guild(self.backend(), guild_id, PresenceEntity::is_online)
+    }
+
+    /// Retrieve a stream of presences in a guild of users who have an
+    /// activity with the given name, such as users playing a game.
+    ///
+    /// Backend implementations aren't obligated to return presences in any
+    /// particular order.
+    fn with_activity<'a>(
+        &'a self,
+        guild_id: GuildId,
+        name: &'a str,
+    ) -> ListEntitiesFuture<'a, PresenceEntity, B::Error> {
+        filter_guild(self.backend(), guild_id, move |presence| {
+            presence.has_activity(name)
+        })
+    }
+}
+
+/// Retrieve the presences in a guild matching a predicate.
+fn filter_guild<'a, B: Backend>(
+    backend: B,
+    guild_id: GuildId,
+    predicate: impl Fn(&PresenceEntity) -> bool + Send + Sync + 'a,
+) -> ListEntitiesFuture<'a, PresenceEntity, B::Error> {
+    Box::pin(async move {
+        let guilds = backend.guilds();
+        let presences = guilds.presences(guild_id).await?;
+        let presences = presences
+            .try_filter(|presence| future::ready(predicate(presence)))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(stream::iter(presences.into_iter().map(Ok)).boxed())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PresenceEntity;
+    use twilight_model::{
+        gateway::presence::{ClientStatus, Presence, Status, UserOrId},
+        id::{GuildId, UserId},
+    };
+
+    #[test]
+    fn test_presence_entity_from_presence() {
+        let presence = Presence {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: Some(Status::Online),
+                mobile: None,
+                web: None,
+            },
+            game: None,
+            guild_id: GuildId(1),
+            nick: None,
+            status: Status::Online,
+            user: UserOrId::UserId { id: UserId(2) },
+        };
+
+        let entity = PresenceEntity::from(presence);
+        assert_eq!((GuildId(1), UserId(2)), (entity.guild_id, entity.user_id));
+        assert_eq!(Status::Online, entity.status);
+    }
+}
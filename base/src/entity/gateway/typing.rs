@@ -0,0 +1,142 @@
+use crate::{
+    entity::user::UserEntity,
+    repository::{GetEntityFuture, ListEntitiesFuture},
+    utils, Backend, Entity, Repository,
+};
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use twilight_model::{
+    gateway::payload::TypingStart,
+    id::{ChannelId, GuildId, UserId},
+};
+
+/// Cachable version of a user typing in a channel.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypingEntity {
+    pub channel_id: ChannelId,
+    pub guild_id: Option<GuildId>,
+    /// Unix timestamp, in seconds, of when the user started typing.
+    pub timestamp: u64,
+    pub user_id: UserId,
+}
+
+impl TypingEntity {
+    /// Number of seconds after starting to type that a user is considered to
+    /// no longer be typing.
+    ///
+    /// Clients send a new typing event roughly every 10 seconds while the
+    /// user continues to type.
+    pub const DURATION: u64 = 10;
+
+    /// Return the Unix timestamp, in seconds, of when the typing state
+    /// expires.
+    pub fn expires_at(&self) -> u64 {
+        self.timestamp.saturating_add(Self::DURATION)
+    }
+
+    /// Whether the typing state has expired.
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(utils::now())
+    }
+
+    /// Whether the typing state has expired as of a Unix timestamp in
+    /// seconds.
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.expires_at() <= timestamp
+    }
+}
+
+impl From<TypingStart> for TypingEntity {
+    fn from(typing: TypingStart) -> Self {
+        Self {
+            channel_id: typing.channel_id,
+            guild_id: typing.guild_id,
+            timestamp: typing.timestamp,
+            user_id: typing.user_id,
+        }
+    }
+}
+
+impl Entity for TypingEntity {
+    type Id = (ChannelId, UserId);
+
+    /// Return an ID consisting of a tuple of the channel ID and user ID.
+    fn id(&self) -> Self::Id {
+        (self.channel_id, self.user_id)
+    }
+}
+
+/// Repository to work with users typing in channels.
+///
+/// Discord doesn't send an event when a user stops typing, so typing states
+/// expire [`TypingEntity::DURATION`] seconds after their timestamp.
+///
+/// **Backend implementations**: expired typing states must not be returned,
+/// and should be removed lazily when they are encountered rather than by a
+/// background task.
+///
+/// [`TypingEntity::DURATION`]: struct.TypingEntity.html#associatedconstant.DURATION
+pub trait TypingRepository<B: Backend>: Repository<TypingEntity, B> {
+    /// Retrieve a stream of the users currently typing in a channel.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the typing states in [`Repository::list`], so backends with an index
+    /// of typing states by channel should override this.
+    ///
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn channel_typing(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, TypingEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.typing();
+            let now = utils::now();
+
+            let typing = repo
+                .list()
+                .await?
+                .try_filter(|typing| {
+                    future::ready(typing.channel_id == channel_id && !typing.is_expired_at(now))
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(typing.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve the user associated with a typing state.
+    fn user(&self, typing_id: (ChannelId, UserId)) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        utils::relation_map(
+            self.backend().typing(),
+            self.backend().users(),
+            typing_id,
+            |typing| typing.user_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypingEntity;
+    use twilight_model::id::{ChannelId, UserId};
+
+    #[test]
+    fn test_expiry() {
+        let typing = TypingEntity {
+            channel_id: ChannelId(1),
+            guild_id: None,
+            timestamp: 1_500_000_000,
+            user_id: UserId(2),
+        };
+
+        assert_eq!(1_500_000_010, typing.expires_at());
+        assert!(!typing.is_expired_at(1_500_000_009));
+        assert!(typing.is_expired_at(1_500_000_010));
+    }
+}
@@ -0,0 +1,104 @@
+use super::super::user::UserEntity;
+use crate::{
+    repository::{GetEntityFuture, ListEntitiesFuture, Repository},
+    utils, Backend, Entity,
+};
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use twilight_model::{
+    guild::Ban,
+    id::{GuildId, UserId},
+};
+
+/// Cachable version of a guild ban.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BanEntity {
+    pub guild_id: GuildId,
+    /// Reason for the ban.
+    ///
+    /// Gateway ban events don't include the reason, so this is only known
+    /// for bans retrieved over the HTTP API.
+    pub reason: Option<String>,
+    pub user_id: UserId,
+}
+
+impl From<(GuildId, Ban)> for BanEntity {
+    fn from((guild_id, ban): (GuildId, Ban)) -> Self {
+        Self {
+            guild_id,
+            reason: ban.reason,
+            user_id: ban.user.id,
+        }
+    }
+}
+
+impl Entity for BanEntity {
+    type Id = (GuildId, UserId);
+
+    /// Return an ID consisting of a tuple of the guild ID and user ID.
+    fn id(&self) -> Self::Id {
+        (self.guild_id, self.user_id)
+    }
+}
+
+pub trait BanRepository<B: Backend>: Repository<BanEntity, B> {
+    /// Retrieve a stream of the bans within a guild.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the bans in [`Repository::list`], so backends with an index of bans
+    /// by guild should override this.
+    ///
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn bans(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, BanEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.bans();
+
+            let bans = repo
+                .list()
+                .await?
+                .try_filter(|ban| future::ready(ban.guild_id == guild_id))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(bans.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve whether a user is banned from a guild.
+    ///
+    /// Returns `None` if the guild isn't in the cache. Only bans received
+    /// while the guild was cached are known, so `false` means that the cache
+    /// doesn't know of a ban rather than that the user isn't banned.
+    fn is_banned(&self, guild_id: GuildId, user_id: UserId) -> GetEntityFuture<'_, bool, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            if backend.guilds().get(guild_id).await?.is_none() {
+                return Ok(None);
+            }
+
+            let ban = backend.bans().get((guild_id, user_id)).await?;
+
+            Ok(Some(ban.is_some()))
+        })
+    }
+
+    /// Retrieve the banned user of a ban.
+    fn user(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        utils::relation_map(
+            self.backend().bans(),
+            self.backend().users(),
+            (guild_id, user_id),
+            |ban| ban.user_id,
+        )
+    }
+}
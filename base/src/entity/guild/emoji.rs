@@ -3,7 +3,10 @@ use crate::{
     repository::{GetEntityFuture, ListEntitiesFuture, Repository},
     utils, Backend, Entity,
 };
-use twilight_model::id::{EmojiId, GuildId, RoleId, UserId};
+use twilight_model::{
+    guild::Emoji,
+    id::{EmojiId, GuildId, RoleId, UserId},
+};
 
 /// Cachable version of an emoji.
 #[allow(clippy::struct_excessive_bools)]
@@ -21,6 +24,22 @@ pub struct EmojiEntity {
     pub user_id: Option<UserId>,
 }
 
+impl From<(GuildId, Emoji)> for EmojiEntity {
+    fn from((guild_id, emoji): (GuildId, Emoji)) -> Self {
+        Self {
+            animated: emoji.animated,
+            available: emoji.available,
+            guild_id,
+            id: emoji.id,
+            managed: emoji.managed,
+            name: emoji.name,
+            require_colons: emoji.require_colons,
+            role_ids: emoji.roles,
+            user_id: emoji.user.map(|user| user.id),
+        }
+    }
+}
+
 impl Entity for EmojiEntity {
     type Id = EmojiId;
 
@@ -31,6 +50,39 @@ impl Entity for EmojiEntity {
 }
 
 pub trait EmojiRepository<B: Backend>: Repository<EmojiEntity, B> {
+    /// Retrieve an emoji along with its creator as a full twilight [`Emoji`].
+    ///
+    /// The emoji's user is `None` if the creating user is not in the cache.
+    ///
+    /// [`Emoji`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Emoji.html
+    fn hydrate(&self, emoji_id: EmojiId) -> GetEntityFuture<'_, Emoji, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let emojis = backend.emojis();
+
+            let Some(emoji) = emojis.get(emoji_id).await? else {
+                return Ok(None);
+            };
+
+            let user = match emoji.user_id {
+                Some(user_id) => backend.users().get(user_id).await?,
+                None => None,
+            };
+
+            Ok(Some(Emoji {
+                animated: emoji.animated,
+                available: emoji.available,
+                id: emoji.id,
+                managed: emoji.managed,
+                name: emoji.name,
+                require_colons: emoji.require_colons,
+                roles: emoji.role_ids,
+                user: user.map(From::from),
+            }))
+        })
+    }
+
     /// Retrieve the guild associated with an emoji.
     fn guild(&self, emoji_id: EmojiId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
         utils::relation_map(
@@ -61,3 +113,47 @@ pub trait EmojiRepository<B: Backend>: Repository<EmojiEntity, B> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::EmojiEntity;
+    use twilight_model::{
+        guild::Emoji,
+        id::{EmojiId, GuildId, RoleId, UserId},
+        user::User,
+    };
+
+    #[test]
+    fn test_emoji_entity_from_emoji() {
+        let emoji = Emoji {
+            animated: false,
+            available: true,
+            id: EmojiId(1),
+            managed: false,
+            name: "emoji".to_owned(),
+            require_colons: true,
+            roles: vec![RoleId(2)],
+            user: Some(User {
+                avatar: None,
+                bot: false,
+                discriminator: "0001".to_owned(),
+                email: None,
+                flags: None,
+                id: UserId(3),
+                locale: None,
+                mfa_enabled: None,
+                name: "test".to_owned(),
+                premium_type: None,
+                public_flags: None,
+                system: None,
+                verified: None,
+            }),
+        };
+
+        let entity = EmojiEntity::from((GuildId(4), emoji));
+        assert_eq!(GuildId(4), entity.guild_id);
+        assert_eq!(EmojiId(1), entity.id);
+        assert_eq!(vec![RoleId(2)], entity.role_ids);
+        assert_eq!(Some(UserId(3)), entity.user_id);
+    }
+}
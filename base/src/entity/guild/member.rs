@@ -1,8 +1,11 @@
 use super::role::RoleEntity;
 use crate::{
+    entity::EntityField,
+    permission,
     repository::{GetEntityFuture, ListEntitiesFuture, Repository},
     utils, Backend, Entity,
 };
+use futures_util::{future, stream::TryStreamExt};
 use twilight_model::{
     guild::Member,
     id::{GuildId, RoleId, UserId},
@@ -47,7 +50,184 @@ impl Entity for MemberEntity {
     }
 }
 
+/// Update to a single field of a member.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum MemberField {
+    /// Add a role to the member if it doesn't already have it.
+    AddRoleId(RoleId),
+    Deaf(bool),
+    Mute(bool),
+    Nick(Option<String>),
+    PremiumSince(Option<String>),
+    /// Remove a role from the member, clearing its hoisted role if it's the
+    /// removed role.
+    RemoveRoleId(RoleId),
+    /// Replace the roles of the member, clearing its hoisted role if the
+    /// member no longer has it.
+    RoleIds(Vec<RoleId>),
+}
+
+impl EntityField<MemberEntity> for MemberField {
+    fn apply(self, member: &mut MemberEntity) {
+        match self {
+            Self::AddRoleId(role_id) => {
+                if !member.role_ids.contains(&role_id) {
+                    member.role_ids.push(role_id);
+                }
+            }
+            Self::Deaf(deaf) => member.deaf = deaf,
+            Self::Mute(mute) => member.mute = mute,
+            Self::Nick(nick) => member.nick = nick,
+            Self::PremiumSince(premium_since) => member.premium_since = premium_since,
+            Self::RemoveRoleId(role_id) => {
+                member.role_ids.retain(|id| *id != role_id);
+            }
+            Self::RoleIds(role_ids) => member.role_ids = role_ids,
+        }
+
+        if member
+            .hoisted_role_id
+            .is_some_and(|id| !member.role_ids.contains(&id))
+        {
+            member.hoisted_role_id = None;
+        }
+    }
+}
+
 pub trait MemberRepository<B: Backend>: Repository<MemberEntity, B> {
+    /// Whether a member can manage another member of the guild, such as by
+    /// kicking or banning them, based on the role hierarchy.
+    ///
+    /// The owner of the guild can manage every other member and can't be
+    /// managed. Otherwise, a member can manage another member if its highest
+    /// role is above the other member's highest role. A member can't manage
+    /// itself.
+    ///
+    /// This only considers the role hierarchy; permissions such as
+    /// `KICK_MEMBERS` need to be checked separately.
+    ///
+    /// Returns `None` if the guild or either of the members is not in the
+    /// cache.
+    fn can_manage(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let guilds = backend.guilds();
+            let members = backend.members();
+
+            let (guild, member, target) = future::try_join3(
+                guilds.get(guild_id),
+                members.get((guild_id, user_id)),
+                members.get((guild_id, target_user_id)),
+            )
+            .await?;
+
+            let (Some(guild), Some(_), Some(_)) = (guild, member, target) else {
+                return Ok(None);
+            };
+
+            if user_id == target_user_id || guild.owner_id == target_user_id {
+                return Ok(Some(false));
+            }
+
+            if guild.owner_id == user_id {
+                return Ok(Some(true));
+            }
+
+            let (roles, target_roles) = future::try_join(
+                members.roles(guild_id, user_id),
+                members.roles(guild_id, target_user_id),
+            )
+            .await?;
+            let (roles, target_roles) = future::try_join(
+                roles.try_collect::<Vec<_>>(),
+                target_roles.try_collect::<Vec<_>>(),
+            )
+            .await?;
+
+            Ok(Some(permission::is_above(
+                permission::highest_role(&roles),
+                permission::highest_role(&target_roles),
+            )))
+        })
+    }
+
+    /// Retrieve the colour that a member is displayed with, which is the
+    /// colour of the member's highest role that has a colour.
+    ///
+    /// Returns `None` if the member is not in the cache or none of its cached
+    /// roles have a colour.
+    fn color(&self, guild_id: GuildId, user_id: UserId) -> GetEntityFuture<'_, u32, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let members = backend.members();
+            let roles = members.roles(guild_id, user_id).await?;
+            let roles = roles.try_collect::<Vec<_>>().await?;
+
+            Ok(permission::color(&roles))
+        })
+    }
+
+    /// Retrieve the highest role of a member in the role hierarchy.
+    ///
+    /// Returns `None` if the member is not in the cache or none of its roles
+    /// are in the cache.
+    fn highest_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, RoleEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let members = backend.members();
+            let roles = members.roles(guild_id, user_id).await?;
+            let roles = roles.try_collect::<Vec<_>>().await?;
+
+            Ok(permission::highest_role(&roles).cloned())
+        })
+    }
+
+    /// Retrieve a member along with its user as a full twilight [`Member`].
+    ///
+    /// Returns `None` if either the member or its user is not in the cache.
+    ///
+    /// [`Member`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html
+    fn hydrate(&self, guild_id: GuildId, user_id: UserId) -> GetEntityFuture<'_, Member, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let members = backend.members();
+            let users = backend.users();
+
+            let (member, user) =
+                future::try_join(members.get((guild_id, user_id)), users.get(user_id)).await?;
+
+            let (Some(member), Some(user)) = (member, user) else {
+                return Ok(None);
+            };
+
+            Ok(Some(Member {
+                deaf: member.deaf,
+                guild_id: member.guild_id,
+                hoisted_role: member.hoisted_role_id,
+                joined_at: member.joined_at,
+                mute: member.mute,
+                nick: member.nick,
+                premium_since: member.premium_since,
+                roles: member.role_ids,
+                user: user.into(),
+            }))
+        })
+    }
+
     /// Retrieve the hoisted role associated with a role.
     fn hoisted_role(
         &self,
@@ -1,12 +1,14 @@
 //! Entities related to and within guilds.
 
+pub mod ban;
 pub mod emoji;
 pub mod member;
 pub mod role;
 
 pub use self::{
+    ban::{BanEntity, BanRepository},
     emoji::{EmojiEntity, EmojiRepository},
-    member::{MemberEntity, MemberRepository},
+    member::{MemberEntity, MemberField, MemberRepository},
     role::{RoleEntity, RoleRepository},
 };
 
@@ -17,15 +19,26 @@ use super::{
     voice::VoiceStateEntity,
 };
 use crate::{
-    repository::{GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, Repository},
+    repository::{
+        cascade, GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture,
+        Repository,
+    },
     utils, Backend, Entity,
 };
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use std::collections::HashMap;
 use twilight_model::{
+    channel::GuildChannel,
+    gateway::presence::Presence,
     guild::{
-        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, Permissions, PremiumTier,
-        SystemChannelFlags, VerificationLevel,
+        DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, MfaLevel, Permissions,
+        PremiumTier, Role, SystemChannelFlags, UnavailableGuild, VerificationLevel,
     },
     id::{ApplicationId, ChannelId, EmojiId, GuildId, RoleId, UserId},
+    voice::VoiceState,
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -77,6 +90,107 @@ pub struct GuildEntity {
     pub widget_enabled: Option<bool>,
 }
 
+impl From<Guild> for GuildEntity {
+    fn from(guild: Guild) -> Self {
+        Self {
+            afk_channel_id: guild.afk_channel_id,
+            afk_timeout: guild.afk_timeout,
+            application_id: guild.application_id,
+            approximate_member_count: guild.approximate_member_count,
+            approximate_presence_count: guild.approximate_presence_count,
+            banner: guild.banner,
+            default_message_notifications: guild.default_message_notifications,
+            description: guild.description,
+            discovery_splash: guild.discovery_splash,
+            embed_channel_id: guild.embed_channel_id,
+            embed_enabled: guild.embed_enabled,
+            explicit_content_filter: guild.explicit_content_filter,
+            features: guild.features,
+            icon: guild.icon,
+            id: guild.id,
+            joined_at: guild.joined_at,
+            large: guild.large,
+            lazy: guild.lazy,
+            max_members: guild.max_members,
+            max_presences: guild.max_presences,
+            max_video_channel_users: guild.max_video_channel_users,
+            member_count: guild.member_count,
+            mfa_level: guild.mfa_level,
+            name: guild.name,
+            owner_id: guild.owner_id,
+            owner: guild.owner,
+            permissions: guild.permissions,
+            preferred_locale: guild.preferred_locale,
+            premium_subscription_count: guild.premium_subscription_count,
+            premium_tier: guild.premium_tier,
+            region: guild.region,
+            rules_channel_id: guild.rules_channel_id,
+            splash: guild.splash,
+            system_channel_flags: guild.system_channel_flags,
+            system_channel_id: guild.system_channel_id,
+            unavailable: guild.unavailable,
+            vanity_url_code: guild.vanity_url_code,
+            verification_level: guild.verification_level,
+            widget_channel_id: guild.widget_channel_id,
+            widget_enabled: guild.widget_enabled,
+        }
+    }
+}
+
+/// Create a placeholder for a guild that is unavailable, such as the guilds
+/// listed in a `Ready` event before their `GuildCreate` event is received.
+///
+/// Only the ID and [`unavailable`] are known; the other fields are empty and
+/// are filled in once the guild becomes available.
+///
+/// [`unavailable`]: struct.GuildEntity.html#structfield.unavailable
+impl From<UnavailableGuild> for GuildEntity {
+    fn from(guild: UnavailableGuild) -> Self {
+        Self {
+            afk_channel_id: None,
+            afk_timeout: 0,
+            application_id: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            banner: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            description: None,
+            discovery_splash: None,
+            embed_channel_id: None,
+            embed_enabled: None,
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: Vec::new(),
+            icon: None,
+            id: guild.id,
+            joined_at: None,
+            large: false,
+            lazy: None,
+            max_members: None,
+            max_presences: None,
+            max_video_channel_users: None,
+            member_count: None,
+            mfa_level: MfaLevel::None,
+            name: String::new(),
+            owner_id: UserId(0),
+            owner: None,
+            permissions: None,
+            preferred_locale: String::new(),
+            premium_subscription_count: None,
+            premium_tier: PremiumTier::None,
+            region: String::new(),
+            rules_channel_id: None,
+            splash: None,
+            system_channel_flags: SystemChannelFlags::empty(),
+            system_channel_id: None,
+            unavailable: guild.unavailable,
+            vanity_url_code: None,
+            verification_level: VerificationLevel::None,
+            widget_channel_id: None,
+            widget_enabled: None,
+        }
+    }
+}
+
 impl Entity for GuildEntity {
     type Id = GuildId;
 
@@ -118,6 +232,140 @@ pub trait GuildRepository<B: Backend>: Repository<GuildEntity, B> {
         utils::stream_ids(self.emoji_ids(guild_id), self.backend().emojis())
     }
 
+    /// Retrieve a guild along with its channels, emojis, members, presences,
+    /// roles and voice states as a full twilight [`Guild`].
+    ///
+    /// Related entities that aren't in the cache are left out of the guild.
+    ///
+    /// [`Guild`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Guild.html
+    #[allow(clippy::too_many_lines)]
+    fn hydrate(&self, guild_id: GuildId) -> GetEntityFuture<'_, Guild, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let guilds = backend.guilds();
+
+            let Some(guild) = guilds.get(guild_id).await? else {
+                return Ok(None);
+            };
+
+            let channels = guilds
+                .channels(guild_id)
+                .await?
+                .map_ok(|channel| {
+                    let channel = GuildChannel::from(channel);
+
+                    (channel.id(), channel)
+                })
+                .try_collect::<HashMap<_, _>>()
+                .await?;
+
+            let emoji_ids = guilds
+                .emoji_ids(guild_id)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+            let emoji_repository = backend.emojis();
+            let emojis = future::try_join_all(
+                emoji_ids
+                    .into_iter()
+                    .map(|emoji_id| emoji_repository.hydrate(emoji_id)),
+            )
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|emoji| (emoji.id, emoji))
+            .collect();
+
+            let member_ids = guilds
+                .member_ids(guild_id)
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+            let member_repository = backend.members();
+            let members = future::try_join_all(
+                member_ids
+                    .into_iter()
+                    .map(|user_id| member_repository.hydrate(guild_id, user_id)),
+            )
+            .await?
+            .into_iter()
+            .flatten()
+            .map(|member| (member.user.id, member))
+            .collect();
+
+            let presences = guilds
+                .presences(guild_id)
+                .await?
+                .map_ok(|presence| (presence.user_id, Presence::from(presence)))
+                .try_collect::<HashMap<_, _>>()
+                .await?;
+
+            let roles = guilds
+                .roles(guild_id)
+                .await?
+                .map_ok(|role| (role.id, Role::from(role)))
+                .try_collect::<HashMap<_, _>>()
+                .await?;
+
+            let voice_states = guilds
+                .voice_states(guild_id)
+                .await?
+                .map_ok(|state| (state.user_id, VoiceState::from(state)))
+                .try_collect::<HashMap<_, _>>()
+                .await?;
+
+            Ok(Some(Guild {
+                afk_channel_id: guild.afk_channel_id,
+                afk_timeout: guild.afk_timeout,
+                application_id: guild.application_id,
+                approximate_member_count: guild.approximate_member_count,
+                approximate_presence_count: guild.approximate_presence_count,
+                banner: guild.banner,
+                channels,
+                default_message_notifications: guild.default_message_notifications,
+                description: guild.description,
+                discovery_splash: guild.discovery_splash,
+                embed_channel_id: guild.embed_channel_id,
+                embed_enabled: guild.embed_enabled,
+                emojis,
+                explicit_content_filter: guild.explicit_content_filter,
+                features: guild.features,
+                icon: guild.icon,
+                id: guild.id,
+                joined_at: guild.joined_at,
+                large: guild.large,
+                lazy: guild.lazy,
+                max_members: guild.max_members,
+                max_presences: guild.max_presences,
+                max_video_channel_users: guild.max_video_channel_users,
+                member_count: guild.member_count,
+                members,
+                mfa_level: guild.mfa_level,
+                name: guild.name,
+                owner_id: guild.owner_id,
+                owner: guild.owner,
+                permissions: guild.permissions,
+                preferred_locale: guild.preferred_locale,
+                premium_subscription_count: guild.premium_subscription_count,
+                premium_tier: guild.premium_tier,
+                presences,
+                region: guild.region,
+                roles,
+                rules_channel_id: guild.rules_channel_id,
+                splash: guild.splash,
+                system_channel_flags: guild.system_channel_flags,
+                system_channel_id: guild.system_channel_id,
+                unavailable: guild.unavailable,
+                vanity_url_code: guild.vanity_url_code,
+                verification_level: guild.verification_level,
+                voice_states,
+                widget_channel_id: guild.widget_channel_id,
+                widget_enabled: guild.widget_enabled,
+            }))
+        })
+    }
+
     /// Retrieve a stream of member IDs within a guild.
     fn member_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, UserId, B::Error>;
 
@@ -143,6 +391,19 @@ pub trait GuildRepository<B: Backend>: Repository<GuildEntity, B> {
     /// Retrieve a stream of presences within a guild.
     fn presences(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, PresenceEntity, B::Error>;
 
+    /// Remove a guild along with the entities it owns: its bans, channels,
+    /// emojis, invites, members, presences, roles and voice states.
+    ///
+    /// **Backend implementations**: a default implementation is provided
+    /// that removes the related entities found through the guild's relation
+    /// methods, as described by [`cascade::guild`]. Backends with a more
+    /// efficient native way may override it.
+    ///
+    /// [`cascade::guild`]: ../../repository/cascade/fn.guild.html
+    fn remove_cascade(&self, guild_id: GuildId) -> RemoveEntityFuture<'_, B::Error> {
+        cascade::guild(self.backend(), guild_id)
+    }
+
     /// Retrieve a stream of role IDs within a guild.
     fn role_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, RoleId, B::Error>;
 
@@ -186,6 +447,28 @@ pub trait GuildRepository<B: Backend>: Repository<GuildEntity, B> {
         )
     }
 
+    /// Retrieve a stream of the IDs of guilds that are unavailable.
+    ///
+    /// This includes guilds listed in the `Ready` event which haven't been
+    /// received in a `GuildCreate` event yet, as well as guilds that became
+    /// unavailable due to an outage. Once this is empty the cache is warm.
+    fn unavailable_ids(&self) -> ListEntityIdsFuture<'_, GuildId, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let guilds = backend.guilds();
+
+            let ids = guilds
+                .list()
+                .await?
+                .try_filter_map(|guild| future::ok(guild.unavailable.then_some(guild.id)))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(ids.into_iter().map(Ok)).boxed())
+        })
+    }
+
     /// Retrieve a stream of voice states' user IDs within a guild.
     fn voice_state_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, UserId, B::Error>;
 
@@ -240,3 +523,82 @@ pub trait GuildRepository<B: Backend>: Repository<GuildEntity, B> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::GuildEntity;
+    use std::collections::HashMap;
+    use twilight_model::{
+        guild::{
+            DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, MfaLevel, PremiumTier,
+            SystemChannelFlags, VerificationLevel,
+        },
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    #[test]
+    fn test_guild_entity_from_guild() {
+        let guild = Guild {
+            afk_channel_id: Some(ChannelId(2)),
+            afk_timeout: 300,
+            application_id: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            banner: None,
+            channels: HashMap::new(),
+            default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+            description: Some("description".to_owned()),
+            discovery_splash: None,
+            embed_channel_id: None,
+            embed_enabled: None,
+            emojis: HashMap::new(),
+            explicit_content_filter: ExplicitContentFilter::AllMembers,
+            features: vec!["COMMUNITY".to_owned()],
+            icon: None,
+            id: GuildId(1),
+            joined_at: Some("2020-01-01T00:00:00+00:00".to_owned()),
+            large: true,
+            lazy: None,
+            max_members: Some(250_000),
+            max_presences: None,
+            max_video_channel_users: None,
+            member_count: Some(10),
+            members: HashMap::new(),
+            mfa_level: MfaLevel::Elevated,
+            name: "guild".to_owned(),
+            owner_id: UserId(3),
+            owner: None,
+            permissions: None,
+            preferred_locale: "en-US".to_owned(),
+            premium_subscription_count: Some(2),
+            premium_tier: PremiumTier::Tier1,
+            presences: HashMap::new(),
+            region: "us-east".to_owned(),
+            roles: HashMap::new(),
+            rules_channel_id: None,
+            splash: None,
+            system_channel_flags: SystemChannelFlags::SUPPRESS_JOIN_NOTIFICATIONS,
+            system_channel_id: Some(ChannelId(4)),
+            unavailable: false,
+            vanity_url_code: None,
+            verification_level: VerificationLevel::High,
+            voice_states: HashMap::new(),
+            widget_channel_id: None,
+            widget_enabled: None,
+        };
+
+        let entity = GuildEntity::from(guild);
+        assert_eq!(GuildId(1), entity.id);
+        assert_eq!("guild", entity.name);
+        assert_eq!(UserId(3), entity.owner_id);
+        assert_eq!(Some(ChannelId(2)), entity.afk_channel_id);
+        assert_eq!(Some(ChannelId(4)), entity.system_channel_id);
+        assert_eq!(vec!["COMMUNITY".to_owned()], entity.features);
+        assert_eq!(PremiumTier::Tier1, entity.premium_tier);
+        assert_eq!(VerificationLevel::High, entity.verification_level);
+        assert_eq!(
+            Some("2020-01-01T00:00:00+00:00"),
+            entity.joined_at.as_deref(),
+        );
+    }
+}
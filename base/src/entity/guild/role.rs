@@ -1,11 +1,13 @@
-use super::GuildEntity;
+use super::{GuildEntity, MemberRepository};
 use crate::{
+    permission,
     repository::{GetEntityFuture, Repository},
     utils, Backend, Entity,
 };
+use futures_util::{future, stream::TryStreamExt};
 use twilight_model::{
-    guild::Permissions,
-    id::{GuildId, RoleId},
+    guild::{Permissions, Role},
+    id::{GuildId, RoleId, UserId},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -22,6 +24,37 @@ pub struct RoleEntity {
     pub position: i64,
 }
 
+impl From<(GuildId, Role)> for RoleEntity {
+    fn from((guild_id, role): (GuildId, Role)) -> Self {
+        Self {
+            color: role.color,
+            guild_id,
+            hoist: role.hoist,
+            id: role.id,
+            managed: role.managed,
+            mentionable: role.mentionable,
+            name: role.name,
+            permissions: role.permissions,
+            position: role.position,
+        }
+    }
+}
+
+impl From<RoleEntity> for Role {
+    fn from(role: RoleEntity) -> Self {
+        Self {
+            color: role.color,
+            hoist: role.hoist,
+            id: role.id,
+            managed: role.managed,
+            mentionable: role.mentionable,
+            name: role.name,
+            permissions: role.permissions,
+            position: role.position,
+        }
+    }
+}
+
 impl Entity for RoleEntity {
     type Id = RoleId;
 
@@ -41,4 +74,84 @@ pub trait RoleRepository<B: Backend>: Repository<RoleEntity, B> {
             |role| role.guild_id,
         )
     }
+
+    /// Whether a member can manage a role, such as by editing or assigning
+    /// it, based on the role hierarchy.
+    ///
+    /// The owner of the guild can manage every role. Otherwise, a member can
+    /// manage a role if its highest role is above the role.
+    ///
+    /// This only considers the role hierarchy; the `MANAGE_ROLES` permission
+    /// needs to be checked separately.
+    ///
+    /// Returns `None` if the role, its guild, or the member is not in the
+    /// cache.
+    fn manageable_by(
+        &self,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let Some(role) = backend.roles().get(role_id).await? else {
+                return Ok(None);
+            };
+
+            let guilds = backend.guilds();
+            let members = backend.members();
+
+            let (guild, member) = future::try_join(
+                guilds.get(role.guild_id),
+                members.get((role.guild_id, user_id)),
+            )
+            .await?;
+
+            let (Some(guild), Some(_)) = (guild, member) else {
+                return Ok(None);
+            };
+
+            if guild.owner_id == user_id {
+                return Ok(Some(true));
+            }
+
+            let roles = members.roles(role.guild_id, user_id).await?;
+            let roles = roles.try_collect::<Vec<_>>().await?;
+
+            Ok(Some(permission::is_above(
+                permission::highest_role(&roles),
+                Some(&role),
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RoleEntity;
+    use twilight_model::{
+        guild::{Permissions, Role},
+        id::{GuildId, RoleId},
+    };
+
+    #[test]
+    fn test_role_entity_from_role() {
+        let role = Role {
+            color: 0x00ff_0000,
+            hoist: true,
+            id: RoleId(1),
+            managed: false,
+            mentionable: true,
+            name: "role".to_owned(),
+            permissions: Permissions::KICK_MEMBERS,
+            position: 3,
+        };
+
+        let entity = RoleEntity::from((GuildId(2), role));
+        assert_eq!(GuildId(2), entity.guild_id);
+        assert_eq!(RoleId(1), entity.id);
+        assert_eq!(0x00ff_0000, entity.color);
+        assert_eq!(Permissions::KICK_MEMBERS, entity.permissions);
+        assert_eq!(3, entity.position);
+    }
 }
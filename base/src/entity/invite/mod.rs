@@ -0,0 +1,251 @@
+//! Entities relating to guild invites.
+
+use super::{channel::GuildChannelEntity, guild::GuildEntity, user::UserEntity};
+use crate::{
+    repository::{GetEntityFuture, ListEntitiesFuture, Repository},
+    utils, Backend, Entity,
+};
+use futures_util::{
+    future,
+    stream::{self, StreamExt, TryStreamExt},
+};
+use twilight_model::{
+    gateway::payload::InviteCreate,
+    id::{ChannelId, GuildId, UserId},
+    invite::TargetUserType,
+};
+
+/// Cachable version of an invite to a guild channel.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InviteEntity {
+    pub channel_id: ChannelId,
+    pub code: String,
+    /// ISO 8601 timestamp of when the invite was created.
+    pub created_at: String,
+    pub guild_id: GuildId,
+    pub inviter_id: Option<UserId>,
+    /// Number of seconds after creation that the invite is valid for, or 0 if
+    /// it never expires.
+    pub max_age: u64,
+    /// Maximum number of times the invite can be used, or 0 if it is
+    /// unlimited.
+    pub max_uses: u64,
+    pub target_user_type: Option<TargetUserType>,
+    pub temporary: bool,
+    pub uses: u64,
+}
+
+impl InviteEntity {
+    /// Return the Unix timestamp, in seconds, of when the invite expires.
+    ///
+    /// Returns `None` if the invite never expires or if its creation time
+    /// can't be parsed.
+    pub fn expires_at(&self) -> Option<u64> {
+        if self.max_age == 0 {
+            return None;
+        }
+
+        utils::parse_timestamp(&self.created_at).map(|created_at| created_at + self.max_age)
+    }
+
+    /// Whether the invite has expired based on its [`max_age`].
+    ///
+    /// The cache doesn't remove invites when they expire, since Discord
+    /// doesn't send an event for this, so expired invites remain in the cache
+    /// until they are removed.
+    ///
+    /// [`max_age`]: #structfield.max_age
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(utils::now())
+    }
+
+    /// Whether the invite has expired as of a Unix timestamp in seconds.
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at <= timestamp)
+    }
+}
+
+impl From<InviteCreate> for InviteEntity {
+    fn from(invite: InviteCreate) -> Self {
+        Self {
+            channel_id: invite.channel_id,
+            code: invite.code,
+            created_at: invite.created_at,
+            guild_id: invite.guild_id,
+            inviter_id: invite.inviter.map(|user| user.id),
+            max_age: invite.max_age,
+            max_uses: invite.max_uses,
+            target_user_type: invite.target_user_type,
+            temporary: invite.temporary,
+            uses: invite.uses.into(),
+        }
+    }
+}
+
+impl Entity for InviteEntity {
+    type Id = String;
+
+    /// Return the invite's code.
+    fn id(&self) -> Self::Id {
+        self.code.clone()
+    }
+}
+
+pub trait InviteRepository<B: Backend>: Repository<InviteEntity, B> {
+    /// Retrieve the channel associated with an invite.
+    fn channel(&self, code: String) -> GetEntityFuture<'_, GuildChannelEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let invites = backend.invites();
+
+            let Some(channel_id) = invites.get(code).await?.map(|invite| invite.channel_id) else {
+                return Ok(None);
+            };
+
+            let text_channels = backend.text_channels();
+
+            if let Some(channel) = text_channels.get(channel_id).await? {
+                return Ok(Some(GuildChannelEntity::Text(channel)));
+            }
+
+            let voice_channels = backend.voice_channels();
+
+            if let Some(channel) = voice_channels.get(channel_id).await? {
+                return Ok(Some(GuildChannelEntity::Voice(channel)));
+            }
+
+            let category_channels = backend.category_channels();
+
+            Ok(category_channels
+                .get(channel_id)
+                .await?
+                .map(GuildChannelEntity::Category))
+        })
+    }
+
+    /// Retrieve a stream of the invites to a channel.
+    ///
+    /// Expired invites are included; use [`InviteEntity::is_expired`] to
+    /// filter them out.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the invites in [`Repository::list`], so backends with an index of
+    /// invites by channel should override this.
+    ///
+    /// [`InviteEntity::is_expired`]: struct.InviteEntity.html#method.is_expired
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn channel_invites(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.invites();
+
+            let invites = repo
+                .list()
+                .await?
+                .try_filter(|invite| future::ready(invite.channel_id == channel_id))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(invites.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve the guild associated with an invite.
+    fn guild(&self, code: String) -> GetEntityFuture<'_, GuildEntity, B::Error> {
+        utils::relation_map(
+            self.backend().invites(),
+            self.backend().guilds(),
+            code,
+            |invite| invite.guild_id,
+        )
+    }
+
+    /// Retrieve a stream of the invites within a guild.
+    ///
+    /// Expired invites are included; use [`InviteEntity::is_expired`] to
+    /// filter them out.
+    ///
+    /// **Backend implementations**: the default implementation filters all
+    /// of the invites in [`Repository::list`], so backends with an index of
+    /// invites by guild should override this.
+    ///
+    /// [`InviteEntity::is_expired`]: struct.InviteEntity.html#method.is_expired
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn guild_invites(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, InviteEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.invites();
+
+            let invites = repo
+                .list()
+                .await?
+                .try_filter(|invite| future::ready(invite.guild_id == guild_id))
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            Ok(stream::iter(invites.into_iter().map(Ok)).boxed())
+        })
+    }
+
+    /// Retrieve the user who created an invite.
+    ///
+    /// Backend implementations should return `None` if the inviter isn't
+    /// known or is not present in the cache.
+    fn inviter(&self, code: String) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        utils::relation_and_then(
+            self.backend().invites(),
+            self.backend().users(),
+            code,
+            |invite| invite.inviter_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InviteEntity;
+    use twilight_model::id::{ChannelId, GuildId};
+
+    fn invite(created_at: &str, max_age: u64) -> InviteEntity {
+        InviteEntity {
+            channel_id: ChannelId(2),
+            code: "code".to_owned(),
+            created_at: created_at.to_owned(),
+            guild_id: GuildId(1),
+            inviter_id: None,
+            max_age,
+            max_uses: 0,
+            target_user_type: None,
+            temporary: false,
+            uses: 0,
+        }
+    }
+
+    #[test]
+    fn test_expires_at() {
+        // 2020-05-30T20:52:43Z
+        let created_at = 1_590_871_963;
+
+        let entity = invite("2020-05-30T20:52:43.457000+00:00", 86_400);
+        assert_eq!(Some(created_at + 86_400), entity.expires_at());
+        assert!(!entity.is_expired_at(created_at + 86_399));
+        assert!(entity.is_expired_at(created_at + 86_400));
+
+        let entity = invite("2020-05-30T22:52:43+02:00", 60);
+        assert_eq!(Some(created_at + 60), entity.expires_at());
+
+        let entity = invite("2020-05-30T20:52:43.457000+00:00", 0);
+        assert!(entity.expires_at().is_none());
+        assert!(!entity.is_expired());
+
+        assert!(invite("not a timestamp", 60).expires_at().is_none());
+    }
+}
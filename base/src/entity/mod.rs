@@ -1,6 +1,7 @@
 pub mod channel;
 pub mod gateway;
 pub mod guild;
+pub mod invite;
 pub mod user;
 pub mod voice;
 
@@ -25,3 +26,30 @@ pub trait Entity: Send {
     /// [`MemberEntity`]: member/struct.MemberEntity.html
     fn id(&self) -> Self::Id;
 }
+
+/// Update to a single field of an entity, such as a member's nickname.
+///
+/// Entities that can be partially updated have a matching field type, such as
+/// [`MemberField`] for the [`MemberEntity`], which can be passed to
+/// [`Repository::update_field`]. Multiple updates can be applied together by
+/// passing a `Vec` of them.
+///
+/// Updates may be applied more than once by backends that retry when the
+/// entity is concurrently modified, so applying an update must not have side
+/// effects.
+///
+/// [`MemberEntity`]: guild/struct.MemberEntity.html
+/// [`MemberField`]: guild/enum.MemberField.html
+/// [`Repository::update_field`]: ../trait.Repository.html#method.update_field
+pub trait EntityField<E: Entity>: Clone + Send + 'static {
+    /// Apply the update to an entity.
+    fn apply(self, entity: &mut E);
+}
+
+impl<E: Entity, F: EntityField<E>> EntityField<E> for Vec<F> {
+    fn apply(self, entity: &mut E) {
+        for field in self {
+            field.apply(entity);
+        }
+    }
+}
@@ -1,5 +1,13 @@
-use crate::{repository::{guild::GuildEntity, ListEntityIdsFuture, ListEntitiesFuture, Repository}, Entity};
-use twilight_model::id::{GuildId, UserId};
+use crate::{
+    entity::{guild::GuildEntity, Entity},
+    repository::{GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, Repository},
+    utils, Backend,
+};
+use futures_util::stream::StreamExt;
+use twilight_model::{
+    id::{GuildId, UserId},
+    user::CurrentUser,
+};
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -14,6 +22,36 @@ pub struct CurrentUserEntity {
     pub verified: bool,
 }
 
+impl From<CurrentUser> for CurrentUserEntity {
+    fn from(user: CurrentUser) -> Self {
+        Self {
+            avatar: user.avatar,
+            bot: user.bot,
+            discriminator: user.discriminator,
+            email: user.email,
+            id: user.id,
+            mfa_enabled: user.mfa_enabled,
+            name: user.name,
+            verified: user.verified,
+        }
+    }
+}
+
+impl From<CurrentUserEntity> for CurrentUser {
+    fn from(user: CurrentUserEntity) -> Self {
+        Self {
+            avatar: user.avatar,
+            bot: user.bot,
+            discriminator: user.discriminator,
+            email: user.email,
+            id: user.id,
+            mfa_enabled: user.mfa_enabled,
+            name: user.name,
+            verified: user.verified,
+        }
+    }
+}
+
 impl Entity for CurrentUserEntity {
     type Id = UserId;
 
@@ -23,10 +61,57 @@ impl Entity for CurrentUserEntity {
     }
 }
 
-pub trait CurrentUserRepository<Error: 'static>: Repository<CurrentUserEntity, Error> {
-    /// Retrieve a stream of guild IDs associated with the current user.
-    fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, Error>;
+pub trait CurrentUserRepository<B: Backend>: Repository<CurrentUserEntity, B> {
+    /// Retrieve the current user.
+    ///
+    /// Returns `None` if the current user hasn't been cached yet, such as
+    /// before the first `Ready` event.
+    ///
+    /// **Backend implementations**: there's only ever one current user, so
+    /// the default implementation returns the first current user in
+    /// [`Repository::list`].
+    ///
+    /// [`Repository::list`]: ../../trait.Repository.html#tymethod.list
+    fn current(&self) -> GetEntityFuture<'_, CurrentUserEntity, B::Error> {
+        let backend = self.backend();
+
+        Box::pin(async move {
+            let repo = backend.current_user();
+            let mut users = repo.list().await?;
+
+            users.next().await.transpose()
+        })
+    }
+
+    /// Retrieve a stream of the IDs of guilds the current user is in.
+    fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, B::Error>;
+
+    /// Retrieve a stream of the guilds the current user is in.
+    fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, B::Error> {
+        utils::stream_ids(self.guild_ids(), self.backend().guilds())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CurrentUserEntity;
+    use twilight_model::{id::UserId, user::CurrentUser};
 
-    /// Retrieve a stream of guilds associated with the current user.
-    fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, Error>;
+    #[test]
+    fn test_current_user_entity_roundtrip() {
+        let user = CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            id: UserId(1),
+            mfa_enabled: true,
+            name: "bot".to_owned(),
+            verified: true,
+        };
+
+        let entity = CurrentUserEntity::from(user.clone());
+        assert_eq!(UserId(1), entity.id);
+        assert_eq!(user, CurrentUser::from(entity));
+    }
 }
@@ -1,5 +1,9 @@
 //! Entities related to users.
 
+pub mod current_user;
+
+pub use self::current_user::{CurrentUserEntity, CurrentUserRepository};
+
 use crate::{
     entity::{guild::GuildEntity, Entity},
     repository::{ListEntitiesFuture, ListEntityIdsFuture, Repository},
@@ -7,7 +11,7 @@ use crate::{
 };
 use twilight_model::{
     id::{GuildId, UserId},
-    user::{PremiumType, UserFlags},
+    user::{PremiumType, User, UserFlags},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -28,6 +32,46 @@ pub struct UserEntity {
     pub verified: Option<bool>,
 }
 
+impl From<User> for UserEntity {
+    fn from(user: User) -> Self {
+        Self {
+            avatar: user.avatar,
+            bot: user.bot,
+            discriminator: user.discriminator,
+            email: user.email,
+            flags: user.flags,
+            id: user.id,
+            locale: user.locale,
+            mfa_enabled: user.mfa_enabled,
+            name: user.name,
+            premium_type: user.premium_type,
+            public_flags: user.public_flags,
+            system: user.system,
+            verified: user.verified,
+        }
+    }
+}
+
+impl From<UserEntity> for User {
+    fn from(user: UserEntity) -> Self {
+        Self {
+            avatar: user.avatar,
+            bot: user.bot,
+            discriminator: user.discriminator,
+            email: user.email,
+            flags: user.flags,
+            id: user.id,
+            locale: user.locale,
+            mfa_enabled: user.mfa_enabled,
+            name: user.name,
+            premium_type: user.premium_type,
+            public_flags: user.public_flags,
+            system: user.system,
+            verified: user.verified,
+        }
+    }
+}
+
 impl Entity for UserEntity {
     type Id = UserId;
 
@@ -46,3 +90,40 @@ pub trait UserRepository<B: Backend>: Repository<UserEntity, B> {
         utils::stream_ids(self.guild_ids(user_id), self.backend().guilds())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::UserEntity;
+    use twilight_model::{
+        id::UserId,
+        user::{PremiumType, User, UserFlags},
+    };
+
+    #[test]
+    fn test_user_entity_from_user() {
+        let user = User {
+            avatar: Some("hash".to_owned()),
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(1),
+            locale: Some("en-US".to_owned()),
+            mfa_enabled: Some(true),
+            name: "test".to_owned(),
+            premium_type: Some(PremiumType::Nitro),
+            public_flags: Some(UserFlags::VERIFIED_BOT),
+            system: None,
+            verified: Some(true),
+        };
+
+        let entity = UserEntity::from(user);
+        assert_eq!(UserId(1), entity.id);
+        assert_eq!("test", entity.name);
+        assert_eq!("0001", entity.discriminator);
+        assert_eq!(Some("hash"), entity.avatar.as_deref());
+        assert!(entity.bot);
+        assert_eq!(Some(PremiumType::Nitro), entity.premium_type);
+        assert_eq!(Some(UserFlags::VERIFIED_BOT), entity.public_flags);
+    }
+}
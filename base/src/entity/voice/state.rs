@@ -3,7 +3,10 @@ use crate::{
     repository::{GetEntityFuture, Repository},
     utils, Backend, Entity,
 };
-use twilight_model::id::{ChannelId, GuildId, UserId};
+use twilight_model::{
+    id::{ChannelId, GuildId, UserId},
+    voice::VoiceState,
+};
 
 #[allow(clippy::struct_excessive_bools)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -21,6 +24,42 @@ pub struct VoiceStateEntity {
     pub user_id: UserId,
 }
 
+impl From<(GuildId, VoiceState)> for VoiceStateEntity {
+    fn from((guild_id, state): (GuildId, VoiceState)) -> Self {
+        Self {
+            channel_id: state.channel_id,
+            deaf: state.deaf,
+            guild_id,
+            mute: state.mute,
+            self_deaf: state.self_deaf,
+            self_mute: state.self_mute,
+            self_stream: state.self_stream,
+            session_id: state.session_id,
+            suppress: state.suppress,
+            user_id: state.user_id,
+        }
+    }
+}
+
+impl From<VoiceStateEntity> for VoiceState {
+    fn from(state: VoiceStateEntity) -> Self {
+        Self {
+            channel_id: state.channel_id,
+            deaf: state.deaf,
+            guild_id: Some(state.guild_id),
+            member: None,
+            mute: state.mute,
+            self_deaf: state.self_deaf,
+            self_mute: state.self_mute,
+            self_stream: state.self_stream,
+            session_id: state.session_id,
+            suppress: state.suppress,
+            token: None,
+            user_id: state.user_id,
+        }
+    }
+}
+
 impl Entity for VoiceStateEntity {
     type Id = (GuildId, UserId);
 
@@ -49,3 +88,36 @@ pub trait VoiceStateRepository<B: Backend>: Repository<VoiceStateEntity, B> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::VoiceStateEntity;
+    use twilight_model::{
+        id::{ChannelId, GuildId, UserId},
+        voice::VoiceState,
+    };
+
+    #[test]
+    fn test_voice_state_entity_from_voice_state() {
+        let state = VoiceState {
+            channel_id: Some(ChannelId(1)),
+            deaf: false,
+            guild_id: None,
+            member: None,
+            mute: true,
+            self_deaf: true,
+            self_mute: false,
+            self_stream: false,
+            session_id: "session".to_owned(),
+            suppress: false,
+            token: None,
+            user_id: UserId(2),
+        };
+
+        let entity = VoiceStateEntity::from((GuildId(3), state));
+        assert_eq!((GuildId(3), UserId(2)), (entity.guild_id, entity.user_id));
+        assert_eq!(Some(ChannelId(1)), entity.channel_id);
+        assert!(entity.mute && entity.self_deaf);
+        assert_eq!("session", entity.session_id);
+    }
+}
@@ -20,6 +20,7 @@
 //! Here's a list of backends supported by Rarity:
 //!
 //! - [`rarity-cache-inmemory`]: datastore in the process's memory
+//! - [`rarity-cache-redis`]: datastore in a Redis server
 //!
 //! # Usage
 //!
@@ -86,14 +87,27 @@
 //! trait for each entity, and implement the [`Backend`] trait which returns
 //! instances of those repositories.
 //!
-//! For detailed information, read the documentation for both traits.
+//! For detailed information, read the documentation for both traits. The
+//! [`conformance`] suite can be run in a backend's tests to check that it
+//! behaves the way the cache expects.
 //!
 //! # Features
 //!
 //! The `serde` feature can be disabled to remove the `Deserialize` and
 //! `Serialize` implementations on entities. It is enabled by default.
 //!
+//! The `conformance` feature enables the [`conformance`] module, a suite of
+//! tests for backend implementations to run against their backends. It is
+//! disabled by default.
+//!
+//! The `replay` feature enables the [`replay`] module, for recording gateway
+//! events and replaying them into a cache to reproduce its state. It enables
+//! the `serde` feature and is disabled by default.
+//!
+//! [`conformance`]: conformance/index.html
+//! [`replay`]: replay/index.html
 //! [`rarity-cache-inmemory`]: ../rarity_cache_inmemory/index.html
+//! [`rarity-cache-redis`]: ../rarity_cache_redis/index.html
 //! [docs:repo:microsoft]: https://docs.microsoft.com/en-us/dotnet/architecture/microservices/microservice-ddd-cqrs-patterns/infrastructure-persistence-layer-design
 
 #![deny(
@@ -107,16 +121,32 @@
 )]
 #![allow(
     clippy::doc_markdown,
+    clippy::inconsistent_struct_constructor,
+    clippy::manual_let_else,
     clippy::match_same_arms,
     clippy::module_name_repetitions,
-    clippy::must_use_candidate
+    clippy::must_use_candidate,
+    clippy::needless_continue,
+    clippy::redundant_closure
 )]
 
+pub mod config;
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod entity;
+pub mod permission;
+#[cfg(feature = "replay")]
+pub mod replay;
 pub mod repository;
 
 mod backend;
 mod cache;
 mod utils;
 
-pub use self::{backend::Backend, cache::Cache, entity::Entity, repository::Repository};
+pub use self::{
+    backend::{Backend, NoopBackend},
+    cache::Cache,
+    entity::Entity,
+    repository::Repository,
+    utils::now,
+};
@@ -0,0 +1,466 @@
+//! Calculation of a member's permissions in a guild or a guild channel, and of
+//! the position of roles in a guild's role hierarchy.
+//!
+//! These functions work on entities that have already been retrieved, so they
+//! can be used with any backend. [`Cache::guild_permissions`] and
+//! [`Cache::channel_permissions`] retrieve the entities from the cache and
+//! call these.
+//!
+//! [`Cache::channel_permissions`]: ../struct.Cache.html#method.channel_permissions
+//! [`Cache::guild_permissions`]: ../struct.Cache.html#method.guild_permissions
+
+use crate::entity::guild::{GuildEntity, MemberEntity, RoleEntity};
+use std::cmp::Ordering;
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::RoleId,
+};
+
+/// Calculate the permissions of a member in a guild.
+///
+/// The owner of the guild and members with the `ADMINISTRATOR` permission have
+/// all permissions. Otherwise, the permissions of the @everyone role are
+/// combined with the permissions of the member's roles.
+///
+/// `roles` may contain roles the member doesn't have; only the @everyone role,
+/// which has the same ID as the guild, and the roles in the member's
+/// [`role_ids`] are used.
+///
+/// [`role_ids`]: ../entity/guild/struct.MemberEntity.html#structfield.role_ids
+pub fn guild(guild: &GuildEntity, member: &MemberEntity, roles: &[RoleEntity]) -> Permissions {
+    if guild.owner_id == member.user_id {
+        return Permissions::all();
+    }
+
+    let everyone_id = RoleId(guild.id.0);
+
+    let permissions = roles
+        .iter()
+        .filter(|role| role.id == everyone_id || member.role_ids.contains(&role.id))
+        .fold(Permissions::empty(), |permissions, role| {
+            permissions | role.permissions
+        });
+
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    permissions
+}
+
+/// Calculate the permissions of a member in a guild channel.
+///
+/// The member's [guild permissions] are modified by the channel's permission
+/// overwrites in order: the @everyone overwrite, then the overwrites of the
+/// member's roles, and lastly the overwrite of the member itself. Denied
+/// permissions are removed before allowed permissions are added at each step.
+///
+/// Permissions that depend on other permissions are implicitly denied: a
+/// member who can't view the channel has no permissions in it, and a member
+/// who can't send messages in a text channel can't send TTS messages, mention
+/// @everyone, embed links, or attach files either.
+///
+/// [guild permissions]: fn.guild.html
+pub fn channel(
+    guild: &GuildEntity,
+    member: &MemberEntity,
+    roles: &[RoleEntity],
+    kind: ChannelType,
+    overwrites: &[PermissionOverwrite],
+) -> Permissions {
+    let mut permissions = self::guild(guild, member, roles);
+
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return permissions;
+    }
+
+    let everyone_id = RoleId(guild.id.0);
+    let mut role_allow = Permissions::empty();
+    let mut role_deny = Permissions::empty();
+    let mut member_overwrite = None;
+
+    for overwrite in overwrites {
+        match overwrite.kind {
+            PermissionOverwriteType::Role(role_id) if role_id == everyone_id => {
+                permissions.remove(overwrite.deny);
+                permissions.insert(overwrite.allow);
+            }
+            PermissionOverwriteType::Role(role_id) if member.role_ids.contains(&role_id) => {
+                role_allow.insert(overwrite.allow);
+                role_deny.insert(overwrite.deny);
+            }
+            PermissionOverwriteType::Member(user_id) if user_id == member.user_id => {
+                member_overwrite.replace(overwrite);
+            }
+            _ => {}
+        }
+    }
+
+    permissions.remove(role_deny);
+    permissions.insert(role_allow);
+
+    if let Some(overwrite) = member_overwrite {
+        permissions.remove(overwrite.deny);
+        permissions.insert(overwrite.allow);
+    }
+
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+
+    if is_text(kind) && !permissions.contains(Permissions::SEND_MESSAGES) {
+        permissions.remove(
+            Permissions::SEND_TTS_MESSAGES
+                | Permissions::MENTION_EVERYONE
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES,
+        );
+    }
+
+    permissions
+}
+
+/// Compare the positions of two roles in a guild's role hierarchy.
+///
+/// Roles are ordered by their position. Roles with the same position are
+/// ordered by their IDs, where the older role - the one with the lower ID - is
+/// higher.
+pub fn cmp_roles(a: &RoleEntity, b: &RoleEntity) -> Ordering {
+    a.position
+        .cmp(&b.position)
+        .then_with(|| b.id.0.cmp(&a.id.0))
+}
+
+/// Return the highest of a member's roles in the role hierarchy.
+///
+/// Returns `None` if there are no roles.
+pub fn highest_role<'a>(roles: impl IntoIterator<Item = &'a RoleEntity>) -> Option<&'a RoleEntity> {
+    roles.into_iter().max_by(|a, b| cmp_roles(a, b))
+}
+
+/// Return the colour a member is displayed with, which is the colour of the
+/// highest of the member's roles that has a colour.
+///
+/// Roles with a colour of `0` don't have a colour. Returns `None` if none of
+/// the roles have a colour.
+pub fn color<'a>(roles: impl IntoIterator<Item = &'a RoleEntity>) -> Option<u32> {
+    highest_role(roles.into_iter().filter(|role| role.color != 0)).map(|role| role.color)
+}
+
+/// Whether a member with the highest role `actor` is above a member or role
+/// with the highest role `target` in the role hierarchy.
+///
+/// A member without any roles is below every role.
+pub fn is_above(actor: Option<&RoleEntity>, target: Option<&RoleEntity>) -> bool {
+    match (actor, target) {
+        (Some(actor), Some(target)) => cmp_roles(actor, target) == Ordering::Greater,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn is_text(kind: ChannelType) -> bool {
+    matches!(
+        kind,
+        ChannelType::GuildText | ChannelType::GuildNews | ChannelType::GuildStore
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::guild::{GuildEntity, MemberEntity, RoleEntity};
+    use std::cmp::Ordering;
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::{
+            DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, Permissions,
+            PremiumTier, SystemChannelFlags, VerificationLevel,
+        },
+        id::{GuildId, RoleId, UserId},
+    };
+
+    fn guild_entity() -> GuildEntity {
+        GuildEntity {
+            afk_channel_id: None,
+            afk_timeout: 0,
+            application_id: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
+            banner: None,
+            default_message_notifications: DefaultMessageNotificationLevel::All,
+            description: None,
+            discovery_splash: None,
+            embed_channel_id: None,
+            embed_enabled: None,
+            explicit_content_filter: ExplicitContentFilter::None,
+            features: Vec::new(),
+            icon: None,
+            id: GuildId(1),
+            joined_at: None,
+            large: false,
+            lazy: None,
+            max_members: None,
+            max_presences: None,
+            max_video_channel_users: None,
+            member_count: None,
+            mfa_level: MfaLevel::None,
+            name: "guild".to_owned(),
+            owner_id: UserId(2),
+            owner: None,
+            permissions: None,
+            preferred_locale: "en-US".to_owned(),
+            premium_subscription_count: None,
+            premium_tier: PremiumTier::None,
+            region: "us-east".to_owned(),
+            rules_channel_id: None,
+            splash: None,
+            system_channel_flags: SystemChannelFlags::empty(),
+            system_channel_id: None,
+            unavailable: false,
+            vanity_url_code: None,
+            verification_level: VerificationLevel::None,
+            widget_channel_id: None,
+            widget_enabled: None,
+        }
+    }
+
+    fn member(user_id: u64, role_ids: Vec<RoleId>) -> MemberEntity {
+        MemberEntity {
+            deaf: false,
+            guild_id: GuildId(1),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids,
+            user_id: UserId(user_id),
+        }
+    }
+
+    fn role(id: u64, permissions: Permissions) -> RoleEntity {
+        RoleEntity {
+            color: 0,
+            guild_id: GuildId(1),
+            hoist: false,
+            id: RoleId(id),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions,
+            position: 0,
+        }
+    }
+
+    fn overwrite(
+        kind: PermissionOverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    fn roles() -> Vec<RoleEntity> {
+        vec![
+            role(1, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            role(3, Permissions::EMBED_LINKS | Permissions::ATTACH_FILES),
+            role(4, Permissions::ADMINISTRATOR),
+        ]
+    }
+
+    #[test]
+    fn test_guild_owner() {
+        let member = member(2, Vec::new());
+
+        assert_eq!(
+            Permissions::all(),
+            super::guild(&guild_entity(), &member, &[])
+        );
+    }
+
+    #[test]
+    fn test_guild_roles() {
+        let everyone = super::guild(&guild_entity(), &member(5, Vec::new()), &roles());
+        assert_eq!(
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            everyone
+        );
+
+        let member = member(5, vec![RoleId(3)]);
+        assert_eq!(
+            Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES,
+            super::guild(&guild_entity(), &member, &roles()),
+        );
+    }
+
+    #[test]
+    fn test_guild_administrator() {
+        let member = member(5, vec![RoleId(4)]);
+
+        assert_eq!(
+            Permissions::all(),
+            super::guild(&guild_entity(), &member, &roles())
+        );
+    }
+
+    #[test]
+    fn test_channel_overwrites_order() {
+        let member = member(5, vec![RoleId(3)]);
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Member(UserId(5)),
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(3)),
+                Permissions::ADD_REACTIONS,
+                Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(1)),
+                Permissions::empty(),
+                Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES,
+            ),
+            // overwrites for roles and members other than the member's are
+            // ignored
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(6)),
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            ),
+            overwrite(
+                PermissionOverwriteType::Member(UserId(7)),
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            ),
+        ];
+
+        assert_eq!(
+            Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::ADD_REACTIONS
+                | Permissions::EMBED_LINKS
+                | Permissions::ATTACH_FILES,
+            super::channel(
+                &guild_entity(),
+                &member,
+                &roles(),
+                ChannelType::GuildText,
+                &overwrites,
+            ),
+        );
+    }
+
+    #[test]
+    fn test_channel_implicit_denials() {
+        let member = member(5, vec![RoleId(3)]);
+        let deny_send = [overwrite(
+            PermissionOverwriteType::Role(RoleId(1)),
+            Permissions::empty(),
+            Permissions::SEND_MESSAGES,
+        )];
+        let deny_view = [overwrite(
+            PermissionOverwriteType::Member(UserId(5)),
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            super::channel(
+                &guild_entity(),
+                &member,
+                &roles(),
+                ChannelType::GuildText,
+                &deny_send,
+            ),
+        );
+        // the implicit denial of sending messages only applies to text
+        // channels
+        assert_eq!(
+            Permissions::VIEW_CHANNEL | Permissions::EMBED_LINKS | Permissions::ATTACH_FILES,
+            super::channel(
+                &guild_entity(),
+                &member,
+                &roles(),
+                ChannelType::GuildVoice,
+                &deny_send,
+            ),
+        );
+        assert!(super::channel(
+            &guild_entity(),
+            &member,
+            &roles(),
+            ChannelType::GuildText,
+            &deny_view,
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_role_hierarchy() {
+        let mut low = role(5, Permissions::empty());
+        low.color = 0x00ff_0000;
+        low.position = 1;
+        let mut high = role(6, Permissions::empty());
+        high.position = 2;
+        let mut tied = role(7, Permissions::empty());
+        tied.position = 2;
+
+        assert_eq!(Ordering::Greater, super::cmp_roles(&high, &low));
+        // roles with the same position are ordered by their IDs
+        assert_eq!(Ordering::Greater, super::cmp_roles(&high, &tied));
+        assert_eq!(
+            Some(&high),
+            super::highest_role(&[low.clone(), tied.clone(), high.clone()])
+        );
+        assert!(super::highest_role(&[]).is_none());
+
+        // the highest role doesn't have a colour
+        assert_eq!(
+            Some(0x00ff_0000),
+            super::color(&[low.clone(), high.clone()])
+        );
+        assert!(super::color(&[high.clone()]).is_none());
+
+        assert!(super::is_above(Some(&high), Some(&low)));
+        assert!(super::is_above(Some(&high), Some(&tied)));
+        assert!(!super::is_above(Some(&high), Some(&high)));
+        assert!(!super::is_above(Some(&low), Some(&high)));
+        assert!(super::is_above(Some(&low), None));
+        assert!(!super::is_above(None, Some(&low)));
+        assert!(!super::is_above(None, None));
+    }
+
+    #[test]
+    fn test_channel_administrator_ignores_overwrites() {
+        let member = member(5, vec![RoleId(4)]);
+        let overwrites = [overwrite(
+            PermissionOverwriteType::Role(RoleId(1)),
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+
+        assert_eq!(
+            Permissions::all(),
+            super::channel(
+                &guild_entity(),
+                &member,
+                &roles(),
+                ChannelType::GuildText,
+                &overwrites,
+            ),
+        );
+    }
+}
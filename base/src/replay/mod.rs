@@ -0,0 +1,167 @@
+//! Recording gateway events and replaying them into a cache to reproduce its
+//! state.
+//!
+//! Bugs in how the cache processes events are often only visible after a
+//! specific sequence of events. The [`EventRecorder`] writes the events a bot
+//! receives to a file, and the [`EventReader`] reads them back and replays
+//! them in the same order through [`Cache::update`] into a new cache. The
+//! resulting state can then be captured as a [`Snapshot`] and compared against
+//! an expected snapshot with [`assert_snapshot_eq`].
+//!
+//! Events are recorded as newline-delimited JSON, with one object per line
+//! containing the event's `kind`, such as `"GUILD_CREATE"`, and its `data`.
+//! Null fields are omitted from the data like they are by Discord, since some
+//! models can't be deserialized with them. Only dispatch events are recorded;
+//! gateway events like heartbeats and shard events don't change the state of
+//! the cache.
+//!
+//! This module is only available with the `replay` feature.
+//!
+//! # Examples
+//!
+//! Record events while a bot is running, and replay them later into a new
+//! cache to check its state:
+//!
+//! ```rust,no_run
+//! use rarity_cache::replay::{self, EventReader, EventRecorder, Snapshot};
+//! use rarity_cache_inmemory::InMemoryCache;
+//! use std::{
+//!     fs::File,
+//!     io::{BufReader, BufWriter},
+//! };
+//!
+//! # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let file = File::create("events.ndjson")?;
+//! let mut recorder = EventRecorder::new(BufWriter::new(file));
+//!
+//! // Record each event that the bot receives.
+//! # let event = twilight_model::gateway::event::Event::GatewayHeartbeatAck;
+//! recorder.record(&event)?;
+//! recorder.flush()?;
+//!
+//! // Then replay the events into a new cache.
+//! let cache = InMemoryCache::new();
+//! let reader = EventReader::new(BufReader::new(File::open("events.ndjson")?));
+//! reader.replay(&cache).await?;
+//!
+//! let expected: Snapshot = serde_json::from_reader(File::open("expected.json")?)?;
+//! replay::assert_snapshot_eq(&expected, &Snapshot::capture(&cache).await?);
+//! # Ok(()) }
+//! ```
+//!
+//! [`Cache::update`]: ../struct.Cache.html#method.update
+//! [`EventReader`]: struct.EventReader.html
+//! [`EventRecorder`]: struct.EventRecorder.html
+//! [`Snapshot`]: struct.Snapshot.html
+//! [`assert_snapshot_eq`]: fn.assert_snapshot_eq.html
+
+mod reader;
+mod recorder;
+mod snapshot;
+
+pub use self::{
+    reader::{EventReader, ReadError, ReplayError},
+    recorder::{EventRecorder, RecordError},
+    snapshot::{assert_snapshot_eq, Difference, FieldDifference, Snapshot},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use twilight_model::gateway::event::EventType;
+
+/// Line of a recording as it's written.
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    kind: EventType,
+    data: &'a Value,
+}
+
+/// Line of a recording as it's read.
+///
+/// The kind is read as a string since it determines how the data is
+/// deserialized.
+#[derive(Deserialize)]
+struct Record {
+    kind: String,
+    data: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventReader, EventRecorder, ReadError};
+    use twilight_model::{
+        gateway::{
+            event::Event,
+            payload::{GuildDelete, MemberRemove, TypingStart},
+        },
+        id::{ChannelId, GuildId, UserId},
+        user::User,
+    };
+
+    fn events() -> Vec<Event> {
+        vec![
+            Event::TypingStart(Box::new(TypingStart {
+                channel_id: ChannelId(2),
+                guild_id: Some(GuildId(1)),
+                member: None,
+                timestamp: 1_500_000_000,
+                user_id: UserId(3),
+            })),
+            Event::MemberRemove(MemberRemove {
+                guild_id: GuildId(1),
+                user: User {
+                    avatar: None,
+                    bot: false,
+                    discriminator: "0001".to_owned(),
+                    email: None,
+                    flags: None,
+                    id: UserId(3),
+                    locale: None,
+                    mfa_enabled: None,
+                    name: "test".to_owned(),
+                    premium_type: None,
+                    public_flags: None,
+                    system: None,
+                    verified: None,
+                },
+            }),
+            Event::GuildDelete(Box::new(GuildDelete {
+                id: GuildId(1),
+                unavailable: false,
+            })),
+        ]
+    }
+
+    #[test]
+    fn test_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let mut recorder = EventRecorder::new(Vec::new());
+
+        for event in events() {
+            assert!(recorder.record(&event)?);
+        }
+
+        assert!(!recorder.record(&Event::GatewayHeartbeatAck)?);
+
+        let recording = recorder.into_inner();
+        assert_eq!(3, std::str::from_utf8(&recording)?.lines().count());
+
+        let read = EventReader::new(recording.as_slice()).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(events(), read);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_error_line() {
+        let recording =
+            b"\n{\"kind\":\"GUILD_DELETE\",\"data\":{\"id\":\"1\"}}\n{\"kind\":\"GUILD_DELETE\"}\n";
+        let mut reader = EventReader::new(&recording[..]);
+
+        assert!(matches!(reader.next(), Some(Ok(Event::GuildDelete(_)))));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(ReadError::Deserializing { line: 3, .. })),
+        ));
+        assert!(reader.next().is_none());
+    }
+}
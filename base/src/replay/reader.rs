@@ -0,0 +1,178 @@
+use super::Record;
+use crate::{Backend, Cache};
+use serde::de::DeserializeSeed;
+use serde_json::Error as JsonError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{BufRead, Error as IoError, Lines},
+};
+use twilight_model::gateway::event::{DispatchEventWithTypeDeserializer, Event};
+
+/// Error returned when reading an event from a recording fails.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReadError {
+    /// Deserializing an event failed.
+    Deserializing {
+        /// Line number of the event in the recording, starting at 1.
+        line: usize,
+        /// Reason for the error.
+        source: JsonError,
+    },
+    /// Reading from the recording failed.
+    Io {
+        /// Reason for the error.
+        source: IoError,
+    },
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Deserializing { line, .. } => {
+                write!(f, "failed to deserialize the event on line {line}")
+            }
+            Self::Io { .. } => f.write_str("failed to read from the recording"),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Deserializing { source, .. } => Some(source),
+            Self::Io { source } => Some(source),
+        }
+    }
+}
+
+/// Error returned when replaying a recording into a cache fails.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReplayError<E> {
+    /// Updating the cache with an event failed.
+    Backend {
+        /// Line number of the event in the recording, starting at 1.
+        line: usize,
+        /// Reason for the error.
+        source: E,
+    },
+    /// Reading an event from the recording failed.
+    Read {
+        /// Reason for the error.
+        source: ReadError,
+    },
+}
+
+impl<E: Display> Display for ReplayError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Backend { line, source } => write!(
+                f,
+                "failed to update the cache with the event on line {line}: {source}",
+            ),
+            Self::Read { source } => Display::fmt(source, f),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for ReplayError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Backend { source, .. } => Some(source),
+            Self::Read { source } => Some(source),
+        }
+    }
+}
+
+/// Reader of events from a recording written by an [`EventRecorder`].
+///
+/// Events are read in the order they were recorded, and empty lines are
+/// skipped.
+///
+/// [`EventRecorder`]: struct.EventRecorder.html
+#[derive(Debug)]
+pub struct EventReader<R> {
+    line: usize,
+    lines: Lines<R>,
+}
+
+impl<R: BufRead> EventReader<R> {
+    /// Create a new reader of a recording.
+    pub fn new(reader: R) -> Self {
+        Self {
+            line: 0,
+            lines: reader.lines(),
+        }
+    }
+
+    /// Replay all of the remaining events in the recording into a cache.
+    ///
+    /// Each event is processed by [`Cache::update`] before the next one is
+    /// read. Returns the number of events that were replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReplayError::Read`] if an event couldn't be read, or
+    /// [`ReplayError::Backend`] if updating the cache with an event failed.
+    /// Events before the one that failed will have been replayed.
+    ///
+    /// [`Cache::update`]: ../struct.Cache.html#method.update
+    /// [`ReplayError::Backend`]: enum.ReplayError.html#variant.Backend
+    /// [`ReplayError::Read`]: enum.ReplayError.html#variant.Read
+    pub async fn replay<B: Backend>(
+        mut self,
+        cache: &Cache<B>,
+    ) -> Result<usize, ReplayError<B::Error>> {
+        let mut replayed = 0;
+
+        while let Some(event) = self.next() {
+            let event = event.map_err(|source| ReplayError::Read { source })?;
+
+            cache
+                .update(&event)
+                .await
+                .map_err(|source| ReplayError::Backend {
+                    line: self.line,
+                    source,
+                })?;
+
+            replayed += 1;
+        }
+
+        Ok(replayed)
+    }
+}
+
+impl<R: BufRead> Iterator for EventReader<R> {
+    type Item = Result<Event, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(source) => return Some(Err(ReadError::Io { source })),
+            };
+            self.line += 1;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return Some(
+                deserialize(&line).map_err(|source| ReadError::Deserializing {
+                    line: self.line,
+                    source,
+                }),
+            );
+        }
+    }
+}
+
+fn deserialize(line: &str) -> Result<Event, JsonError> {
+    let record = serde_json::from_str::<Record>(line)?;
+    let event = DispatchEventWithTypeDeserializer::new(&record.kind).deserialize(record.data)?;
+
+    Ok(Event::from(Box::new(event)))
+}
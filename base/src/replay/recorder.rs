@@ -0,0 +1,126 @@
+use super::RecordRef;
+use serde_json::{Error as JsonError, Value};
+use std::{
+    convert::TryFrom,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::{Error as IoError, Write},
+};
+use twilight_model::gateway::event::{DispatchEvent, Event};
+
+/// Error returned when recording an event fails.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RecordError {
+    /// Writing to the recording failed.
+    Io {
+        /// Reason for the error.
+        source: IoError,
+    },
+    /// Serializing an event failed.
+    Serializing {
+        /// Reason for the error.
+        source: JsonError,
+    },
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Io { .. } => f.write_str("failed to write to the recording"),
+            Self::Serializing { .. } => f.write_str("failed to serialize an event"),
+        }
+    }
+}
+
+impl Error for RecordError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { source } => Some(source),
+            Self::Serializing { source } => Some(source),
+        }
+    }
+}
+
+/// Recorder writing events to a writer as newline-delimited JSON.
+///
+/// Writes aren't buffered, so a buffered writer should be used when writing
+/// to a file.
+#[derive(Debug)]
+pub struct EventRecorder<W> {
+    writer: W,
+}
+
+impl<W: Write> EventRecorder<W> {
+    /// Create a new recorder writing to a writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Record an event.
+    ///
+    /// Returns whether the event was recorded. Events that aren't dispatch
+    /// events, such as heartbeats and shard events, aren't recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordError::Serializing`] if the event couldn't be
+    /// serialized, or [`RecordError::Io`] if writing the event failed.
+    ///
+    /// [`RecordError::Io`]: enum.RecordError.html#variant.Io
+    /// [`RecordError::Serializing`]: enum.RecordError.html#variant.Serializing
+    pub fn record(&mut self, event: &Event) -> Result<bool, RecordError> {
+        let kind = event.kind();
+
+        let Ok(data) = DispatchEvent::try_from(event.clone()) else {
+            return Ok(false);
+        };
+
+        let mut data =
+            serde_json::to_value(&data).map_err(|source| RecordError::Serializing { source })?;
+        remove_nulls(&mut data);
+
+        let mut line = serde_json::to_vec(&RecordRef { kind, data: &data })
+            .map_err(|source| RecordError::Serializing { source })?;
+        line.push(b'\n');
+
+        self.writer
+            .write_all(&line)
+            .map_err(|source| RecordError::Io { source })?;
+
+        Ok(true)
+    }
+
+    /// Flush the recorded events to the writer's destination.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecordError::Io`] if flushing the writer failed.
+    ///
+    /// [`RecordError::Io`]: enum.RecordError.html#variant.Io
+    pub fn flush(&mut self) -> Result<(), RecordError> {
+        self.writer
+            .flush()
+            .map_err(|source| RecordError::Io { source })
+    }
+
+    /// Consume the recorder, returning the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Remove the fields of objects that are null.
+///
+/// Optional fields of the models are serialized as null, but some of them
+/// can't be deserialized from null since Discord omits them instead.
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(remove_nulls),
+        Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(remove_nulls);
+        }
+        _ => {}
+    }
+}
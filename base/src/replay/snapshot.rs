@@ -0,0 +1,394 @@
+use crate::{
+    entity::{
+        channel::{
+            AttachmentEntity, CategoryChannelEntity, GroupEntity, MessageEntity,
+            PrivateChannelEntity, TextChannelEntity, VoiceChannelEntity, WebhookEntity,
+        },
+        gateway::PresenceEntity,
+        guild::{BanEntity, EmojiEntity, GuildEntity, MemberEntity, RoleEntity},
+        invite::InviteEntity,
+        user::{CurrentUserEntity, CurrentUserRepository, UserEntity},
+        voice::VoiceStateEntity,
+    },
+    Backend, Cache, Entity, Repository,
+};
+use futures_util::stream::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
+
+/// Difference between an expected and an actual [`Snapshot`].
+///
+/// IDs of entities are formatted with their `Debug` implementations.
+///
+/// [`Snapshot`]: struct.Snapshot.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Difference {
+    /// An entity differs between the snapshots.
+    Changed {
+        /// Type of entity, such as `"guild"`.
+        entity: &'static str,
+        /// ID of the entity.
+        id: String,
+        /// Fields of the entity that differ, ordered by name.
+        fields: Vec<FieldDifference>,
+    },
+    /// An entity in the expected snapshot isn't in the actual snapshot.
+    Missing {
+        /// Type of entity, such as `"guild"`.
+        entity: &'static str,
+        /// ID of the entity.
+        id: String,
+    },
+    /// An entity in the actual snapshot isn't in the expected snapshot.
+    Unexpected {
+        /// Type of entity, such as `"guild"`.
+        entity: &'static str,
+        /// ID of the entity.
+        id: String,
+    },
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Changed { entity, id, fields } => {
+                write!(f, "{entity} {id} changed")?;
+
+                for (idx, field) in fields.iter().enumerate() {
+                    f.write_str(if idx == 0 { ": " } else { "; " })?;
+                    Display::fmt(field, f)?;
+                }
+
+                Ok(())
+            }
+            Self::Missing { entity, id } => write!(f, "{entity} {id} is missing"),
+            Self::Unexpected { entity, id } => write!(f, "{entity} {id} is unexpected"),
+        }
+    }
+}
+
+/// Field of an entity that differs between snapshots.
+///
+/// Fields are compared by their serialized JSON values, which are used for
+/// the expected and actual values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldDifference {
+    /// Name of the field.
+    pub name: String,
+    /// Value of the field in the expected snapshot.
+    pub expected: String,
+    /// Value of the field in the actual snapshot.
+    pub actual: String,
+}
+
+impl Display for FieldDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{} expected {}, got {}",
+            self.name, self.expected, self.actual,
+        )
+    }
+}
+
+/// State of the entities in a cache at a point in time.
+///
+/// The entities of each type are sorted by their IDs, so snapshots of caches
+/// with the same entities are equal regardless of the backend's order.
+/// Typing states aren't included since they expire.
+///
+/// Snapshots can be serialized to store an expected state alongside a
+/// recording.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Snapshot {
+    pub attachments: Vec<AttachmentEntity>,
+    pub bans: Vec<BanEntity>,
+    pub category_channels: Vec<CategoryChannelEntity>,
+    pub current_user: Option<CurrentUserEntity>,
+    pub emojis: Vec<EmojiEntity>,
+    pub groups: Vec<GroupEntity>,
+    pub guilds: Vec<GuildEntity>,
+    pub invites: Vec<InviteEntity>,
+    pub members: Vec<MemberEntity>,
+    pub messages: Vec<MessageEntity>,
+    pub presences: Vec<PresenceEntity>,
+    pub private_channels: Vec<PrivateChannelEntity>,
+    pub roles: Vec<RoleEntity>,
+    pub text_channels: Vec<TextChannelEntity>,
+    pub users: Vec<UserEntity>,
+    pub voice_channels: Vec<VoiceChannelEntity>,
+    pub voice_states: Vec<VoiceStateEntity>,
+    pub webhooks: Vec<WebhookEntity>,
+}
+
+impl Snapshot {
+    /// Capture the entities in a cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns a backend error if a backend repository operation errors.
+    pub async fn capture<B: Backend>(cache: &Cache<B>) -> Result<Self, B::Error> {
+        Ok(Self {
+            attachments: list(&cache.attachments).await?,
+            bans: list(&cache.bans).await?,
+            category_channels: list(&cache.category_channels).await?,
+            current_user: cache.current_user.current().await?,
+            emojis: list(&cache.emojis).await?,
+            groups: list(&cache.groups).await?,
+            guilds: list(&cache.guilds).await?,
+            invites: list(&cache.invites).await?,
+            members: list(&cache.members).await?,
+            messages: list(&cache.messages).await?,
+            presences: list(&cache.presences).await?,
+            private_channels: list(&cache.private_channels).await?,
+            roles: list(&cache.roles).await?,
+            text_channels: list(&cache.text_channels).await?,
+            users: list(&cache.users).await?,
+            voice_channels: list(&cache.voice_channels).await?,
+            voice_states: list(&cache.voice_states).await?,
+            webhooks: list(&cache.webhooks).await?,
+        })
+    }
+
+    /// Return the differences from this expected snapshot to an actual
+    /// snapshot.
+    ///
+    /// Differences are ordered by the type of entity and then by ID.
+    pub fn diff(&self, actual: &Self) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let d = &mut differences;
+
+        diff("attachment", &self.attachments, &actual.attachments, d);
+        diff("ban", &self.bans, &actual.bans, d);
+        diff(
+            "category channel",
+            &self.category_channels,
+            &actual.category_channels,
+            d,
+        );
+        diff(
+            "current user",
+            self.current_user.as_slice(),
+            actual.current_user.as_slice(),
+            d,
+        );
+        diff("emoji", &self.emojis, &actual.emojis, d);
+        diff("group", &self.groups, &actual.groups, d);
+        diff("guild", &self.guilds, &actual.guilds, d);
+        diff("invite", &self.invites, &actual.invites, d);
+        diff("member", &self.members, &actual.members, d);
+        diff("message", &self.messages, &actual.messages, d);
+        diff("presence", &self.presences, &actual.presences, d);
+        diff(
+            "private channel",
+            &self.private_channels,
+            &actual.private_channels,
+            d,
+        );
+        diff("role", &self.roles, &actual.roles, d);
+        diff(
+            "text channel",
+            &self.text_channels,
+            &actual.text_channels,
+            d,
+        );
+        diff("user", &self.users, &actual.users, d);
+        diff(
+            "voice channel",
+            &self.voice_channels,
+            &actual.voice_channels,
+            d,
+        );
+        diff("voice state", &self.voice_states, &actual.voice_states, d);
+        diff("webhook", &self.webhooks, &actual.webhooks, d);
+
+        differences
+    }
+}
+
+/// Assert that an actual snapshot is equal to an expected snapshot.
+///
+/// # Panics
+///
+/// Panics with a list of the [`Difference`]s if the snapshots aren't equal.
+///
+/// [`Difference`]: enum.Difference.html
+pub fn assert_snapshot_eq(expected: &Snapshot, actual: &Snapshot) {
+    let differences = expected.diff(actual);
+
+    if !differences.is_empty() {
+        let lines = differences
+            .iter()
+            .map(|difference| format!("  {difference}"))
+            .collect::<Vec<_>>();
+
+        panic!("snapshots differ:\n{}", lines.join("\n"));
+    }
+}
+
+async fn list<B, E, R>(repo: &R) -> Result<Vec<E>, B::Error>
+where
+    B: Backend,
+    E: Entity,
+    E::Id: Ord,
+    R: Repository<E, B>,
+{
+    let mut entities = repo.list().await?.try_collect::<Vec<_>>().await?;
+    entities.sort_by_key(Entity::id);
+
+    Ok(entities)
+}
+
+fn diff<E>(entity: &'static str, expected: &[E], actual: &[E], differences: &mut Vec<Difference>)
+where
+    E: Entity + PartialEq + Serialize,
+    E::Id: Debug + Ord,
+{
+    let mut pairs = BTreeMap::new();
+
+    for item in expected {
+        pairs.entry(item.id()).or_insert((None, None)).0 = Some(item);
+    }
+
+    for item in actual {
+        pairs.entry(item.id()).or_insert((None, None)).1 = Some(item);
+    }
+
+    for (id, pair) in pairs {
+        let id = format!("{id:?}");
+
+        match pair {
+            (Some(expected), Some(actual)) if expected != actual => {
+                differences.push(Difference::Changed {
+                    entity,
+                    id,
+                    fields: field_differences(expected, actual),
+                });
+            }
+            (Some(_), None) => differences.push(Difference::Missing { entity, id }),
+            (None, Some(_)) => differences.push(Difference::Unexpected { entity, id }),
+            _ => {}
+        }
+    }
+}
+
+fn field_differences<E: Serialize>(expected: &E, actual: &E) -> Vec<FieldDifference> {
+    let (Ok(Value::Object(expected)), Ok(Value::Object(mut actual))) =
+        (serde_json::to_value(expected), serde_json::to_value(actual))
+    else {
+        return Vec::new();
+    };
+
+    let mut fields = Vec::new();
+
+    for (name, expected) in expected {
+        let actual = actual.remove(&name).unwrap_or(Value::Null);
+
+        if expected != actual {
+            fields.push(FieldDifference {
+                name,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+    }
+
+    for (name, actual) in actual {
+        fields.push(FieldDifference {
+            name,
+            expected: Value::Null.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Difference, FieldDifference, Snapshot};
+    use crate::entity::guild::RoleEntity;
+    use twilight_model::{
+        guild::Permissions,
+        id::{GuildId, RoleId},
+    };
+
+    fn role(id: u64, name: &str) -> RoleEntity {
+        RoleEntity {
+            color: 0,
+            guild_id: GuildId(1),
+            hoist: false,
+            id: RoleId(id),
+            managed: false,
+            mentionable: false,
+            name: name.to_owned(),
+            permissions: Permissions::empty(),
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = Snapshot {
+            roles: vec![role(1, "a"), role(2, "b")],
+            ..Snapshot::default()
+        };
+        let actual = Snapshot {
+            roles: vec![role(2, "c"), role(3, "d")],
+            ..Snapshot::default()
+        };
+
+        let differences = expected.diff(&actual);
+        assert_eq!(3, differences.len());
+        assert_eq!(
+            Difference::Missing {
+                entity: "role",
+                id: "RoleId(1)".to_owned(),
+            },
+            differences[0],
+        );
+        assert_eq!(
+            Difference::Changed {
+                entity: "role",
+                id: "RoleId(2)".to_owned(),
+                fields: vec![FieldDifference {
+                    name: "name".to_owned(),
+                    expected: "\"b\"".to_owned(),
+                    actual: "\"c\"".to_owned(),
+                }],
+            },
+            differences[1],
+        );
+        assert_eq!(
+            "role RoleId(2) changed: name expected \"b\", got \"c\"",
+            differences[1].to_string(),
+        );
+        assert_eq!(
+            Difference::Unexpected {
+                entity: "role",
+                id: "RoleId(3)".to_owned(),
+            },
+            differences[2],
+        );
+        assert!(expected.diff(&expected).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "role RoleId(1) is missing")]
+    fn test_assert_snapshot_eq() {
+        let expected = Snapshot {
+            roles: vec![role(1, "a")],
+            ..Snapshot::default()
+        };
+
+        super::assert_snapshot_eq(&expected, &Snapshot::default());
+    }
+}
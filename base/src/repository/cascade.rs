@@ -0,0 +1,180 @@
+//! Cascading removal of entities built on top of the relation methods of
+//! repositories.
+//!
+//! These are the default implementations of the `remove_cascade` methods of
+//! [`GuildRepository`], [`MessageRepository`] and [`TextChannelRepository`].
+//! Backends with a more efficient native way of removing related entities may
+//! override those methods instead.
+//!
+//! [`GuildRepository`]: ../../entity/guild/trait.GuildRepository.html
+//! [`MessageRepository`]: ../../entity/channel/message/trait.MessageRepository.html
+//! [`TextChannelRepository`]: ../../entity/channel/text_channel/trait.TextChannelRepository.html
+
+use super::{RemoveEntitiesFuture, RemoveEntityFuture, Repository};
+use crate::{
+    entity::{
+        channel::{MessageRepository, TextChannelRepository, WebhookRepository},
+        guild::{BanRepository, GuildRepository},
+        invite::InviteRepository,
+    },
+    Backend,
+};
+use futures_util::{future, stream::TryStreamExt};
+use twilight_model::id::{ChannelId, GuildId, MessageId};
+
+/// Remove a guild along with its bans, channels, emojis, invites, members,
+/// presences, roles and voice states.
+///
+/// Text channels are removed with [`text_channel`], so their messages are
+/// removed as well.
+///
+/// [`text_channel`]: fn.text_channel.html
+pub fn guild<'a, B: Backend>(backend: B, guild_id: GuildId) -> RemoveEntityFuture<'a, B::Error> {
+    Box::pin(async move {
+        let bans = backend.bans();
+        let guilds = backend.guilds();
+        let invites = backend.invites();
+
+        let ban_ids = bans
+            .bans(guild_id)
+            .await?
+            .map_ok(|ban| ban.user_id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        let channel_ids = guilds.channel_ids(guild_id).await?;
+        let channel_ids = channel_ids.try_collect::<Vec<_>>().await?;
+        let emoji_ids = guilds.emoji_ids(guild_id).await?;
+        let emoji_ids = emoji_ids.try_collect::<Vec<_>>().await?;
+        let invite_codes = invites
+            .guild_invites(guild_id)
+            .await?
+            .map_ok(|invite| invite.code)
+            .try_collect::<Vec<_>>()
+            .await?;
+        let member_ids = guilds.member_ids(guild_id).await?;
+        let member_ids = member_ids.try_collect::<Vec<_>>().await?;
+        let presence_ids = guilds.presence_ids(guild_id).await?;
+        let presence_ids = presence_ids.try_collect::<Vec<_>>().await?;
+        let role_ids = guilds.role_ids(guild_id).await?;
+        let role_ids = role_ids.try_collect::<Vec<_>>().await?;
+        let voice_state_ids = guilds.voice_state_ids(guild_id).await?;
+        let voice_state_ids = voice_state_ids.try_collect::<Vec<_>>().await?;
+
+        let category_channels = backend.category_channels();
+        let emojis = backend.emojis();
+        let members = backend.members();
+        let presences = backend.presences();
+        let roles = backend.roles();
+        let text_channels = backend.text_channels();
+        let voice_channels = backend.voice_channels();
+        let voice_states = backend.voice_states();
+
+        future::try_join_all(
+            channel_ids
+                .iter()
+                .map(|channel_id| text_channels.remove_cascade(*channel_id)),
+        )
+        .await?;
+
+        future::try_join_all(vec![
+            bans.remove_bulk(ban_ids.into_iter().map(|id| (guild_id, id))),
+            category_channels.remove_bulk(channel_ids.iter().copied()),
+            voice_channels.remove_bulk(channel_ids.into_iter()),
+            emojis.remove_bulk(emoji_ids.into_iter()),
+            invites.remove_bulk(invite_codes.into_iter()),
+            members.remove_bulk(member_ids.into_iter().map(|id| (guild_id, id))),
+            presences.remove_bulk(presence_ids.into_iter().map(|id| (guild_id, id))),
+            roles.remove_bulk(role_ids.into_iter()),
+            voice_states.remove_bulk(voice_state_ids.into_iter().map(|id| (guild_id, id))),
+        ])
+        .await?;
+
+        guilds.remove(guild_id).await
+    })
+}
+
+/// Remove a message along with its attachments.
+pub fn message<'a, B: Backend>(
+    backend: B,
+    message_id: MessageId,
+) -> RemoveEntityFuture<'a, B::Error> {
+    Box::pin(async move {
+        let messages = backend.messages();
+
+        if let Some(message) = messages.get(message_id).await? {
+            let attachments = backend.attachments();
+
+            attachments
+                .remove_bulk(message.attachments.into_iter())
+                .await?;
+        }
+
+        messages.remove(message_id).await
+    })
+}
+
+/// Bulk remove messages along with their attachments.
+///
+/// The messages are retrieved concurrently, and then their attachments and
+/// the messages themselves are removed with a bulk removal each.
+pub fn messages<'a, B: Backend>(
+    backend: B,
+    message_ids: Vec<MessageId>,
+) -> RemoveEntitiesFuture<'a, B::Error> {
+    Box::pin(async move {
+        let messages = backend.messages();
+
+        let attachment_ids = future::try_join_all(message_ids.iter().map(|id| messages.get(*id)))
+            .await?
+            .into_iter()
+            .flatten()
+            .flat_map(|message| message.attachments)
+            .collect::<Vec<_>>();
+
+        let attachments = backend.attachments();
+
+        attachments.remove_bulk(attachment_ids.into_iter()).await?;
+
+        messages.remove_bulk(message_ids.into_iter()).await
+    })
+}
+
+/// Remove a text channel along with its messages and webhooks.
+///
+/// Messages are removed with [`messages`], so their attachments are removed
+/// as well.
+///
+/// [`messages`]: fn.messages.html
+pub fn text_channel<'a, B: Backend>(
+    backend: B,
+    channel_id: ChannelId,
+) -> RemoveEntityFuture<'a, B::Error> {
+    Box::pin(async move {
+        let text_channels = backend.text_channels();
+
+        let message_ids = text_channels.message_ids(channel_id).await?;
+        let message_ids = message_ids.try_collect::<Vec<_>>().await?;
+
+        let messages = backend.messages();
+        let webhooks = backend.webhooks();
+
+        let webhook_ids = webhooks
+            .channel_webhooks(channel_id)
+            .await?
+            .map_ok(|webhook| webhook.id)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        messages
+            .remove_cascade_bulk(message_ids.into_iter())
+            .await?;
+
+        future::try_join(
+            webhooks.remove_bulk(webhook_ids.into_iter()),
+            webhooks.set_stale(channel_id, false),
+        )
+        .await?;
+
+        text_channels.remove(channel_id).await
+    })
+}
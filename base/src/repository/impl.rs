@@ -1,7 +1,10 @@
 use super::{
-    super::{backend::Backend, entity::Entity},
+    super::{
+        backend::Backend,
+        entity::{Entity, EntityField},
+    },
     GetEntityFuture, ListEntitiesFuture, RemoveEntitiesFuture, RemoveEntityFuture,
-    UpsertEntitiesFuture, UpsertEntityFuture,
+    UpdateEntityFuture, UpsertEntitiesFuture, UpsertEntityFuture,
 };
 use futures_util::future::{self, FutureExt, TryFutureExt};
 
@@ -36,6 +39,42 @@ pub trait Repository<E: Entity, B: Backend> {
             .boxed()
     }
 
+    /// Update a single field of an entity in the cache.
+    ///
+    /// Returns whether the entity was in the cache. Entities that aren't in
+    /// the cache are left as-is, since a field alone isn't enough to create
+    /// one.
+    ///
+    /// **Backend implementations**: a default implementation is provided that
+    /// will [`get`] the entity, apply the update, and then [`upsert`] it. This
+    /// can race with other updates of the entity and takes multiple round
+    /// trips to remote backends, so you may want to implement this natively.
+    ///
+    /// [`get`]: #tymethod.get
+    /// [`upsert`]: #tymethod.upsert
+    fn update_field<F: EntityField<E>>(
+        &self,
+        entity_id: E::Id,
+        field: F,
+    ) -> UpdateEntityFuture<'_, B::Error>
+    where
+        E: 'static,
+        Self: Sync,
+    {
+        let entity = self.get(entity_id);
+
+        Box::pin(async move {
+            let Some(mut entity) = entity.await? else {
+                return Ok(false);
+            };
+
+            field.apply(&mut entity);
+            self.upsert(entity).await?;
+
+            Ok(true)
+        })
+    }
+
     /// Upsert an entity into the cache.
     fn upsert(&self, entity: E) -> UpsertEntityFuture<'_, B::Error>;
 
@@ -1,3 +1,5 @@
+pub mod cascade;
+
 mod r#impl;
 mod noop;
 
@@ -16,5 +18,6 @@ pub type ListEntityIdsFuture<'a, T, E> =
 pub type ListEntityIdsStream<'a, T, E> = Pin<Box<dyn Stream<Item = Result<T, E>> + Send + 'a>>;
 pub type RemoveEntityFuture<'a, E> = Pin<Box<dyn Future<Output = Result<(), E>> + Send + 'a>>;
 pub type RemoveEntitiesFuture<'a, E> = Pin<Box<dyn Future<Output = Result<(), E>> + Send + 'a>>;
+pub type UpdateEntityFuture<'a, E> = Pin<Box<dyn Future<Output = Result<bool, E>> + Send + 'a>>;
 pub type UpsertEntityFuture<'a, E> = Pin<Box<dyn Future<Output = Result<(), E>> + Send + 'a>>;
 pub type UpsertEntitiesFuture<'a, E> = Pin<Box<dyn Future<Output = Result<(), E>> + Send + 'a>>;
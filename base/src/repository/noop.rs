@@ -10,16 +10,22 @@ use super::{
                 private_channel::PrivateChannelRepository,
                 text_channel::{TextChannelEntity, TextChannelRepository},
                 voice_channel::{VoiceChannelEntity, VoiceChannelRepository},
-                ChannelEntity,
+                webhook::{WebhookEntity, WebhookRepository},
+                ChannelEntity, GuildChannelEntity,
+            },
+            gateway::{
+                presence::{PresenceEntity, PresenceRepository},
+                typing::{TypingEntity, TypingRepository},
             },
-            gateway::presence::{PresenceEntity, PresenceRepository},
             guild::{
+                ban::{BanEntity, BanRepository},
                 emoji::{EmojiEntity, EmojiRepository},
                 member::{MemberEntity, MemberRepository},
                 role::{RoleEntity, RoleRepository},
                 GuildEntity, GuildRepository,
             },
-            user::{UserEntity, UserRepository},
+            invite::{InviteEntity, InviteRepository},
+            user::{CurrentUserEntity, CurrentUserRepository, UserEntity, UserRepository},
             voice::{VoiceStateEntity, VoiceStateRepository},
             Entity,
         },
@@ -31,7 +37,9 @@ use futures_util::{
     future::{self, FutureExt},
     stream::{self, StreamExt},
 };
-use twilight_model::id::{AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId};
+use twilight_model::id::{
+    AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId, WebhookId,
+};
 
 /// Repository that implements no operations: when called it will do nothing.
 ///
@@ -103,12 +111,40 @@ impl<B: Backend + Clone + Send> AttachmentRepository<B> for NoopRepository<B> {
     }
 }
 
+impl<B: Backend + Clone + Send> BanRepository<B> for NoopRepository<B> {
+    fn bans(&self, _: GuildId) -> ListEntitiesFuture<'_, BanEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn is_banned(&self, _: GuildId, _: UserId) -> GetEntityFuture<'_, bool, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn user(&self, _: GuildId, _: UserId) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+}
+
 impl<B: Backend + Clone + Send> CategoryChannelRepository<B> for NoopRepository<B> {
     fn guild(&self, _: ChannelId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
         future::ok(None).boxed()
     }
 }
 
+impl<B: Backend + Clone + Send> CurrentUserRepository<B> for NoopRepository<B> {
+    fn current(&self) -> GetEntityFuture<'_, CurrentUserEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn guild_ids(&self) -> super::ListEntityIdsFuture<'_, GuildId, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+}
+
 impl<B: Backend + Clone + Send> EmojiRepository<B> for NoopRepository<B> {
     fn guild(&self, _: EmojiId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
         future::ok(None).boxed()
@@ -213,6 +249,28 @@ impl<B: Backend + Clone + Send> GuildRepository<B> for NoopRepository<B> {
     }
 }
 
+impl<B: Backend + Clone + Send> InviteRepository<B> for NoopRepository<B> {
+    fn channel(&self, _: String) -> GetEntityFuture<'_, GuildChannelEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn channel_invites(&self, _: ChannelId) -> ListEntitiesFuture<'_, InviteEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn guild(&self, _: String) -> GetEntityFuture<'_, GuildEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn guild_invites(&self, _: GuildId) -> ListEntitiesFuture<'_, InviteEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn inviter(&self, _: String) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+}
+
 impl<B: Backend + Clone + Send> MemberRepository<B> for NoopRepository<B> {
     fn hoisted_role(&self, _: GuildId, _: UserId) -> GetEntityFuture<'_, RoleEntity, B::Error> {
         future::ok(None).boxed()
@@ -266,6 +324,10 @@ impl<B: Backend + Clone + Send> MessageRepository<B> for NoopRepository<B> {
     ) -> ListEntitiesFuture<'_, crate::entity::user::UserEntity, B::Error> {
         future::ok(stream::empty().boxed()).boxed()
     }
+
+    fn webhook(&self, _: MessageId) -> GetEntityFuture<'_, WebhookEntity, B::Error> {
+        future::ok(None).boxed()
+    }
 }
 
 impl<B: Backend + Clone + Send> PresenceRepository<B> for NoopRepository<B> {}
@@ -295,11 +357,29 @@ impl<B: Backend + Clone + Send> TextChannelRepository<B> for NoopRepository<B> {
         future::ok(None).boxed()
     }
 
+    fn message_ids(&self, _: ChannelId) -> super::ListEntityIdsFuture<'_, MessageId, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn messages(&self, _: ChannelId) -> ListEntitiesFuture<'_, MessageEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
     fn parent(&self, _: ChannelId) -> GetEntityFuture<'_, CategoryChannelEntity, B::Error> {
         future::ok(None).boxed()
     }
 }
 
+impl<B: Backend + Clone + Send> TypingRepository<B> for NoopRepository<B> {
+    fn channel_typing(&self, _: ChannelId) -> ListEntitiesFuture<'_, TypingEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn user(&self, _: (ChannelId, UserId)) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+}
+
 impl<B: Backend + Clone + Send> UserRepository<B> for NoopRepository<B> {
     fn guild_ids(&self, _: UserId) -> super::ListEntityIdsFuture<'_, GuildId, B::Error> {
         future::ok(stream::empty().boxed()).boxed()
@@ -325,3 +405,41 @@ impl<B: Backend + Clone + Send> VoiceStateRepository<B> for NoopRepository<B> {
         future::ok(None).boxed()
     }
 }
+
+impl<B: Backend + Clone + Send> WebhookRepository<B> for NoopRepository<B> {
+    fn channel(&self, _: WebhookId) -> GetEntityFuture<'_, TextChannelEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn channel_webhooks(&self, _: ChannelId) -> ListEntitiesFuture<'_, WebhookEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn guild(&self, _: WebhookId) -> GetEntityFuture<'_, GuildEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn guild_webhooks(&self, _: GuildId) -> ListEntitiesFuture<'_, WebhookEntity, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn is_stale(&self, _: ChannelId) -> GetEntityFuture<'_, bool, B::Error> {
+        future::ok(None).boxed()
+    }
+
+    fn refresh(&self, _: ChannelId, _: Vec<WebhookEntity>) -> UpsertEntitiesFuture<'_, B::Error> {
+        future::ok(()).boxed()
+    }
+
+    fn set_stale(&self, _: ChannelId, _: bool) -> UpsertEntityFuture<'_, B::Error> {
+        future::ok(()).boxed()
+    }
+
+    fn stale_channel_ids(&self) -> super::ListEntityIdsFuture<'_, ChannelId, B::Error> {
+        future::ok(stream::empty().boxed()).boxed()
+    }
+
+    fn user(&self, _: WebhookId) -> GetEntityFuture<'_, UserEntity, B::Error> {
+        future::ok(None).boxed()
+    }
+}
@@ -4,7 +4,11 @@ use super::{
     repository::{GetEntityFuture, ListEntitiesFuture, ListEntityIdsStream, Repository},
 };
 use futures_util::stream::{self, StreamExt};
-use std::future::Future;
+use std::{
+    convert::TryFrom,
+    future::Future,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub fn relation_and_then<
     'a,
@@ -159,3 +163,91 @@ pub fn stream_ids<
         .boxed())
     })
 }
+
+/// Return the current Unix timestamp in seconds.
+///
+/// This is the time that expiring entities, such as typing states and
+/// invites, are checked against. Backends can use it to expire entities the
+/// same way.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Parse an ISO 8601 timestamp such as `2020-05-30T20:52:43.457000+00:00`, as
+/// sent by Discord, into a Unix timestamp in seconds.
+///
+/// Fractional seconds are ignored. Returns `None` if the timestamp is
+/// malformed or is before the Unix epoch.
+pub fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    fn number(s: &str, range: std::ops::Range<usize>) -> Option<i64> {
+        let digits = s.get(range)?;
+
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        digits.parse().ok()
+    }
+
+    let bytes = timestamp.as_bytes();
+
+    if bytes.len() < 19
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return None;
+    }
+
+    let year = number(timestamp, 0..4)?;
+    let month = number(timestamp, 5..7)?;
+    let day = number(timestamp, 8..10)?;
+    let hour = number(timestamp, 11..13)?;
+    let minute = number(timestamp, 14..16)?;
+    let second = number(timestamp, 17..19)?;
+
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+
+    let mut rest = &timestamp[19..];
+
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        rest = &fraction[digits..];
+    }
+
+    let offset = match rest.as_bytes().first() {
+        None | Some(b'Z') if rest.len() <= 1 => 0,
+        Some(sign @ (b'+' | b'-')) if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let minutes = number(rest, 1..3)? * 60 + number(rest, 4..6)?;
+
+            if *sign == b'+' {
+                minutes * 60
+            } else {
+                -minutes * 60
+            }
+        }
+        _ => return None,
+    };
+
+    // Days since the epoch of a civil date, from Howard Hinnant's algorithm.
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+
+    u64::try_from(seconds).ok()
+}
@@ -0,0 +1,156 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::guild::GuildEntity, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{BanAdd, BanRemove, GuildDelete},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, PremiumTier,
+        SystemChannelFlags, VerificationLevel,
+    },
+    id::{GuildId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn guild(id: u64) -> GuildEntity {
+    GuildEntity {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(id),
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: None,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(2),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        region: "us-east".to_owned(),
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn ban_add(guild_id: u64, user_id: u64) -> Event {
+    Event::BanAdd(BanAdd {
+        guild_id: GuildId(guild_id),
+        user: user(user_id),
+    })
+}
+
+#[tokio::test]
+async fn test_ban_add_remove() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    assert!(cache.bans.is_banned(GuildId(1), UserId(2)).await?.is_none());
+
+    cache.guilds.upsert(guild(1)).await?;
+    cache.update(&ban_add(1, 2)).await?;
+    cache.update(&ban_add(1, 3)).await?;
+    cache.update(&ban_add(4, 2)).await?;
+
+    assert_eq!(
+        Some(true),
+        cache.bans.is_banned(GuildId(1), UserId(2)).await?
+    );
+    assert_eq!(
+        Some(false),
+        cache.bans.is_banned(GuildId(1), UserId(5)).await?
+    );
+    let banned = cache.bans.user(GuildId(1), UserId(2)).await?;
+    assert_eq!(Some(UserId(2)), banned.map(|user| user.id));
+
+    let mut user_ids = cache
+        .bans
+        .bans(GuildId(1))
+        .await?
+        .map_ok(|ban| ban.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    user_ids.sort();
+    assert_eq!(vec![UserId(2), UserId(3)], user_ids);
+
+    cache
+        .update(&Event::BanRemove(BanRemove {
+            guild_id: GuildId(1),
+            user: user(2),
+        }))
+        .await?;
+    assert_eq!(
+        Some(false),
+        cache.bans.is_banned(GuildId(1), UserId(2)).await?
+    );
+    assert!(cache.bans.get((GuildId(4), UserId(2))).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_delete_removes_bans() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache.guilds.upsert(guild(1)).await?;
+    cache.update(&ban_add(1, 2)).await?;
+
+    cache
+        .update(&Event::GuildDelete(Box::new(GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        })))
+        .await?;
+
+    assert!(cache.bans.get((GuildId(1), UserId(2))).await?.is_none());
+    assert!(cache
+        .bans
+        .bans(GuildId(1))
+        .await?
+        .try_next()
+        .await?
+        .is_none());
+
+    Ok(())
+}
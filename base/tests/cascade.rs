@@ -0,0 +1,140 @@
+use rarity_cache::{
+    entity::{
+        channel::{AttachmentEntity, MessageEntity, TextChannelEntity},
+        guild::{MemberEntity, RoleEntity},
+    },
+    repository::cascade,
+    Backend, Repository,
+};
+use rarity_cache_inmemory::InMemoryBackend;
+use twilight_model::{
+    channel::{message::MessageType, ChannelType},
+    guild::Permissions,
+    id::{AttachmentId, ChannelId, GuildId, MessageId, RoleId, UserId},
+};
+
+fn text_channel() -> TextChannelEntity {
+    TextChannelEntity {
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(2),
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "general".to_owned(),
+        nsfw: false,
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    }
+}
+
+fn message() -> MessageEntity {
+    MessageEntity {
+        application_id: None,
+        attachments: Vec::new(),
+        author_id: UserId(3),
+        channel_id: ChannelId(2),
+        content: "ping".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: Some(GuildId(1)),
+        id: MessageId(4),
+        kind: MessageType::Regular,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: Vec::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reaction_users: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+#[tokio::test]
+async fn test_cascade_guild() -> Result<(), Box<dyn std::error::Error>> {
+    let backend = InMemoryBackend::new();
+
+    backend.text_channels().upsert(text_channel()).await?;
+    backend.messages().upsert(message()).await?;
+    backend
+        .members()
+        .upsert(MemberEntity {
+            deaf: false,
+            guild_id: GuildId(1),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids: vec![RoleId(5)],
+            user_id: UserId(3),
+        })
+        .await?;
+    backend
+        .roles()
+        .upsert(RoleEntity {
+            color: 0,
+            guild_id: GuildId(1),
+            hoist: false,
+            id: RoleId(5),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions: Permissions::empty(),
+            position: 0,
+        })
+        .await?;
+
+    cascade::guild(backend.clone(), GuildId(1)).await?;
+
+    assert!(backend.text_channels().get(ChannelId(2)).await?.is_none());
+    assert!(backend.messages().get(MessageId(4)).await?.is_none());
+    assert!(backend
+        .members()
+        .get((GuildId(1), UserId(3)))
+        .await?
+        .is_none());
+    assert!(backend.roles().get(RoleId(5)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cascade_messages() -> Result<(), Box<dyn std::error::Error>> {
+    let backend = InMemoryBackend::new();
+
+    let attachment = AttachmentEntity {
+        filename: "a.png".to_owned(),
+        height: None,
+        id: AttachmentId(5),
+        message_id: MessageId(4),
+        proxy_url: "https://example.com/a.png".to_owned(),
+        size: 1,
+        url: "https://example.com/a.png".to_owned(),
+        width: None,
+    };
+    let mut first = message();
+    first.attachments.push(attachment.id);
+    let mut second = message();
+    second.id = MessageId(6);
+
+    backend.attachments().upsert(attachment).await?;
+    backend
+        .messages()
+        .upsert_bulk(vec![first, second].into_iter())
+        .await?;
+
+    cascade::messages(backend.clone(), vec![MessageId(4), MessageId(6)]).await?;
+
+    assert!(backend.messages().get(MessageId(4)).await?.is_none());
+    assert!(backend.messages().get(MessageId(6)).await?.is_none());
+    assert!(backend.attachments().get(AttachmentId(5)).await?.is_none());
+
+    Ok(())
+}
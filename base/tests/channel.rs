@@ -0,0 +1,184 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{
+    entity::{channel::MessageEntity, guild::GuildRepository},
+    Repository,
+};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    channel::{
+        message::MessageType, CategoryChannel, Channel, ChannelType, GuildChannel, PrivateChannel,
+        TextChannel,
+    },
+    gateway::{
+        event::Event,
+        payload::{ChannelCreate, ChannelPinsUpdate, ChannelUpdate},
+    },
+    id::{ChannelId, GuildId, MessageId, UserId},
+};
+
+fn text_channel() -> TextChannel {
+    TextChannel {
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(2),
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "general".to_owned(),
+        nsfw: false,
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    }
+}
+
+fn private_channel() -> PrivateChannel {
+    PrivateChannel {
+        id: ChannelId(3),
+        last_message_id: None,
+        last_pin_timestamp: None,
+        kind: ChannelType::Private,
+        recipients: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_channel_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(Channel::Guild(
+            GuildChannel::Text(text_channel()),
+        ))))
+        .await?;
+
+    let mut channel = text_channel();
+    channel.name = "renamed".to_owned();
+    channel.topic.replace("a topic".to_owned());
+    cache
+        .update(&Event::ChannelUpdate(ChannelUpdate(Channel::Guild(
+            GuildChannel::Text(channel),
+        ))))
+        .await?;
+
+    let channel = cache
+        .text_channels
+        .get(ChannelId(2))
+        .await?
+        .expect("not cached");
+    assert_eq!("renamed", channel.name);
+    assert_eq!(Some("a topic"), channel.topic.as_deref());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channel_update_kind_change() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(Channel::Guild(
+            GuildChannel::Text(text_channel()),
+        ))))
+        .await?;
+    cache
+        .messages
+        .upsert(MessageEntity {
+            application_id: None,
+            attachments: Vec::new(),
+            author_id: UserId(3),
+            channel_id: ChannelId(2),
+            content: "ping".to_owned(),
+            edited_timestamp: None,
+            embeds: Vec::new(),
+            flags: None,
+            guild_id: Some(GuildId(1)),
+            id: MessageId(4),
+            kind: MessageType::Regular,
+            mention_channels: Vec::new(),
+            mention_everyone: false,
+            mention_roles: Vec::new(),
+            mentions: Vec::new(),
+            pinned: false,
+            reactions: Vec::new(),
+            reaction_users: Vec::new(),
+            timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+            tts: false,
+            webhook_id: None,
+        })
+        .await?;
+
+    let category = CategoryChannel {
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(2),
+        kind: ChannelType::GuildCategory,
+        name: "category".to_owned(),
+        permission_overwrites: Vec::new(),
+        position: 0,
+    };
+    cache
+        .update(&Event::ChannelUpdate(ChannelUpdate(Channel::Guild(
+            GuildChannel::Category(category),
+        ))))
+        .await?;
+
+    assert!(cache.text_channels.get(ChannelId(2)).await?.is_none());
+    assert!(cache.category_channels.get(ChannelId(2)).await?.is_some());
+    assert!(cache.messages.get(MessageId(4)).await?.is_none());
+
+    let channel_ids = cache
+        .guilds
+        .channel_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(channel_ids, vec![ChannelId(2)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channel_pins_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(Channel::Guild(
+            GuildChannel::Text(text_channel()),
+        ))))
+        .await?;
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(Channel::Private(
+            private_channel(),
+        ))))
+        .await?;
+
+    let timestamp = "2020-01-01T00:00:00+00:00";
+
+    for (channel_id, guild_id) in &[(2, Some(GuildId(1))), (3, None), (4, None)] {
+        cache
+            .update(&Event::ChannelPinsUpdate(ChannelPinsUpdate {
+                channel_id: ChannelId(*channel_id),
+                guild_id: *guild_id,
+                last_pin_timestamp: Some(timestamp.to_owned()),
+            }))
+            .await?;
+    }
+
+    let channel = cache
+        .text_channels
+        .get(ChannelId(2))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some(timestamp), channel.last_pin_timestamp.as_deref());
+    assert_eq!("general", channel.name);
+
+    let channel = cache
+        .private_channels
+        .get(ChannelId(3))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some(timestamp), channel.last_pin_timestamp.as_deref());
+
+    // updates for channels that aren't cached are ignored
+    assert!(cache.groups.get(ChannelId(4)).await?.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::guild::GuildEntity, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{Ready, UserUpdate},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, PremiumTier,
+        SystemChannelFlags, VerificationLevel,
+    },
+    id::{GuildId, UserId},
+    user::CurrentUser,
+};
+
+fn current_user(name: &str) -> CurrentUser {
+    CurrentUser {
+        avatar: None,
+        bot: true,
+        discriminator: "0001".to_owned(),
+        email: None,
+        id: UserId(1),
+        mfa_enabled: false,
+        name: name.to_owned(),
+        verified: true,
+    }
+}
+
+fn guild(id: u64) -> GuildEntity {
+    GuildEntity {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(id),
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: None,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(2),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        region: "us-east".to_owned(),
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn ready() -> Event {
+    Event::Ready(Box::new(Ready {
+        guilds: HashMap::new(),
+        session_id: "session".to_owned(),
+        shard: Some([0, 1]),
+        user: current_user("bot"),
+        version: 6,
+    }))
+}
+
+#[tokio::test]
+async fn test_ready_user_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    assert!(cache.current_user.current().await?.is_none());
+
+    cache.update(&ready()).await?;
+    let user = cache.current_user.current().await?.expect("not cached");
+    assert_eq!("bot", user.name);
+
+    cache
+        .update(&Event::UserUpdate(UserUpdate(current_user("renamed"))))
+        .await?;
+    let user = cache
+        .current_user
+        .get(UserId(1))
+        .await?
+        .expect("not cached");
+    assert_eq!("renamed", user.name);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_current_user_guilds() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache.update(&ready()).await?;
+    cache
+        .guilds
+        .upsert_bulk(vec![guild(3), guild(4)].into_iter())
+        .await?;
+
+    let mut guild_ids = cache
+        .current_user
+        .guilds()
+        .await?
+        .map_ok(|guild| guild.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    guild_ids.sort();
+    assert_eq!(vec![GuildId(3), GuildId(4)], guild_ids);
+
+    cache.guilds.remove(GuildId(3)).await?;
+    let guild_ids = cache
+        .current_user
+        .guild_ids()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![GuildId(4)], guild_ids);
+
+    Ok(())
+}
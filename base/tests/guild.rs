@@ -0,0 +1,442 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::guild::GuildRepository, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{ChannelType, GuildChannel, TextChannel},
+    gateway::{
+        event::Event,
+        payload::{GuildCreate, GuildDelete, GuildUpdate, Ready, UnavailableGuild},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, Emoji, ExplicitContentFilter, Guild, GuildStatus, Member,
+        MfaLevel, PartialGuild, Permissions, PremiumTier, Role, SystemChannelFlags,
+        VerificationLevel,
+    },
+    id::{ChannelId, EmojiId, GuildId, RoleId, UserId},
+    user::{CurrentUser, User},
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn guild() -> Guild {
+    let mut channels = HashMap::new();
+    channels.insert(
+        ChannelId(2),
+        GuildChannel::Text(TextChannel {
+            guild_id: None,
+            id: ChannelId(2),
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "general".to_owned(),
+            nsfw: false,
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        }),
+    );
+
+    let mut emojis = HashMap::new();
+    emojis.insert(
+        EmojiId(3),
+        Emoji {
+            animated: false,
+            available: true,
+            id: EmojiId(3),
+            managed: false,
+            name: "emoji".to_owned(),
+            require_colons: true,
+            roles: Vec::new(),
+            user: Some(user(5)),
+        },
+    );
+
+    let mut members = HashMap::new();
+    members.insert(
+        UserId(4),
+        Member {
+            deaf: false,
+            guild_id: GuildId(1),
+            hoisted_role: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            roles: vec![RoleId(1)],
+            user: user(4),
+        },
+    );
+
+    let mut roles = HashMap::new();
+    roles.insert(
+        RoleId(1),
+        Role {
+            color: 0,
+            hoist: false,
+            id: RoleId(1),
+            managed: false,
+            mentionable: false,
+            name: "@everyone".to_owned(),
+            permissions: Permissions::SEND_MESSAGES,
+            position: 0,
+        },
+    );
+
+    Guild {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        channels,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        emojis,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(1),
+        joined_at: Some("2020-01-01T00:00:00+00:00".to_owned()),
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: Some(1),
+        members,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(4),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        presences: HashMap::new(),
+        region: "us-east".to_owned(),
+        roles,
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        voice_states: HashMap::new(),
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn ready(guilds: Vec<GuildStatus>) -> Event {
+    Event::Ready(Box::new(Ready {
+        guilds: guilds
+            .into_iter()
+            .map(|status| match &status {
+                GuildStatus::Online(guild) => (guild.id, status),
+                GuildStatus::Offline(guild) => (guild.id, status),
+            })
+            .collect(),
+        session_id: "session".to_owned(),
+        shard: Some([0, 1]),
+        user: CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            id: UserId(6),
+            mfa_enabled: false,
+            name: "bot".to_owned(),
+            verified: true,
+        },
+        version: 6,
+    }))
+}
+
+fn offline(id: u64) -> GuildStatus {
+    GuildStatus::Offline(twilight_model::guild::UnavailableGuild {
+        id: GuildId(id),
+        unavailable: true,
+    })
+}
+
+#[tokio::test]
+async fn test_guild_create() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    assert!(cache.guilds.get(GuildId(1)).await?.is_some());
+    assert!(cache.roles.get(RoleId(1)).await?.is_some());
+    assert!(cache.members.get((GuildId(1), UserId(4))).await?.is_some());
+    assert!(cache.users.get(UserId(4)).await?.is_some());
+    assert!(cache.users.get(UserId(5)).await?.is_some());
+
+    let emoji = cache.emojis.get(EmojiId(3)).await?.expect("not cached");
+    assert_eq!(GuildId(1), emoji.guild_id);
+
+    let channel = cache
+        .text_channels
+        .get(ChannelId(2))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some(GuildId(1)), channel.guild_id);
+
+    Ok(())
+}
+
+fn partial_guild(guild: Guild) -> PartialGuild {
+    PartialGuild {
+        id: guild.id,
+        afk_channel_id: guild.afk_channel_id,
+        afk_timeout: guild.afk_timeout,
+        application_id: guild.application_id,
+        banner: guild.banner,
+        default_message_notifications: guild.default_message_notifications,
+        description: guild.description,
+        discovery_splash: guild.discovery_splash,
+        embed_channel_id: guild.embed_channel_id,
+        embed_enabled: false,
+        emojis: guild.emojis,
+        explicit_content_filter: guild.explicit_content_filter,
+        features: guild.features,
+        icon: guild.icon,
+        max_members: guild.max_members,
+        max_presences: guild.max_presences,
+        member_count: guild.member_count,
+        mfa_level: guild.mfa_level,
+        name: guild.name,
+        owner_id: guild.owner_id,
+        owner: guild.owner,
+        permissions: guild.permissions,
+        preferred_locale: guild.preferred_locale,
+        premium_subscription_count: guild.premium_subscription_count,
+        premium_tier: guild.premium_tier,
+        region: guild.region,
+        roles: guild.roles,
+        rules_channel_id: guild.rules_channel_id,
+        splash: guild.splash,
+        system_channel_flags: guild.system_channel_flags,
+        system_channel_id: guild.system_channel_id,
+        verification_level: guild.verification_level,
+        vanity_url_code: guild.vanity_url_code,
+        widget_channel_id: guild.widget_channel_id,
+        widget_enabled: guild.widget_enabled,
+    }
+}
+
+#[tokio::test]
+async fn test_guild_update_preserves_fields() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    let guild = guild();
+    let joined_at = guild.joined_at.clone();
+    let mut partial = partial_guild(guild);
+    partial.member_count = None;
+    partial.name = "renamed".to_owned();
+    cache
+        .update(&Event::GuildUpdate(Box::new(GuildUpdate(partial))))
+        .await?;
+
+    let entity = cache.guilds.get(GuildId(1)).await?.expect("not cached");
+    assert_eq!("renamed", entity.name);
+    assert_eq!(joined_at, entity.joined_at);
+    assert_eq!(Some(1), entity.member_count);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_update_removes_missing_roles_and_emojis(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    let mut partial = partial_guild(guild());
+    partial.emojis.clear();
+    partial.roles.clear();
+    cache
+        .update(&Event::GuildUpdate(Box::new(GuildUpdate(partial))))
+        .await?;
+
+    assert!(cache.emojis.get(EmojiId(3)).await?.is_none());
+    assert!(cache.roles.get(RoleId(1)).await?.is_none());
+    assert_eq!(
+        0,
+        cache
+            .guilds
+            .emoji_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .len()
+    );
+    assert_eq!(
+        0,
+        cache
+            .guilds
+            .role_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .len()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_unavailable() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    cache
+        .update(&Event::GuildDelete(Box::new(GuildDelete {
+            id: GuildId(1),
+            unavailable: true,
+        })))
+        .await?;
+
+    let entity = cache.guilds.get(GuildId(1)).await?.expect("not cached");
+    assert!(entity.unavailable);
+    assert!(cache.roles.get(RoleId(1)).await?.is_some());
+
+    cache
+        .update(&Event::UnavailableGuild(UnavailableGuild {
+            id: GuildId(1),
+        }))
+        .await?;
+    assert!(cache.guilds.get(GuildId(1)).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_delete() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    cache
+        .update(&Event::GuildDelete(Box::new(GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        })))
+        .await?;
+
+    assert!(cache.guilds.get(GuildId(1)).await?.is_none());
+    assert!(cache.text_channels.get(ChannelId(2)).await?.is_none());
+    assert!(cache.emojis.get(EmojiId(3)).await?.is_none());
+    assert!(cache.members.get((GuildId(1), UserId(4))).await?.is_none());
+    assert!(cache.roles.get(RoleId(1)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_hydrate() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    let hydrated = cache.guilds.hydrate(GuildId(1)).await?.expect("not cached");
+    assert_eq!("guild", hydrated.name);
+    assert_eq!(guild().joined_at, hydrated.joined_at);
+    assert_eq!(guild().emojis, hydrated.emojis);
+    assert_eq!(guild().members, hydrated.members);
+    assert_eq!(guild().roles, hydrated.roles);
+    assert_eq!(
+        Some(GuildId(1)),
+        hydrated.channels[&ChannelId(2)].guild_id()
+    );
+
+    let member = cache
+        .members
+        .hydrate(GuildId(1), UserId(4))
+        .await?
+        .expect("not cached");
+    assert_eq!(guild().members[&UserId(4)], member);
+
+    let emoji = cache.emojis.hydrate(EmojiId(3)).await?.expect("not cached");
+    assert_eq!(guild().emojis[&EmojiId(3)], emoji);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ready_unavailable_guilds() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&ready(vec![GuildStatus::Online(guild()), offline(7)]))
+        .await?;
+
+    assert!(cache.current_user.current().await?.is_some());
+    assert!(cache.roles.get(RoleId(1)).await?.is_some());
+
+    let placeholder = cache.guilds.get(GuildId(7)).await?.expect("not cached");
+    assert!(placeholder.unavailable);
+
+    let pending = cache
+        .guilds
+        .unavailable_ids()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![GuildId(7)], pending);
+
+    let mut available = guild();
+    available.id = GuildId(7);
+    available.channels.clear();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(available))))
+        .await?;
+
+    assert!(cache
+        .guilds
+        .unavailable_ids()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    // a cached guild listed as unavailable after reconnecting keeps its data
+    cache.update(&ready(vec![offline(1)])).await?;
+    let guild = cache.guilds.get(GuildId(1)).await?.expect("not cached");
+    assert!(guild.unavailable);
+    assert_eq!("guild", guild.name);
+
+    Ok(())
+}
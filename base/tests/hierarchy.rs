@@ -0,0 +1,203 @@
+use rarity_cache::{
+    entity::guild::{GuildEntity, MemberEntity, RoleEntity},
+    Repository,
+};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, Permissions, PremiumTier,
+        SystemChannelFlags, VerificationLevel,
+    },
+    id::{GuildId, RoleId, UserId},
+};
+
+fn guild() -> GuildEntity {
+    GuildEntity {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(1),
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: None,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(2),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        region: "us-east".to_owned(),
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn member(user_id: u64, role_ids: &[u64]) -> MemberEntity {
+    MemberEntity {
+        deaf: false,
+        guild_id: GuildId(1),
+        hoisted_role_id: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        role_ids: role_ids.iter().copied().map(RoleId).collect(),
+        user_id: UserId(user_id),
+    }
+}
+
+fn role(id: u64, position: i64, color: u32) -> RoleEntity {
+    RoleEntity {
+        color,
+        guild_id: GuildId(1),
+        hoist: false,
+        id: RoleId(id),
+        managed: false,
+        mentionable: false,
+        name: "role".to_owned(),
+        permissions: Permissions::empty(),
+        position,
+    }
+}
+
+/// Guild owned by user 2, where user 3 is a moderator, users 4 and 5 are
+/// regular members and user 6 has no roles.
+async fn cache() -> Result<InMemoryCache, Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+
+    cache.guilds.upsert(guild()).await?;
+    cache
+        .roles
+        .upsert_bulk(
+            vec![
+                role(10, 3, 0),
+                role(11, 2, 0x00ff_0000),
+                role(12, 1, 0x0000_ff00),
+            ]
+            .into_iter(),
+        )
+        .await?;
+    cache
+        .members
+        .upsert_bulk(
+            vec![
+                member(2, &[]),
+                member(3, &[10, 12]),
+                member(4, &[12]),
+                member(5, &[11, 12]),
+                member(6, &[]),
+            ]
+            .into_iter(),
+        )
+        .await?;
+
+    Ok(cache)
+}
+
+#[tokio::test]
+async fn test_highest_role_and_color() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+
+    let highest = cache.members.highest_role(GuildId(1), UserId(3)).await?;
+    assert_eq!(Some(RoleId(10)), highest.map(|role| role.id));
+    assert!(cache
+        .members
+        .highest_role(GuildId(1), UserId(6))
+        .await?
+        .is_none());
+
+    // the highest role of user 3 doesn't have a colour
+    assert_eq!(
+        Some(0x0000_ff00),
+        cache.members.color(GuildId(1), UserId(3)).await?
+    );
+    assert_eq!(
+        Some(0x00ff_0000),
+        cache.members.color(GuildId(1), UserId(5)).await?
+    );
+    assert!(cache.members.color(GuildId(1), UserId(6)).await?.is_none());
+    assert!(cache.members.color(GuildId(1), UserId(7)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_can_manage() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+    let can_manage = |user_id, target_user_id| {
+        cache
+            .members
+            .can_manage(GuildId(1), UserId(user_id), UserId(target_user_id))
+    };
+
+    assert_eq!(Some(true), can_manage(2, 3).await?);
+    assert_eq!(Some(false), can_manage(3, 2).await?);
+    assert_eq!(Some(true), can_manage(3, 5).await?);
+    assert_eq!(Some(false), can_manage(5, 3).await?);
+    assert_eq!(Some(true), can_manage(5, 6).await?);
+    // members can't manage themselves
+    assert_eq!(Some(false), can_manage(4, 4).await?);
+    assert_eq!(Some(false), can_manage(3, 3).await?);
+    assert_eq!(Some(false), can_manage(6, 4).await?);
+    assert!(can_manage(3, 7).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_role_manageable_by() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+
+    assert_eq!(
+        Some(true),
+        cache.roles.manageable_by(RoleId(10), UserId(2)).await?
+    );
+    assert_eq!(
+        Some(false),
+        cache.roles.manageable_by(RoleId(10), UserId(3)).await?
+    );
+    assert_eq!(
+        Some(true),
+        cache.roles.manageable_by(RoleId(11), UserId(3)).await?
+    );
+    assert_eq!(
+        Some(false),
+        cache.roles.manageable_by(RoleId(12), UserId(6)).await?
+    );
+    assert!(cache
+        .roles
+        .manageable_by(RoleId(13), UserId(3))
+        .await?
+        .is_none());
+    assert!(cache
+        .roles
+        .manageable_by(RoleId(10), UserId(7))
+        .await?
+        .is_none());
+
+    Ok(())
+}
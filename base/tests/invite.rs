@@ -0,0 +1,162 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::guild::GuildEntity, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{GuildDelete, InviteCreate, InviteDelete},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, MfaLevel, PremiumTier,
+        SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, GuildId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn guild(id: u64) -> GuildEntity {
+    GuildEntity {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(id),
+        joined_at: None,
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: None,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(2),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        region: "us-east".to_owned(),
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn invite_create(code: &str, channel_id: u64, max_age: u64) -> Event {
+    Event::InviteCreate(Box::new(InviteCreate {
+        channel_id: ChannelId(channel_id),
+        code: code.to_owned(),
+        created_at: "2020-05-30T20:52:43.457000+00:00".to_owned(),
+        guild_id: GuildId(1),
+        inviter: Some(user(5)),
+        max_age,
+        max_uses: 0,
+        target_user_type: None,
+        target_user: None,
+        temporary: false,
+        uses: 0,
+    }))
+}
+
+async fn codes(
+    cache: &InMemoryCache,
+    guild_id: GuildId,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut codes = cache
+        .invites
+        .guild_invites(guild_id)
+        .await?
+        .map_ok(|invite| invite.code)
+        .try_collect::<Vec<_>>()
+        .await?;
+    codes.sort();
+
+    Ok(codes)
+}
+
+#[tokio::test]
+async fn test_invite_create_delete() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache.guilds.upsert(guild(1)).await?;
+    cache.update(&invite_create("a", 2, 0)).await?;
+    cache.update(&invite_create("b", 3, 86_400)).await?;
+
+    assert_eq!(
+        vec!["a".to_owned(), "b".to_owned()],
+        codes(&cache, GuildId(1)).await?
+    );
+    let invites = cache
+        .invites
+        .channel_invites(ChannelId(3))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(1, invites.len());
+    assert!(invites[0].is_expired());
+
+    let invite = cache
+        .invites
+        .get("a".to_owned())
+        .await?
+        .expect("not cached");
+    assert!(!invite.is_expired());
+    let inviter = cache.invites.inviter("a".to_owned()).await?;
+    assert_eq!(Some(UserId(5)), inviter.map(|user| user.id));
+    let guild = cache.invites.guild("a".to_owned()).await?;
+    assert_eq!(Some(GuildId(1)), guild.map(|guild| guild.id));
+
+    cache
+        .update(&Event::InviteDelete(InviteDelete {
+            channel_id: ChannelId(2),
+            code: "a".to_owned(),
+            guild_id: GuildId(1),
+        }))
+        .await?;
+    assert_eq!(vec!["b".to_owned()], codes(&cache, GuildId(1)).await?);
+
+    cache
+        .update(&Event::GuildDelete(Box::new(GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        })))
+        .await?;
+    assert!(codes(&cache, GuildId(1)).await?.is_empty());
+    assert!(cache.invites.get("b".to_owned()).await?.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{
+    entity::guild::{GuildRepository, MemberField},
+    Repository,
+};
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{MemberAdd, MemberChunk, MemberRemove, MemberUpdate},
+    },
+    guild::Member,
+    id::{GuildId, RoleId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn member(id: u64) -> Member {
+    Member {
+        deaf: true,
+        guild_id: GuildId(1),
+        hoisted_role: Some(RoleId(2)),
+        joined_at: Some("2020-01-01T00:00:00+00:00".to_owned()),
+        mute: false,
+        nick: None,
+        premium_since: None,
+        roles: vec![RoleId(2)],
+        user: user(id),
+    }
+}
+
+#[tokio::test]
+async fn test_member_add() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::MemberAdd(Box::new(MemberAdd(member(3)))))
+        .await?;
+
+    assert!(cache.members.get((GuildId(1), UserId(3))).await?.is_some());
+    assert!(cache.users.get(UserId(3)).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_member_chunk() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+
+    let mut members = HashMap::new();
+    members.insert(UserId(3), member(3));
+    members.insert(UserId(4), member(4));
+
+    cache
+        .update(&Event::MemberChunk(MemberChunk {
+            chunk_count: 1,
+            chunk_index: 0,
+            guild_id: GuildId(1),
+            members,
+            nonce: None,
+            not_found: Vec::new(),
+            presences: HashMap::new(),
+        }))
+        .await?;
+
+    let mut member_ids = cache
+        .guilds
+        .member_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    member_ids.sort();
+    assert_eq!(vec![UserId(3), UserId(4)], member_ids);
+    assert!(cache.users.get(UserId(3)).await?.is_some());
+    assert!(cache.users.get(UserId(4)).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_member_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::MemberAdd(Box::new(MemberAdd(member(3)))))
+        .await?;
+
+    let mut user = user(3);
+    user.name = "renamed".to_owned();
+
+    cache
+        .update(&Event::MemberUpdate(Box::new(MemberUpdate {
+            guild_id: GuildId(1),
+            joined_at: "2020-01-01T00:00:00+00:00".to_owned(),
+            nick: Some("nick".to_owned()),
+            premium_since: Some("2020-02-01T00:00:00+00:00".to_owned()),
+            roles: vec![RoleId(5)],
+            user,
+        })))
+        .await?;
+
+    let member = cache
+        .members
+        .get((GuildId(1), UserId(3)))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some("nick"), member.nick.as_deref());
+    assert_eq!(
+        Some("2020-02-01T00:00:00+00:00"),
+        member.premium_since.as_deref()
+    );
+    assert_eq!(vec![RoleId(5)], member.role_ids);
+    // the hoisted role was removed from the member
+    assert!(member.hoisted_role_id.is_none());
+    // fields that aren't in the update are preserved
+    assert!(member.deaf);
+
+    let user = cache.users.get(UserId(3)).await?.expect("not cached");
+    assert_eq!("renamed", user.name);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_member_remove() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::MemberAdd(Box::new(MemberAdd(member(3)))))
+        .await?;
+    cache
+        .update(&Event::MemberRemove(MemberRemove {
+            guild_id: GuildId(1),
+            user: user(3),
+        }))
+        .await?;
+
+    assert!(cache.members.get((GuildId(1), UserId(3))).await?.is_none());
+    assert!(cache
+        .guilds
+        .member_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_field() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::MemberAdd(Box::new(MemberAdd(member(3)))))
+        .await?;
+
+    let fields = vec![
+        MemberField::AddRoleId(RoleId(4)),
+        MemberField::Nick(Some("nick".to_owned())),
+    ];
+    assert!(
+        cache
+            .members
+            .update_field((GuildId(1), UserId(3)), fields)
+            .await?
+    );
+
+    let entity = cache.members.get((GuildId(1), UserId(3))).await?.unwrap();
+    assert_eq!(Some("nick"), entity.nick.as_deref());
+    assert_eq!(vec![RoleId(2), RoleId(4)], entity.role_ids);
+    assert_eq!(Some(RoleId(2)), entity.hoisted_role_id);
+
+    let field = MemberField::RemoveRoleId(RoleId(2));
+    assert!(
+        cache
+            .members
+            .update_field((GuildId(1), UserId(3)), field)
+            .await?
+    );
+
+    let entity = cache.members.get((GuildId(1), UserId(3))).await?.unwrap();
+    assert_eq!(vec![RoleId(4)], entity.role_ids);
+    assert!(entity.hoisted_role_id.is_none());
+
+    let field = MemberField::Mute(true);
+    assert!(
+        !cache
+            .members
+            .update_field((GuildId(1), UserId(4)), field)
+            .await?
+    );
+    assert!(cache.members.get((GuildId(1), UserId(4))).await?.is_none());
+
+    Ok(())
+}
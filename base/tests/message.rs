@@ -0,0 +1,339 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::Repository;
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{
+        message::{Message, MessageReaction, MessageType},
+        Attachment, Reaction, ReactionType,
+    },
+    gateway::{
+        event::Event,
+        payload::{
+            reaction_remove_emoji::PartialEmoji, MessageCreate, MessageDelete, MessageDeleteBulk,
+            MessageUpdate, ReactionAdd, ReactionRemove, ReactionRemoveAll, ReactionRemoveEmoji,
+            UserUpdate,
+        },
+    },
+    id::{AttachmentId, ChannelId, EmojiId, GuildId, MessageId, UserId},
+    user::{CurrentUser, User},
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn attachment(id: u64) -> Attachment {
+    Attachment {
+        filename: "image.png".to_owned(),
+        height: Some(100),
+        id: AttachmentId(id),
+        proxy_url: "https://media.example.com/image.png".to_owned(),
+        size: 1024,
+        url: "https://cdn.example.com/image.png".to_owned(),
+        width: Some(100),
+    }
+}
+
+fn message(id: u64, attachments: Vec<Attachment>) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments,
+        author: user(1),
+        channel_id: ChannelId(2),
+        content: "ping".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: None,
+        id: MessageId(id),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+fn thumbs_up() -> ReactionType {
+    ReactionType::Unicode {
+        name: "👍".to_owned(),
+    }
+}
+
+fn reaction(emoji: ReactionType, user_id: u64) -> Reaction {
+    Reaction {
+        channel_id: ChannelId(2),
+        emoji,
+        guild_id: None,
+        member: None,
+        message_id: MessageId(3),
+        user_id: UserId(user_id),
+    }
+}
+
+#[tokio::test]
+async fn test_message_create() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let event = Event::MessageCreate(Box::new(MessageCreate(message(3, vec![attachment(4)]))));
+    cache.update(&event).await?;
+
+    let entity = cache.messages.get(MessageId(3)).await?.expect("not cached");
+    assert_eq!(UserId(1), entity.author_id);
+    assert_eq!(vec![AttachmentId(4)], entity.attachments);
+    assert!(cache.users.get(UserId(1)).await?.is_some());
+
+    let attachments = cache
+        .messages
+        .attachments(MessageId(3))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(1, attachments.len());
+    assert_eq!(MessageId(3), attachments[0].message_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let event = Event::MessageCreate(Box::new(MessageCreate(message(3, vec![attachment(4)]))));
+    cache.update(&event).await?;
+
+    let event = Event::MessageUpdate(Box::new(MessageUpdate {
+        attachments: Some(vec![attachment(5)]),
+        author: None,
+        channel_id: ChannelId(2),
+        content: Some("pong".to_owned()),
+        edited_timestamp: Some("2020-01-01T00:01:00+00:00".to_owned()),
+        embeds: None,
+        guild_id: None,
+        id: MessageId(3),
+        kind: None,
+        mention_everyone: None,
+        mention_roles: None,
+        mentions: None,
+        pinned: Some(true),
+        timestamp: None,
+        tts: None,
+    }));
+    cache.update(&event).await?;
+
+    let entity = cache.messages.get(MessageId(3)).await?.expect("not cached");
+    assert_eq!("pong", entity.content);
+    assert!(entity.pinned);
+    assert_eq!(UserId(1), entity.author_id);
+    assert_eq!("2020-01-01T00:00:00+00:00", entity.timestamp);
+    assert_eq!(vec![AttachmentId(5)], entity.attachments);
+    assert!(cache.attachments.get(AttachmentId(4)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(5)).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_delete() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let event = Event::MessageCreate(Box::new(MessageCreate(message(3, vec![attachment(4)]))));
+    cache.update(&event).await?;
+
+    let event = Event::MessageDelete(MessageDelete {
+        channel_id: ChannelId(2),
+        guild_id: None,
+        id: MessageId(3),
+    });
+    cache.update(&event).await?;
+
+    assert!(cache.messages.get(MessageId(3)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(4)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_delete_bulk() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+
+    for (id, attachment_id) in &[(3, 4), (5, 6)] {
+        let event = Event::MessageCreate(Box::new(MessageCreate(message(
+            *id,
+            vec![attachment(*attachment_id)],
+        ))));
+        cache.update(&event).await?;
+    }
+
+    let event = Event::MessageDeleteBulk(MessageDeleteBulk {
+        channel_id: ChannelId(2),
+        guild_id: None,
+        ids: vec![MessageId(3), MessageId(5)],
+    });
+    cache.update(&event).await?;
+
+    assert!(cache.messages.get(MessageId(3)).await?.is_none());
+    assert!(cache.messages.get(MessageId(5)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(4)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(6)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_hydrate() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let mut message = message(3, vec![attachment(4)]);
+    message.mentions.insert(UserId(5), user(5));
+    cache
+        .update(&Event::MessageCreate(Box::new(MessageCreate(
+            message.clone(),
+        ))))
+        .await?;
+
+    let hydrated = cache
+        .messages
+        .hydrate(MessageId(3))
+        .await?
+        .expect("not cached");
+    assert_eq!(message, hydrated);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_reactions() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::UserUpdate(UserUpdate(CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            id: UserId(6),
+            mfa_enabled: false,
+            name: "bot".to_owned(),
+            verified: true,
+        })))
+        .await?;
+
+    let mut message = message(3, Vec::new());
+    message.reactions.push(MessageReaction {
+        count: 2,
+        emoji: thumbs_up(),
+        me: false,
+    });
+    cache
+        .update(&Event::MessageCreate(Box::new(MessageCreate(message))))
+        .await?;
+
+    let custom = ReactionType::Custom {
+        animated: false,
+        id: EmojiId(9),
+        name: Some("custom".to_owned()),
+    };
+    let events = vec![
+        Event::ReactionAdd(Box::new(ReactionAdd(reaction(thumbs_up(), 6)))),
+        Event::ReactionAdd(Box::new(ReactionAdd(reaction(custom.clone(), 7)))),
+        // the same reaction received twice is only counted once
+        Event::ReactionAdd(Box::new(ReactionAdd(reaction(thumbs_up(), 6)))),
+    ];
+
+    for event in &events {
+        cache.update(event).await?;
+    }
+
+    let reactions = cache
+        .messages
+        .reactions(MessageId(3))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        vec![
+            MessageReaction {
+                count: 3,
+                emoji: thumbs_up(),
+                me: true,
+            },
+            MessageReaction {
+                count: 1,
+                emoji: custom.clone(),
+                me: false,
+            },
+        ],
+        reactions
+    );
+
+    let user_ids = cache
+        .messages
+        .reaction_user_ids(MessageId(3), custom.clone())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![UserId(7)], user_ids);
+
+    cache
+        .update(&Event::ReactionRemove(Box::new(ReactionRemove(reaction(
+            thumbs_up(),
+            6,
+        )))))
+        .await?;
+    let message = cache.messages.get(MessageId(3)).await?.expect("not cached");
+    assert_eq!(2, message.reactions[0].count);
+    assert!(!message.reactions[0].me);
+    assert!(message.reaction_user_ids(&thumbs_up()).is_empty());
+
+    cache
+        .update(&Event::ReactionRemoveEmoji(ReactionRemoveEmoji {
+            channel_id: ChannelId(2),
+            emoji: PartialEmoji {
+                id: Some(EmojiId(9)),
+                name: "custom".to_owned(),
+            },
+            guild_id: GuildId(1),
+            message_id: MessageId(3),
+        }))
+        .await?;
+    let message = cache.messages.get(MessageId(3)).await?.expect("not cached");
+    assert_eq!(1, message.reactions.len());
+    assert!(message.reaction_users.is_empty());
+
+    cache
+        .update(&Event::ReactionRemoveAll(ReactionRemoveAll {
+            channel_id: ChannelId(2),
+            guild_id: None,
+            message_id: MessageId(3),
+        }))
+        .await?;
+    assert!(cache
+        .messages
+        .reactions(MessageId(3))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use rarity_cache::Repository;
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType, GuildChannel, TextChannel, VoiceChannel,
+    },
+    gateway::{event::Event, payload::GuildCreate},
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, Member, MfaLevel,
+        Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, GuildId, RoleId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn member(id: u64, roles: Vec<RoleId>) -> Member {
+    Member {
+        deaf: false,
+        guild_id: GuildId(1),
+        hoisted_role: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        roles,
+        user: user(id),
+    }
+}
+
+fn role(id: u64, permissions: Permissions) -> Role {
+    Role {
+        color: 0,
+        hoist: false,
+        id: RoleId(id),
+        managed: false,
+        mentionable: false,
+        name: "role".to_owned(),
+        permissions,
+        position: 0,
+    }
+}
+
+/// Guild with the ID 1 owned by user 4.
+///
+/// Members can view channels and send messages, and user 5 has role 3, which
+/// can manage messages. In text channel 2 only role 3 can send messages, and
+/// user 5 can't view voice channel 6.
+fn guild() -> Guild {
+    let mut channels = HashMap::new();
+    channels.insert(
+        ChannelId(2),
+        GuildChannel::Text(TextChannel {
+            guild_id: None,
+            id: ChannelId(2),
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "announcements".to_owned(),
+            nsfw: false,
+            permission_overwrites: vec![
+                PermissionOverwrite {
+                    allow: Permissions::empty(),
+                    deny: Permissions::SEND_MESSAGES,
+                    kind: PermissionOverwriteType::Role(RoleId(1)),
+                },
+                PermissionOverwrite {
+                    allow: Permissions::SEND_MESSAGES,
+                    deny: Permissions::empty(),
+                    kind: PermissionOverwriteType::Role(RoleId(3)),
+                },
+            ],
+            parent_id: None,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        }),
+    );
+    channels.insert(
+        ChannelId(6),
+        GuildChannel::Voice(VoiceChannel {
+            bitrate: 64000,
+            guild_id: None,
+            id: ChannelId(6),
+            kind: ChannelType::GuildVoice,
+            name: "voice".to_owned(),
+            permission_overwrites: vec![PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                kind: PermissionOverwriteType::Member(UserId(5)),
+            }],
+            parent_id: None,
+            position: 1,
+            user_limit: None,
+        }),
+    );
+
+    let mut members = HashMap::new();
+    members.insert(UserId(4), member(4, Vec::new()));
+    members.insert(UserId(5), member(5, vec![RoleId(3)]));
+    members.insert(UserId(7), member(7, Vec::new()));
+
+    let mut roles = HashMap::new();
+    roles.insert(
+        RoleId(1),
+        role(1, Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+    );
+    roles.insert(RoleId(3), role(3, Permissions::MANAGE_MESSAGES));
+
+    Guild {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        channels,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        emojis: HashMap::new(),
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(1),
+        joined_at: Some("2020-01-01T00:00:00+00:00".to_owned()),
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: Some(3),
+        members,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(4),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        presences: HashMap::new(),
+        region: "us-east".to_owned(),
+        roles,
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        voice_states: HashMap::new(),
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+async fn cache() -> Result<InMemoryCache, Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::GuildCreate(Box::new(GuildCreate(guild()))))
+        .await?;
+
+    Ok(cache)
+}
+
+#[tokio::test]
+async fn test_guild_permissions() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+
+    assert_eq!(
+        Some(Permissions::all()),
+        cache.guild_permissions(GuildId(1), UserId(4)).await?
+    );
+    assert_eq!(
+        Some(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES),
+        cache.guild_permissions(GuildId(1), UserId(5)).await?
+    );
+    assert_eq!(
+        Some(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+        cache.guild_permissions(GuildId(1), UserId(7)).await?
+    );
+    assert!(cache
+        .guild_permissions(GuildId(1), UserId(8))
+        .await?
+        .is_none());
+    assert!(cache
+        .guild_permissions(GuildId(9), UserId(5))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channel_permissions() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+
+    assert_eq!(
+        Some(Permissions::all()),
+        cache.channel_permissions(ChannelId(6), UserId(4)).await?
+    );
+    assert_eq!(
+        Some(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES),
+        cache.channel_permissions(ChannelId(2), UserId(5)).await?
+    );
+    assert_eq!(
+        Some(Permissions::VIEW_CHANNEL),
+        cache.channel_permissions(ChannelId(2), UserId(7)).await?
+    );
+    assert_eq!(
+        Some(Permissions::empty()),
+        cache.channel_permissions(ChannelId(6), UserId(5)).await?
+    );
+    assert!(cache
+        .channel_permissions(ChannelId(10), UserId(5))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_permissions_with_uncached_role() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = cache().await?;
+    cache.roles.remove(RoleId(3)).await?;
+
+    assert_eq!(
+        Some(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+        cache.guild_permissions(GuildId(1), UserId(5)).await?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{config::Config, entity::user::UserEntity, Cache, Repository};
+use rarity_cache_inmemory::{InMemoryBackend, InMemoryCache};
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::PresenceUpdate,
+        presence::{Activity, ActivityType, ClientStatus, Status, UserOrId},
+    },
+    id::{GuildId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn activity(name: &str) -> Activity {
+    Activity {
+        application_id: None,
+        assets: None,
+        created_at: None,
+        details: None,
+        emoji: None,
+        flags: None,
+        id: None,
+        instance: None,
+        kind: ActivityType::Playing,
+        name: name.to_owned(),
+        party: None,
+        secrets: None,
+        state: None,
+        timestamps: None,
+        url: None,
+    }
+}
+
+fn presence_update(user_id: u64, status: Status, activities: Vec<Activity>) -> Event {
+    Event::PresenceUpdate(Box::new(PresenceUpdate {
+        activities,
+        client_status: ClientStatus {
+            desktop: Some(status),
+            mobile: None,
+            web: None,
+        },
+        game: None,
+        guild_id: GuildId(1),
+        nick: None,
+        premium_since: None,
+        roles: None,
+        status,
+        user: UserOrId::UserId {
+            id: UserId(user_id),
+        },
+    }))
+}
+
+#[tokio::test]
+async fn test_presence_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+
+    let mut cached = UserEntity::from(user(2));
+    cached.email.replace("test@example.com".to_owned());
+    cache.users.upsert(cached).await?;
+
+    let mut user = user(2);
+    user.name = "renamed".to_owned();
+
+    cache
+        .update(&Event::PresenceUpdate(Box::new(PresenceUpdate {
+            activities: Vec::new(),
+            client_status: ClientStatus {
+                desktop: None,
+                mobile: Some(Status::Idle),
+                web: None,
+            },
+            game: None,
+            guild_id: GuildId(1),
+            nick: None,
+            premium_since: None,
+            roles: None,
+            status: Status::Idle,
+            user: UserOrId::User(user),
+        })))
+        .await?;
+
+    let presence = cache
+        .presences
+        .get((GuildId(1), UserId(2)))
+        .await?
+        .expect("not cached");
+    assert_eq!(Status::Idle, presence.status);
+
+    // the user is merged with the cached user
+    let user = cache.users.get(UserId(2)).await?.expect("not cached");
+    assert_eq!("renamed", user.name);
+    assert_eq!(Some("test@example.com"), user.email.as_deref());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_offline_presences() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&presence_update(2, Status::Online, Vec::new()))
+        .await?;
+    cache
+        .update(&presence_update(2, Status::Offline, Vec::new()))
+        .await?;
+
+    let presence = cache.presences.get((GuildId(1), UserId(2))).await?;
+    assert_eq!(Some(Status::Offline), presence.map(|p| p.status));
+
+    let mut config = Config::default();
+    *config.evict_offline_presences_mut() = true;
+    let cache: Cache<InMemoryBackend> = Cache::with_config(InMemoryBackend::new(), config);
+
+    cache
+        .update(&presence_update(2, Status::Online, Vec::new()))
+        .await?;
+    cache
+        .update(&presence_update(2, Status::Offline, Vec::new()))
+        .await?;
+
+    assert!(cache
+        .presences
+        .get((GuildId(1), UserId(2)))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_queries() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+
+    let events = vec![
+        presence_update(2, Status::Online, vec![activity("Minecraft")]),
+        presence_update(3, Status::DoNotDisturb, vec![activity("Terraria")]),
+        presence_update(4, Status::Invisible, vec![activity("Minecraft")]),
+        presence_update(5, Status::Offline, Vec::new()),
+    ];
+
+    for event in &events {
+        cache.update(event).await?;
+    }
+
+    let mut online = cache
+        .presences
+        .online(GuildId(1))
+        .await?
+        .map_ok(|presence| presence.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    online.sort();
+    assert_eq!(vec![UserId(2), UserId(3)], online);
+
+    let mut playing = cache
+        .presences
+        .with_activity(GuildId(1), "Minecraft")
+        .await?
+        .map_ok(|presence| presence.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    playing.sort();
+    assert_eq!(vec![UserId(2), UserId(4)], playing);
+
+    assert!(cache
+        .presences
+        .online(GuildId(6))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{
+    entity::guild::{EmojiEntity, GuildRepository, MemberEntity},
+    Repository,
+};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    gateway::{
+        event::Event,
+        payload::{GuildEmojisUpdate, RoleCreate, RoleDelete, RoleUpdate},
+    },
+    guild::{Emoji, Permissions, Role},
+    id::{EmojiId, GuildId, RoleId, UserId},
+    user::User,
+};
+
+fn role(name: &str) -> Role {
+    Role {
+        color: 0,
+        hoist: false,
+        id: RoleId(2),
+        managed: false,
+        mentionable: false,
+        name: name.to_owned(),
+        permissions: Permissions::SEND_MESSAGES,
+        position: 1,
+    }
+}
+
+fn emoji(id: u64, role_ids: Vec<RoleId>) -> Emoji {
+    Emoji {
+        animated: false,
+        available: true,
+        id: EmojiId(id),
+        managed: false,
+        name: "emoji".to_owned(),
+        require_colons: true,
+        roles: role_ids,
+        user: Some(User {
+            avatar: None,
+            bot: false,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(5),
+            locale: None,
+            mfa_enabled: None,
+            name: "test".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }),
+    }
+}
+
+fn member(user_id: u64, role_ids: Vec<RoleId>) -> MemberEntity {
+    MemberEntity {
+        deaf: false,
+        guild_id: GuildId(1),
+        hoisted_role_id: role_ids.first().copied(),
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        role_ids,
+        user_id: UserId(user_id),
+    }
+}
+
+fn emojis_update(emojis: Vec<Emoji>) -> Event {
+    Event::GuildEmojisUpdate(GuildEmojisUpdate {
+        emojis: emojis.into_iter().map(|emoji| (emoji.id, emoji)).collect(),
+        guild_id: GuildId(1),
+    })
+}
+
+#[tokio::test]
+async fn test_role_create_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::RoleCreate(RoleCreate {
+            guild_id: GuildId(1),
+            role: role("created"),
+        }))
+        .await?;
+
+    let role_ids = cache
+        .guilds
+        .role_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![RoleId(2)], role_ids);
+
+    cache
+        .update(&Event::RoleUpdate(RoleUpdate {
+            guild_id: GuildId(1),
+            role: role("updated"),
+        }))
+        .await?;
+
+    let role = cache.roles.get(RoleId(2)).await?.expect("not cached");
+    assert_eq!("updated", role.name);
+    assert_eq!(GuildId(1), role.guild_id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_role_delete() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::RoleCreate(RoleCreate {
+            guild_id: GuildId(1),
+            role: role("role"),
+        }))
+        .await?;
+    cache
+        .update(&emojis_update(vec![
+            emoji(3, vec![RoleId(2), RoleId(4)]),
+            emoji(6, Vec::new()),
+        ]))
+        .await?;
+    cache
+        .members
+        .upsert_bulk(
+            vec![
+                member(7, vec![RoleId(2), RoleId(4)]),
+                member(8, vec![RoleId(4)]),
+            ]
+            .into_iter(),
+        )
+        .await?;
+
+    cache
+        .update(&Event::RoleDelete(RoleDelete {
+            guild_id: GuildId(1),
+            role_id: RoleId(2),
+        }))
+        .await?;
+
+    assert!(cache.roles.get(RoleId(2)).await?.is_none());
+
+    let member = cache
+        .members
+        .get((GuildId(1), UserId(7)))
+        .await?
+        .expect("not cached");
+    assert_eq!(vec![RoleId(4)], member.role_ids);
+    assert!(member.hoisted_role_id.is_none());
+
+    let member = cache
+        .members
+        .get((GuildId(1), UserId(8)))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some(RoleId(4)), member.hoisted_role_id);
+
+    let emoji = cache.emojis.get(EmojiId(3)).await?.expect("not cached");
+    assert_eq!(vec![RoleId(4)], emoji.role_ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guild_emojis_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&emojis_update(vec![
+            emoji(3, Vec::new()),
+            emoji(6, Vec::new()),
+        ]))
+        .await?;
+
+    let mut renamed = emoji(6, Vec::new());
+    renamed.name = "renamed".to_owned();
+    cache
+        .update(&emojis_update(vec![renamed, emoji(9, Vec::new())]))
+        .await?;
+
+    let mut emojis = cache
+        .guilds
+        .emojis(GuildId(1))
+        .await?
+        .try_collect::<Vec<EmojiEntity>>()
+        .await?;
+    emojis.sort_by_key(|emoji| emoji.id);
+    assert_eq!(
+        vec![(EmojiId(6), "renamed"), (EmojiId(9), "emoji")],
+        emojis
+            .iter()
+            .map(|emoji| (emoji.id, emoji.name.as_str()))
+            .collect::<Vec<_>>()
+    );
+    assert!(cache.emojis.get(EmojiId(3)).await?.is_none());
+    assert!(cache.users.get(UserId(5)).await?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::entity::gateway::TypingEntity;
+use rarity_cache_inmemory::InMemoryCache;
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use twilight_model::{
+    channel::message::{Message, MessageType},
+    gateway::{
+        event::Event,
+        payload::{MessageCreate, TypingStart},
+    },
+    id::{ChannelId, GuildId, MessageId, UserId},
+    user::User,
+};
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap()
+}
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn typing_start(user_id: u64, timestamp: u64) -> Event {
+    Event::TypingStart(Box::new(TypingStart {
+        channel_id: ChannelId(2),
+        guild_id: Some(GuildId(1)),
+        member: None,
+        timestamp,
+        user_id: UserId(user_id),
+    }))
+}
+
+fn message(author_id: u64) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: Vec::new(),
+        author: user(author_id),
+        channel_id: ChannelId(2),
+        content: "hello".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: Some(GuildId(1)),
+        id: MessageId(5),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+async fn typing_user_ids(cache: &InMemoryCache) -> Result<Vec<UserId>, Box<dyn std::error::Error>> {
+    let mut user_ids = cache
+        .typing
+        .channel_typing(ChannelId(2))
+        .await?
+        .map_ok(|typing| typing.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    user_ids.sort();
+
+    Ok(user_ids)
+}
+
+#[tokio::test]
+async fn test_typing_start() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let now = now();
+    cache.update(&typing_start(3, now)).await?;
+    cache.update(&typing_start(4, now - 1)).await?;
+    cache
+        .update(&typing_start(5, now - TypingEntity::DURATION))
+        .await?;
+
+    assert_eq!(vec![UserId(3), UserId(4)], typing_user_ids(&cache).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_create_ends_typing() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    let now = now();
+    cache.update(&typing_start(3, now)).await?;
+    cache.update(&typing_start(4, now)).await?;
+
+    cache
+        .update(&Event::MessageCreate(Box::new(MessageCreate(message(3)))))
+        .await?;
+    assert_eq!(vec![UserId(4)], typing_user_ids(&cache).await?);
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::channel::VoiceChannelEntity, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use twilight_model::{
+    channel::ChannelType,
+    gateway::{event::Event, payload::VoiceStateUpdate},
+    guild::Member,
+    id::{ChannelId, GuildId, UserId},
+    user::User,
+    voice::VoiceState,
+};
+
+fn voice_channel(id: u64) -> VoiceChannelEntity {
+    VoiceChannelEntity {
+        bitrate: 64000,
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(id),
+        kind: ChannelType::GuildVoice,
+        name: "voice".to_owned(),
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        user_limit: None,
+    }
+}
+
+fn member(user_id: u64) -> Member {
+    Member {
+        deaf: false,
+        guild_id: GuildId(1),
+        hoisted_role: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        roles: Vec::new(),
+        user: User {
+            avatar: None,
+            bot: false,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(user_id),
+            locale: None,
+            mfa_enabled: None,
+            name: "test".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        },
+    }
+}
+
+fn voice_state_update(user_id: u64, channel_id: Option<u64>) -> Event {
+    Event::VoiceStateUpdate(Box::new(VoiceStateUpdate(VoiceState {
+        channel_id: channel_id.map(ChannelId),
+        deaf: false,
+        guild_id: Some(GuildId(1)),
+        member: Some(member(user_id)),
+        mute: false,
+        self_deaf: false,
+        self_mute: true,
+        self_stream: false,
+        session_id: "session".to_owned(),
+        suppress: false,
+        token: None,
+        user_id: UserId(user_id),
+    })))
+}
+
+#[tokio::test]
+async fn test_voice_state_update() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache.update(&voice_state_update(3, Some(2))).await?;
+
+    let state = cache
+        .voice_states
+        .get((GuildId(1), UserId(3)))
+        .await?
+        .expect("not cached");
+    assert_eq!(Some(ChannelId(2)), state.channel_id);
+    assert!(state.self_mute);
+    assert!(cache.members.get((GuildId(1), UserId(3))).await?.is_some());
+    assert!(cache.users.get(UserId(3)).await?.is_some());
+
+    // disconnecting from voice removes the voice state
+    cache.update(&voice_state_update(3, None)).await?;
+    assert!(cache
+        .voice_states
+        .get((GuildId(1), UserId(3)))
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_voice_channel_voice_states() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .voice_channels
+        .upsert_bulk(vec![voice_channel(2), voice_channel(5)].into_iter())
+        .await?;
+
+    for (user_id, channel_id) in &[(3, 2), (4, 2), (6, 5)] {
+        cache
+            .update(&voice_state_update(*user_id, Some(*channel_id)))
+            .await?;
+    }
+
+    // moving to another channel moves the user between channels
+    cache.update(&voice_state_update(4, Some(5))).await?;
+
+    let states = cache
+        .voice_channels
+        .voice_states(ChannelId(2))
+        .await?
+        .map_ok(|state| state.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![UserId(3)], states);
+
+    let mut members = cache
+        .voice_channels
+        .members(ChannelId(5))
+        .await?
+        .map_ok(|member| member.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    members.sort();
+    assert_eq!(vec![UserId(4), UserId(6)], members);
+
+    assert!(cache
+        .voice_channels
+        .voice_states(ChannelId(7))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use futures_util::stream::TryStreamExt;
+use rarity_cache::{entity::channel::WebhookEntity, Repository};
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{
+        message::{Message, MessageType},
+        Channel, ChannelType, GuildChannel, TextChannel, WebhookType,
+    },
+    gateway::{
+        event::Event,
+        payload::{ChannelCreate, ChannelDelete, MessageCreate, WebhooksUpdate},
+    },
+    id::{ChannelId, GuildId, MessageId, UserId, WebhookId},
+    user::User,
+};
+
+fn text_channel() -> Channel {
+    Channel::Guild(GuildChannel::Text(TextChannel {
+        guild_id: Some(GuildId(1)),
+        id: ChannelId(2),
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "general".to_owned(),
+        nsfw: false,
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    }))
+}
+
+fn webhook(id: u64) -> WebhookEntity {
+    WebhookEntity {
+        avatar: None,
+        channel_id: ChannelId(2),
+        guild_id: Some(GuildId(1)),
+        id: WebhookId(id),
+        kind: WebhookType::Incoming,
+        name: Some("hook".to_owned()),
+        token: None,
+        user_id: None,
+    }
+}
+
+fn webhook_message(webhook_id: u64) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: Vec::new(),
+        author: User {
+            avatar: None,
+            bot: true,
+            discriminator: "0000".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(webhook_id),
+            locale: None,
+            mfa_enabled: None,
+            name: "hook".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        },
+        channel_id: ChannelId(2),
+        content: "ping".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: Some(GuildId(1)),
+        id: MessageId(4),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: Some(WebhookId(webhook_id)),
+    }
+}
+
+fn webhooks_update() -> Event {
+    Event::WebhooksUpdate(WebhooksUpdate {
+        channel_id: ChannelId(2),
+        guild_id: GuildId(1),
+    })
+}
+
+#[tokio::test]
+async fn test_webhooks_update_marks_stale() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache.update(&webhooks_update()).await?;
+    assert!(cache.webhooks.is_stale(ChannelId(2)).await?.is_none());
+
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(text_channel())))
+        .await?;
+    cache.webhooks.upsert(webhook(3)).await?;
+    assert_eq!(Some(false), cache.webhooks.is_stale(ChannelId(2)).await?);
+
+    cache.update(&webhooks_update()).await?;
+    assert_eq!(Some(true), cache.webhooks.is_stale(ChannelId(2)).await?);
+
+    cache
+        .webhooks
+        .refresh(ChannelId(2), vec![webhook(5)])
+        .await?;
+    assert_eq!(Some(false), cache.webhooks.is_stale(ChannelId(2)).await?);
+    assert!(cache.webhooks.get(WebhookId(3)).await?.is_none());
+
+    let ids = cache
+        .webhooks
+        .guild_webhooks(GuildId(1))
+        .await?
+        .map_ok(|webhook| webhook.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![WebhookId(5)], ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_webhook() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = InMemoryCache::new();
+    cache
+        .update(&Event::ChannelCreate(ChannelCreate(text_channel())))
+        .await?;
+    cache.webhooks.upsert(webhook(3)).await?;
+    cache
+        .update(&Event::MessageCreate(Box::new(MessageCreate(
+            webhook_message(3),
+        ))))
+        .await?;
+
+    let webhook = cache.messages.webhook(MessageId(4)).await?;
+    assert_eq!(Some(WebhookId(3)), webhook.map(|webhook| webhook.id));
+
+    cache
+        .update(&Event::ChannelDelete(ChannelDelete(text_channel())))
+        .await?;
+    assert!(cache.webhooks.get(WebhookId(3)).await?.is_none());
+
+    Ok(())
+}
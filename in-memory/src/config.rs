@@ -22,6 +22,10 @@ bitflags! {
         const USER = 1 << 12;
         const USER_CURRENT = 1 << 13;
         const VOICE_STATE = 1 << 14;
+        const BAN = 1 << 15;
+        const INVITE = 1 << 16;
+        const WEBHOOK = 1 << 17;
+        const TYPING = 1 << 18;
     }
 }
 
@@ -100,6 +104,10 @@ mod tests {
         assert_eq!(1 << 12, EntityType::USER.bits());
         assert_eq!(1 << 13, EntityType::USER_CURRENT.bits());
         assert_eq!(1 << 14, EntityType::VOICE_STATE.bits());
+        assert_eq!(1 << 15, EntityType::BAN.bits());
+        assert_eq!(1 << 16, EntityType::INVITE.bits());
+        assert_eq!(1 << 17, EntityType::WEBHOOK.bits());
+        assert_eq!(1 << 18, EntityType::TYPING.bits());
     }
 
     #[test]
@@ -79,11 +79,13 @@ pub use rarity_cache::Repository;
 use self::{
     config::{Config, EntityType},
     repository::{
-        InMemoryAttachmentRepository, InMemoryCategoryChannelRepository, InMemoryEmojiRepository,
-        InMemoryGroupRepository, InMemoryGuildRepository, InMemoryMemberRepository,
+        InMemoryAttachmentRepository, InMemoryBanRepository, InMemoryCategoryChannelRepository,
+        InMemoryCurrentUserRepository, InMemoryEmojiRepository, InMemoryGroupRepository,
+        InMemoryGuildRepository, InMemoryInviteRepository, InMemoryMemberRepository,
         InMemoryMessageRepository, InMemoryPresenceRepository, InMemoryPrivateChannelRepository,
-        InMemoryRoleRepository, InMemoryTextChannelRepository, InMemoryUserRepository,
-        InMemoryVoiceChannelRepository, InMemoryVoiceStateRepository,
+        InMemoryRoleRepository, InMemoryTextChannelRepository, InMemoryTypingRepository,
+        InMemoryUserRepository, InMemoryVoiceChannelRepository, InMemoryVoiceStateRepository,
+        InMemoryWebhookRepository,
     },
 };
 use dashmap::DashMap;
@@ -91,11 +93,12 @@ use rarity_cache::{
     entity::{
         channel::{
             AttachmentEntity, CategoryChannelEntity, GroupEntity, MessageEntity,
-            PrivateChannelEntity, TextChannelEntity, VoiceChannelEntity,
+            PrivateChannelEntity, TextChannelEntity, VoiceChannelEntity, WebhookEntity,
         },
-        gateway::PresenceEntity,
-        guild::{EmojiEntity, GuildEntity, MemberEntity, RoleEntity},
-        user::UserEntity,
+        gateway::{PresenceEntity, TypingEntity},
+        guild::{BanEntity, EmojiEntity, GuildEntity, MemberEntity, RoleEntity},
+        invite::InviteEntity,
+        user::{CurrentUserEntity, UserEntity},
         voice::VoiceStateEntity,
     },
     Backend, Cache,
@@ -104,9 +107,11 @@ use std::{
     collections::{BTreeSet, HashSet},
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
-    sync::Arc,
+    sync::{Arc, Mutex},
+};
+use twilight_model::id::{
+    AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId, WebhookId,
 };
-use twilight_model::id::{AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId};
 
 /// Alias over `rarity_cache::Cache` which uses the [`InMemoryBackend`].
 ///
@@ -148,28 +153,40 @@ impl Error for InMemoryBackendError {}
 #[derive(Debug, Default)]
 struct InMemoryBackendRef {
     attachments: DashMap<AttachmentId, AttachmentEntity>,
+    bans: DashMap<(GuildId, UserId), BanEntity>,
     channels_category: DashMap<ChannelId, CategoryChannelEntity>,
     channels_private: DashMap<ChannelId, PrivateChannelEntity>,
     channels_text: DashMap<ChannelId, TextChannelEntity>,
     channels_voice: DashMap<ChannelId, VoiceChannelEntity>,
+    channel_invites: DashMap<ChannelId, HashSet<String>>,
     channel_messages: DashMap<ChannelId, BTreeSet<MessageId>>,
+    channel_typing: DashMap<ChannelId, HashSet<UserId>>,
+    channel_webhooks: DashMap<ChannelId, HashSet<WebhookId>>,
     config: Config,
+    current_user: Mutex<Option<CurrentUserEntity>>,
     emojis: DashMap<EmojiId, EmojiEntity>,
     groups: DashMap<ChannelId, GroupEntity>,
     guilds: DashMap<GuildId, GuildEntity>,
+    guild_bans: DashMap<GuildId, HashSet<UserId>>,
     guild_channels: DashMap<GuildId, HashSet<ChannelId>>,
     guild_emojis: DashMap<GuildId, HashSet<EmojiId>>,
+    guild_invites: DashMap<GuildId, HashSet<String>>,
     guild_members: DashMap<GuildId, HashSet<UserId>>,
     guild_presences: DashMap<GuildId, HashSet<UserId>>,
     guild_roles: DashMap<GuildId, HashSet<RoleId>>,
     guild_voice_states: DashMap<GuildId, HashSet<UserId>>,
+    guild_webhooks: DashMap<GuildId, HashSet<WebhookId>>,
+    invites: DashMap<String, InviteEntity>,
     members: DashMap<(GuildId, UserId), MemberEntity>,
     messages: DashMap<MessageId, MessageEntity>,
     presences: DashMap<(GuildId, UserId), PresenceEntity>,
     roles: DashMap<RoleId, RoleEntity>,
+    typing: DashMap<(ChannelId, UserId), TypingEntity>,
     users: DashMap<UserId, UserEntity>,
     user_guilds: DashMap<UserId, Vec<GuildId>>,
     voice_states: DashMap<(GuildId, UserId), VoiceStateEntity>,
+    webhooks: DashMap<WebhookId, WebhookEntity>,
+    webhooks_stale: DashMap<ChannelId, ()>,
 }
 
 /// Builder to create a configured [`InMemoryBackend`].
@@ -204,6 +221,17 @@ impl InMemoryBackendBuilder {
 }
 
 /// Backend implementation to cache entities in the process's memory.
+///
+/// Entities are stored separately from the guild and user indexes that back
+/// relation methods such as [`GuildRepository::members`]. Upserts write the
+/// entity before indexing it and removals unindex the entity before removing
+/// it, so an indexed entity can always be retrieved. Writing the two isn't
+/// atomic, however: a removal that runs concurrently with an upsert of the
+/// same entity can leave an index entry without an entity, which relation
+/// methods skip, or an entity that isn't indexed, until it's next upserted or
+/// removed.
+///
+/// [`GuildRepository::members`]: ../rarity_cache/entity/guild/trait.GuildRepository.html#tymethod.members
 #[derive(Clone, Debug, Default)]
 pub struct InMemoryBackend(Arc<InMemoryBackendRef>);
 
@@ -281,30 +309,45 @@ impl InMemoryBackend {
 impl Backend for InMemoryBackend {
     type Error = InMemoryBackendError;
     type AttachmentRepository = InMemoryAttachmentRepository;
+    type BanRepository = InMemoryBanRepository;
     type CategoryChannelRepository = InMemoryCategoryChannelRepository;
+    type CurrentUserRepository = InMemoryCurrentUserRepository;
     type EmojiRepository = InMemoryEmojiRepository;
     type GroupRepository = InMemoryGroupRepository;
     type GuildRepository = InMemoryGuildRepository;
+    type InviteRepository = InMemoryInviteRepository;
     type MemberRepository = InMemoryMemberRepository;
     type MessageRepository = InMemoryMessageRepository;
     type PresenceRepository = InMemoryPresenceRepository;
     type PrivateChannelRepository = InMemoryPrivateChannelRepository;
     type RoleRepository = InMemoryRoleRepository;
     type TextChannelRepository = InMemoryTextChannelRepository;
+    type TypingRepository = InMemoryTypingRepository;
     type UserRepository = InMemoryUserRepository;
     type VoiceChannelRepository = InMemoryVoiceChannelRepository;
     type VoiceStateRepository = InMemoryVoiceStateRepository;
+    type WebhookRepository = InMemoryWebhookRepository;
 
     /// A new instance of a repository for working with attachments.
     fn attachments(&self) -> Self::AttachmentRepository {
         InMemoryAttachmentRepository(self.clone())
     }
 
+    /// A new instance of a repository for working with bans.
+    fn bans(&self) -> Self::BanRepository {
+        InMemoryBanRepository(self.clone())
+    }
+
     /// A new instance of a repository for working with guild category channels.
     fn category_channels(&self) -> Self::CategoryChannelRepository {
         InMemoryCategoryChannelRepository(self.clone())
     }
 
+    /// A new instance of a repository for working with the current user.
+    fn current_user(&self) -> Self::CurrentUserRepository {
+        InMemoryCurrentUserRepository(self.clone())
+    }
+
     /// A new instance of a repository for working with emojis.
     fn emojis(&self) -> Self::EmojiRepository {
         InMemoryEmojiRepository(self.clone())
@@ -320,6 +363,11 @@ impl Backend for InMemoryBackend {
         InMemoryGuildRepository(self.clone())
     }
 
+    /// A new instance of a repository for working with invites.
+    fn invites(&self) -> Self::InviteRepository {
+        InMemoryInviteRepository(self.clone())
+    }
+
     /// A new instance of a repository for working with members.
     fn members(&self) -> Self::MemberRepository {
         InMemoryMemberRepository(self.clone())
@@ -350,6 +398,10 @@ impl Backend for InMemoryBackend {
         InMemoryTextChannelRepository(self.clone())
     }
 
+    fn typing(&self) -> Self::TypingRepository {
+        InMemoryTypingRepository(self.clone())
+    }
+
     /// A new instance of a repository for working with users.
     fn users(&self) -> Self::UserRepository {
         InMemoryUserRepository(self.clone())
@@ -364,6 +416,11 @@ impl Backend for InMemoryBackend {
     fn voice_states(&self) -> Self::VoiceStateRepository {
         InMemoryVoiceStateRepository(self.clone())
     }
+
+    /// A new instance of a repository for working with webhooks.
+    fn webhooks(&self) -> Self::WebhookRepository {
+        InMemoryWebhookRepository(self.clone())
+    }
 }
 
 #[cfg(test)]
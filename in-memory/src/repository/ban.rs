@@ -0,0 +1,198 @@
+use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        guild::{BanEntity, BanRepository},
+        user::UserEntity,
+        Entity,
+    },
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+    },
+};
+use twilight_model::id::{GuildId, UserId};
+
+/// Repository to retrieve and work with bans and their related entities.
+#[derive(Clone, Debug)]
+pub struct InMemoryBanRepository(pub(crate) InMemoryBackend);
+
+impl Repository<BanEntity, InMemoryBackend> for InMemoryBanRepository {
+    fn backend(&self) -> InMemoryBackend {
+        self.0.clone()
+    }
+
+    fn get(
+        &self,
+        ban_id: (GuildId, UserId),
+    ) -> GetEntityFuture<'_, BanEntity, InMemoryBackendError> {
+        future::ok((self.0).0.bans.get(&ban_id).map(|r| r.value().clone())).boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, BanEntity, InMemoryBackendError> {
+        let stream = stream::iter((self.0).0.bans.iter().map(|r| Ok(r.value().clone()))).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn remove(&self, ban_id: (GuildId, UserId)) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        if (self.0).0.bans.remove(&ban_id).is_some() {
+            let (guild_id, user_id) = ban_id;
+
+            if let Some(mut guild_bans) = (self.0).0.guild_bans.get_mut(&guild_id) {
+                guild_bans.remove(&user_id);
+            }
+        }
+
+        future::ok(()).boxed()
+    }
+
+    fn upsert(&self, entity: BanEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if !(self.0).0.config.entity_types().contains(EntityType::BAN) {
+            return future::ok(()).boxed();
+        }
+
+        (self.0)
+            .0
+            .guild_bans
+            .entry(entity.guild_id)
+            .or_default()
+            .insert(entity.user_id);
+
+        (self.0).0.bans.insert(entity.id(), entity);
+
+        future::ok(()).boxed()
+    }
+}
+
+impl BanRepository<InMemoryBackend> for InMemoryBanRepository {
+    fn bans(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, BanEntity, InMemoryBackendError> {
+        let user_ids = match (self.0).0.guild_bans.get(&guild_id) {
+            Some(guild_bans) => guild_bans.clone(),
+            None => return future::ok(stream::empty().boxed()).boxed(),
+        };
+
+        let iter = user_ids.into_iter().filter_map(move |id| {
+            (self.0)
+                .0
+                .bans
+                .get(&(guild_id, id))
+                .map(|r| Ok(r.value().clone()))
+        });
+        let stream = stream::iter(iter).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn is_banned(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        if !(self.0).0.guilds.contains_key(&guild_id) {
+            return future::ok(None).boxed();
+        }
+
+        let banned = (self.0).0.bans.contains_key(&(guild_id, user_id));
+
+        future::ok(Some(banned)).boxed()
+    }
+
+    fn user(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        let user = (self.0)
+            .0
+            .bans
+            .get(&(guild_id, user_id))
+            .and_then(|ban| (self.0).0.users.get(&ban.user_id))
+            .map(|r| r.value().clone());
+
+        future::ok(user).boxed()
+    }
+}
+
+impl InMemoryBanRepository {
+    /// Retrieve a stream of the bans within a guild.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut bans = cache.bans.bans(GuildId(123456)).await?;
+    ///
+    /// while let Some(ban) = bans.try_next().await? {
+    ///     println!("user {} is banned", ban.user_id);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn bans(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, BanEntity, InMemoryBackendError> {
+        BanRepository::bans(self, guild_id)
+    }
+
+    /// Retrieve whether a user is banned from a guild.
+    ///
+    /// Returns `None` if the guild isn't in the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{GuildId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if cache.bans.is_banned(GuildId(1), UserId(2)).await? == Some(true) {
+    ///     println!("the user is banned");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn is_banned(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        BanRepository::is_banned(self, guild_id, user_id)
+    }
+
+    /// Retrieve the banned user of a ban.
+    pub fn user(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        BanRepository::user(self, guild_id, user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BanEntity, BanRepository, InMemoryBackend, InMemoryBanRepository, Repository};
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+
+    assert_impl_all!(
+        InMemoryBanRepository:
+        BanRepository<InMemoryBackend>,
+        Clone,
+        Debug,
+        Repository<BanEntity, InMemoryBackend>,
+        Send,
+        Sync,
+    );
+    assert_obj_safe!(InMemoryBanRepository);
+}
@@ -52,6 +52,25 @@ impl Repository<CategoryChannelEntity, InMemoryBackend> for InMemoryCategoryChan
     }
 
     fn remove(&self, channel_id: ChannelId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let guild_id = (self.0)
+            .0
+            .channels_category
+            .get(&channel_id)
+            .and_then(|channel| channel.guild_id);
+
+        // Guild channels of every kind share the guild's channel index, so it
+        // keeps the channel while another kind of channel with its ID is
+        // cached, such as after the channel changed kind.
+        let other_kind = (self.0).0.channels_text.contains_key(&channel_id)
+            || (self.0).0.channels_voice.contains_key(&channel_id);
+
+        if let Some(mut guild_channels) = guild_id
+            .filter(|_| !other_kind)
+            .and_then(|id| (self.0).0.guild_channels.get_mut(&id))
+        {
+            guild_channels.remove(&channel_id);
+        }
+
         (self.0).0.channels_category.remove(&channel_id);
 
         future::ok(()).boxed()
@@ -71,10 +90,32 @@ impl Repository<CategoryChannelEntity, InMemoryBackend> for InMemoryCategoryChan
             return future::ok(()).boxed();
         }
 
-        self.0
-             .0
+        let channel_id = category_channel.id();
+        let guild_id = category_channel.guild_id;
+
+        let old = (self.0)
+            .0
             .channels_category
-            .insert(category_channel.id(), category_channel);
+            .insert(channel_id, category_channel);
+
+        if let Some(guild_id) = guild_id {
+            (self.0)
+                .0
+                .guild_channels
+                .entry(guild_id)
+                .or_default()
+                .insert(channel_id);
+        }
+
+        // If the channel moved out of a guild then it needs to be removed from
+        // the old guild's set of channels.
+        if let Some(old_guild_id) = old.and_then(|channel| channel.guild_id) {
+            if Some(old_guild_id) != guild_id {
+                if let Some(mut guild_channels) = (self.0).0.guild_channels.get_mut(&old_guild_id) {
+                    guild_channels.remove(&channel_id);
+                }
+            }
+        }
 
         future::ok(()).boxed()
     }
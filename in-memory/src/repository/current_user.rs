@@ -0,0 +1,230 @@
+use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        guild::GuildEntity,
+        user::{CurrentUserEntity, CurrentUserRepository},
+    },
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture, Repository,
+        UpsertEntityFuture,
+    },
+};
+use twilight_model::id::{GuildId, UserId};
+
+/// Repository to retrieve and work with the current user and their related
+/// entities.
+#[derive(Clone, Debug)]
+pub struct InMemoryCurrentUserRepository(pub(crate) InMemoryBackend);
+
+impl InMemoryCurrentUserRepository {
+    fn cached(&self) -> Option<CurrentUserEntity> {
+        (self.0)
+            .0
+            .current_user
+            .lock()
+            .expect("current user poisoned")
+            .clone()
+    }
+}
+
+impl Repository<CurrentUserEntity, InMemoryBackend> for InMemoryCurrentUserRepository {
+    fn backend(&self) -> InMemoryBackend {
+        self.0.clone()
+    }
+
+    fn get(&self, user_id: UserId) -> GetEntityFuture<'_, CurrentUserEntity, InMemoryBackendError> {
+        let user = self.cached().filter(|user| user.id == user_id);
+
+        future::ok(user).boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, CurrentUserEntity, InMemoryBackendError> {
+        let stream = stream::iter(self.cached().into_iter().map(Ok)).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn remove(&self, user_id: UserId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let mut current_user = (self.0)
+            .0
+            .current_user
+            .lock()
+            .expect("current user poisoned");
+
+        if current_user.as_ref().is_some_and(|user| user.id == user_id) {
+            current_user.take();
+        }
+
+        future::ok(()).boxed()
+    }
+
+    fn upsert(&self, entity: CurrentUserEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if !(self.0)
+            .0
+            .config
+            .entity_types()
+            .contains(EntityType::USER_CURRENT)
+        {
+            return future::ok(()).boxed();
+        }
+
+        (self.0)
+            .0
+            .current_user
+            .lock()
+            .expect("current user poisoned")
+            .replace(entity);
+
+        future::ok(()).boxed()
+    }
+}
+
+impl CurrentUserRepository<InMemoryBackend> for InMemoryCurrentUserRepository {
+    fn current(&self) -> GetEntityFuture<'_, CurrentUserEntity, InMemoryBackendError> {
+        future::ok(self.cached()).boxed()
+    }
+
+    fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, InMemoryBackendError> {
+        let ids = (self.0)
+            .0
+            .guilds
+            .iter()
+            .map(|r| Ok(*r.key()))
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(ids).boxed()).boxed()
+    }
+
+    fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, InMemoryBackendError> {
+        let guilds = (self.0)
+            .0
+            .guilds
+            .iter()
+            .map(|r| Ok(r.value().clone()))
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(guilds).boxed()).boxed()
+    }
+}
+
+impl InMemoryCurrentUserRepository {
+    /// Retrieve the current user.
+    ///
+    /// Returns `None` if the current user hasn't been cached yet, such as
+    /// before the first `Ready` event.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(user) = cache.current_user.current().await? {
+    ///     println!("logged in as {}#{}", user.name, user.discriminator);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn current(&self) -> GetEntityFuture<'_, CurrentUserEntity, InMemoryBackendError> {
+        CurrentUserRepository::current(self)
+    }
+
+    /// Retrieve a stream of the IDs of guilds the current user is in.
+    pub fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, InMemoryBackendError> {
+        CurrentUserRepository::guild_ids(self)
+    }
+
+    /// Retrieve a stream of the guilds the current user is in.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut guilds = cache.current_user.guilds().await?;
+    ///
+    /// while let Some(guild) = guilds.try_next().await? {
+    ///     println!("in guild {}", guild.name);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, InMemoryBackendError> {
+        CurrentUserRepository::guilds(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CurrentUserEntity, CurrentUserRepository, InMemoryBackend, InMemoryCurrentUserRepository,
+        Repository,
+    };
+    use crate::config::EntityType;
+    use rarity_cache::Backend;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+    use twilight_model::id::UserId;
+
+    assert_impl_all!(
+        InMemoryCurrentUserRepository:
+        CurrentUserRepository<InMemoryBackend>,
+        Clone,
+        Debug,
+        Repository<CurrentUserEntity, InMemoryBackend>,
+        Send,
+        Sync,
+    );
+    assert_obj_safe!(InMemoryCurrentUserRepository);
+
+    fn current_user(id: u64) -> CurrentUserEntity {
+        CurrentUserEntity {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            id: UserId(id),
+            mfa_enabled: false,
+            name: "bot".to_owned(),
+            verified: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upsert_replaces() -> Result<(), Box<dyn std::error::Error>> {
+        let repo = InMemoryBackend::new().current_user();
+        repo.upsert(current_user(1)).await?;
+        repo.upsert(current_user(2)).await?;
+
+        assert!(repo.get(UserId(1)).await?.is_none());
+        assert_eq!(Some(UserId(2)), repo.current().await?.map(|user| user.id));
+
+        // removing another user's ID leaves the current user in place
+        repo.remove(UserId(1)).await?;
+        assert!(repo.current().await?.is_some());
+        repo.remove(UserId(2)).await?;
+        assert!(repo.current().await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_entity_type_disabled() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = InMemoryBackend::builder();
+        builder.entity_types(EntityType::all() - EntityType::USER_CURRENT);
+        let repo = builder.build().current_user();
+        repo.upsert(current_user(1)).await?;
+
+        assert!(repo.current().await?.is_none());
+
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ use rarity_cache::{
         GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
     },
 };
-use twilight_model::id::EmojiId;
+use twilight_model::{guild::Emoji, id::EmojiId};
 
 /// Repository to retrieve and work with emojis and their related entities.
 #[derive(Clone, Debug)]
@@ -35,6 +35,13 @@ impl Repository<EmojiEntity, InMemoryBackend> for InMemoryEmojiRepository {
     }
 
     fn remove(&self, emoji_id: EmojiId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let guild_id = (self.0).0.emojis.get(&emoji_id).map(|emoji| emoji.guild_id);
+
+        if let Some(mut guild_emojis) = guild_id.and_then(|id| (self.0).0.guild_emojis.get_mut(&id))
+        {
+            guild_emojis.remove(&emoji_id);
+        }
+
         (self.0).0.emojis.remove(&emoji_id);
 
         future::ok(()).boxed()
@@ -45,7 +52,17 @@ impl Repository<EmojiEntity, InMemoryBackend> for InMemoryEmojiRepository {
             return future::ok(()).boxed();
         }
 
-        (self.0).0.emojis.insert(entity.id(), entity);
+        let guild_id = entity.guild_id;
+        let emoji_id = entity.id();
+
+        (self.0).0.emojis.insert(emoji_id, entity);
+
+        (self.0)
+            .0
+            .guild_emojis
+            .entry(guild_id)
+            .or_default()
+            .insert(emoji_id);
 
         future::ok(()).boxed()
     }
@@ -117,6 +134,26 @@ impl InMemoryEmojiRepository {
         EmojiRepository::guild(self, emoji_id)
     }
 
+    /// Retrieve an emoji along with its creator as a twilight `Emoji`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::EmojiId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(emoji) = cache.emojis.hydrate(EmojiId(123456)).await? {
+    ///     println!("the emoji's name is {}", emoji.name);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn hydrate(&self, emoji_id: EmojiId) -> GetEntityFuture<'_, Emoji, InMemoryBackendError> {
+        EmojiRepository::hydrate(self, emoji_id)
+    }
+
     pub fn roles(
         &self,
         emoji_id: EmojiId,
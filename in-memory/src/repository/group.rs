@@ -7,10 +7,11 @@ use rarity_cache::{
     entity::{
         channel::{GroupEntity, GroupRepository, MessageEntity},
         user::UserEntity,
-        Entity,
+        Entity, EntityField,
     },
     repository::{
-        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpdateEntityFuture,
+        UpsertEntityFuture,
     },
 };
 use twilight_model::id::ChannelId;
@@ -40,6 +41,21 @@ impl Repository<GroupEntity, InMemoryBackend> for InMemoryGroupRepository {
         future::ok(()).boxed()
     }
 
+    fn update_field<F: EntityField<GroupEntity>>(
+        &self,
+        group_id: ChannelId,
+        field: F,
+    ) -> UpdateEntityFuture<'_, InMemoryBackendError> {
+        let updated = (self.0)
+            .0
+            .groups
+            .get_mut(&group_id)
+            .map(|mut group| field.apply(&mut group))
+            .is_some();
+
+        future::ok(updated).boxed()
+    }
+
     fn upsert(&self, entity: GroupEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
         if !self
             .0
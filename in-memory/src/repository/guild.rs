@@ -1,3 +1,4 @@
+use super::{InMemoryTextChannelRepository, InMemoryWebhookRepository};
 use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
 use futures_util::{
     future::{self, FutureExt},
@@ -17,7 +18,10 @@ use rarity_cache::{
         UpsertEntityFuture,
     },
 };
-use twilight_model::id::{ChannelId, EmojiId, GuildId, RoleId, UserId};
+use twilight_model::{
+    guild::Guild,
+    id::{ChannelId, EmojiId, GuildId, RoleId, UserId},
+};
 
 /// Repository to retrieve and work with guilds and their related entities.
 #[derive(Clone, Debug)]
@@ -56,12 +60,58 @@ impl Repository<GuildEntity, InMemoryBackend> for InMemoryGuildRepository {
 }
 
 impl InMemoryGuildRepository {
+    /// Retrieve a guild along with its related entities as a twilight
+    /// `Guild`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(guild) = cache.guilds.hydrate(GuildId(123456)).await? {
+    ///     println!("{} has {} roles", guild.name, guild.roles.len());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn hydrate(&self, guild_id: GuildId) -> GetEntityFuture<'_, Guild, InMemoryBackendError> {
+        GuildRepository::hydrate(self, guild_id)
+    }
+
     pub fn members(
         &self,
         guild_id: GuildId,
     ) -> ListEntitiesFuture<'_, MemberEntity, InMemoryBackendError> {
         GuildRepository::members(self, guild_id)
     }
+
+    /// Retrieve a stream of the IDs of guilds that are unavailable.
+    ///
+    /// # Examples
+    ///
+    /// Check whether all of the guilds in the `Ready` event have been
+    /// received:
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let pending = cache.guilds.unavailable_ids().await?.count().await;
+    ///
+    /// if pending == 0 {
+    ///     println!("all guilds are available");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn unavailable_ids(&self) -> ListEntityIdsFuture<'_, GuildId, InMemoryBackendError> {
+        GuildRepository::unavailable_ids(self)
+    }
 }
 
 impl GuildRepository<InMemoryBackend> for InMemoryGuildRepository {
@@ -230,6 +280,77 @@ impl GuildRepository<InMemoryBackend> for InMemoryGuildRepository {
         future::ok(stream).boxed()
     }
 
+    fn remove_cascade(&self, guild_id: GuildId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let backend = &(self.0).0;
+
+        if let Some((_, user_ids)) = backend.guild_bans.remove(&guild_id) {
+            for user_id in user_ids {
+                backend.bans.remove(&(guild_id, user_id));
+            }
+        }
+
+        if let Some((_, channel_ids)) = backend.guild_channels.remove(&guild_id) {
+            let text_channels = InMemoryTextChannelRepository(self.0.clone());
+            let webhooks = InMemoryWebhookRepository(self.0.clone());
+
+            for channel_id in channel_ids {
+                text_channels.remove_messages(channel_id);
+                webhooks.remove_channel_webhooks(channel_id);
+                backend.channels_category.remove(&channel_id);
+                backend.channels_text.remove(&channel_id);
+                backend.channels_voice.remove(&channel_id);
+            }
+        }
+
+        if let Some((_, emoji_ids)) = backend.guild_emojis.remove(&guild_id) {
+            for emoji_id in emoji_ids {
+                backend.emojis.remove(&emoji_id);
+            }
+        }
+
+        if let Some((_, codes)) = backend.guild_invites.remove(&guild_id) {
+            for code in codes {
+                if let Some((_, invite)) = backend.invites.remove(&code) {
+                    if let Some(mut channel_invites) =
+                        backend.channel_invites.get_mut(&invite.channel_id)
+                    {
+                        channel_invites.remove(&code);
+                    }
+                }
+            }
+        }
+
+        if let Some((_, user_ids)) = backend.guild_members.remove(&guild_id) {
+            for user_id in user_ids {
+                backend.members.remove(&(guild_id, user_id));
+
+                if let Some(mut user_guilds) = backend.user_guilds.get_mut(&user_id) {
+                    user_guilds.retain(|id| *id != guild_id);
+                }
+            }
+        }
+
+        if let Some((_, user_ids)) = backend.guild_presences.remove(&guild_id) {
+            for user_id in user_ids {
+                backend.presences.remove(&(guild_id, user_id));
+            }
+        }
+
+        if let Some((_, role_ids)) = backend.guild_roles.remove(&guild_id) {
+            for role_id in role_ids {
+                backend.roles.remove(&role_id);
+            }
+        }
+
+        if let Some((_, user_ids)) = backend.guild_voice_states.remove(&guild_id) {
+            for user_id in user_ids {
+                backend.voice_states.remove(&(guild_id, user_id));
+            }
+        }
+
+        self.remove(guild_id)
+    }
+
     fn role_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, RoleId, InMemoryBackendError> {
         let stream = (self.0).0.guild_roles.get(&guild_id).map_or_else(
             || stream::empty().boxed(),
@@ -285,6 +406,18 @@ impl GuildRepository<InMemoryBackend> for InMemoryGuildRepository {
         future::ok(guild).boxed()
     }
 
+    fn unavailable_ids(&self) -> ListEntityIdsFuture<'_, GuildId, InMemoryBackendError> {
+        let ids = (self.0)
+            .0
+            .guilds
+            .iter()
+            .filter(|r| r.value().unavailable)
+            .map(|r| Ok(*r.key()))
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(ids).boxed()).boxed()
+    }
+
     fn voice_state_ids(
         &self,
         guild_id: GuildId,
@@ -354,8 +487,24 @@ mod tests {
     use super::{
         GuildEntity, GuildRepository, InMemoryBackend, InMemoryGuildRepository, Repository,
     };
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::{
+        entity::{
+            channel::TextChannelEntity,
+            gateway::PresenceEntity,
+            guild::{EmojiEntity, MemberEntity, RoleEntity},
+            voice::VoiceStateEntity,
+        },
+        Backend,
+    };
     use static_assertions::{assert_impl_all, assert_obj_safe};
     use std::fmt::Debug;
+    use twilight_model::{
+        channel::ChannelType,
+        gateway::presence::{ClientStatus, Status},
+        guild::Permissions,
+        id::{ChannelId, EmojiId, GuildId, RoleId, UserId},
+    };
 
     assert_impl_all!(
         InMemoryGuildRepository:
@@ -367,4 +516,296 @@ mod tests {
         Sync,
     );
     assert_obj_safe!(InMemoryGuildRepository);
+
+    fn member(guild_id: u64, user_id: u64) -> MemberEntity {
+        MemberEntity {
+            deaf: false,
+            guild_id: GuildId(guild_id),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids: Vec::new(),
+            user_id: UserId(user_id),
+        }
+    }
+
+    fn role(guild_id: u64, role_id: u64) -> RoleEntity {
+        RoleEntity {
+            color: 0,
+            guild_id: GuildId(guild_id),
+            hoist: false,
+            id: RoleId(role_id),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions: Permissions::empty(),
+            position: 0,
+        }
+    }
+
+    fn text_channel(guild_id: Option<u64>, channel_id: u64) -> TextChannelEntity {
+        TextChannelEntity {
+            guild_id: guild_id.map(GuildId),
+            id: ChannelId(channel_id),
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "channel".to_owned(),
+            nsfw: false,
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        }
+    }
+
+    fn emoji(guild_id: u64, emoji_id: u64) -> EmojiEntity {
+        EmojiEntity {
+            animated: false,
+            available: true,
+            guild_id: GuildId(guild_id),
+            id: EmojiId(emoji_id),
+            managed: false,
+            name: "emoji".to_owned(),
+            require_colons: true,
+            role_ids: Vec::new(),
+            user_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_member_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+        let members = backend.members();
+
+        members.upsert(member(1, 2)).await?;
+        members.upsert(member(1, 3)).await?;
+        members.upsert(member(4, 2)).await?;
+
+        let mut ids = guilds
+            .member_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        ids.sort();
+        assert_eq!(vec![UserId(2), UserId(3)], ids);
+
+        members.remove((GuildId(1), UserId(2))).await?;
+        let found = guilds
+            .members(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![member(1, 3)], found);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_role_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+        let roles = backend.roles();
+
+        roles.upsert(role(1, 2)).await?;
+        roles.upsert(role(3, 4)).await?;
+
+        let found = guilds
+            .roles(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![role(1, 2)], found);
+
+        roles.remove(RoleId(2)).await?;
+        let ids = guilds
+            .role_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(ids.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_emoji_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+        let emojis = backend.emojis();
+
+        emojis.upsert(emoji(1, 2)).await?;
+
+        let found = guilds
+            .emojis(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![emoji(1, 2)], found);
+
+        emojis.remove(EmojiId(2)).await?;
+        let ids = guilds
+            .emoji_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(ids.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_channel_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+        let channels = backend.text_channels();
+
+        channels.upsert(text_channel(Some(1), 2)).await?;
+        channels.upsert(text_channel(None, 3)).await?;
+
+        let ids = guilds
+            .channel_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![ChannelId(2)], ids);
+
+        // Moving the channel to another guild removes it from the first.
+        channels.upsert(text_channel(Some(4), 2)).await?;
+        let ids = guilds
+            .channel_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(ids.is_empty());
+        let ids = guilds
+            .channel_ids(GuildId(4))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![ChannelId(2)], ids);
+
+        channels.remove(ChannelId(2)).await?;
+        let found = guilds
+            .channels(GuildId(4))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(found.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_presence_and_voice_state_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+
+        backend
+            .presences()
+            .upsert(PresenceEntity {
+                activities: Vec::new(),
+                client_status: ClientStatus {
+                    desktop: None,
+                    mobile: None,
+                    web: Some(Status::Online),
+                },
+                game: None,
+                guild_id: GuildId(1),
+                nick: None,
+                status: Status::Online,
+                user_id: UserId(2),
+            })
+            .await?;
+        backend
+            .voice_states()
+            .upsert(VoiceStateEntity {
+                channel_id: Some(ChannelId(3)),
+                deaf: false,
+                guild_id: GuildId(1),
+                mute: false,
+                self_deaf: false,
+                self_mute: false,
+                self_stream: false,
+                session_id: "session".to_owned(),
+                suppress: false,
+                user_id: UserId(2),
+            })
+            .await?;
+
+        let ids = guilds
+            .presence_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![UserId(2)], ids);
+        let ids = guilds
+            .voice_state_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![UserId(2)], ids);
+
+        backend.presences().remove((GuildId(1), UserId(2))).await?;
+        backend
+            .voice_states()
+            .remove((GuildId(1), UserId(2)))
+            .await?;
+
+        let found = guilds
+            .presences(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(found.is_empty());
+        let found = guilds
+            .voice_states(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(found.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_cascade() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let guilds = backend.guilds();
+
+        backend.members().upsert(member(1, 2)).await?;
+        backend.roles().upsert(role(1, 3)).await?;
+        backend.emojis().upsert(emoji(1, 4)).await?;
+        backend
+            .text_channels()
+            .upsert(text_channel(Some(1), 5))
+            .await?;
+        backend.roles().upsert(role(6, 7)).await?;
+
+        guilds.remove_cascade(GuildId(1)).await?;
+
+        assert!(backend
+            .members()
+            .get((GuildId(1), UserId(2)))
+            .await?
+            .is_none());
+        assert!(backend.roles().get(RoleId(3)).await?.is_none());
+        assert!(backend.emojis().get(EmojiId(4)).await?.is_none());
+        assert!(backend.text_channels().get(ChannelId(5)).await?.is_none());
+        assert!(backend.roles().get(RoleId(7)).await?.is_some());
+
+        let ids = backend
+            .users()
+            .guild_ids(UserId(2))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(ids.is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,269 @@
+use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        guild::GuildEntity,
+        invite::{InviteEntity, InviteRepository},
+        user::UserEntity,
+        Entity,
+    },
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+    },
+};
+use std::collections::HashSet;
+use twilight_model::id::{ChannelId, GuildId};
+
+/// Repository to retrieve and work with invites and their related entities.
+#[derive(Clone, Debug)]
+pub struct InMemoryInviteRepository(pub(crate) InMemoryBackend);
+
+impl InMemoryInviteRepository {
+    fn invites(
+        &self,
+        codes: Option<HashSet<String>>,
+    ) -> Vec<Result<InviteEntity, InMemoryBackendError>> {
+        codes
+            .into_iter()
+            .flatten()
+            .filter_map(|code| (self.0).0.invites.get(&code).map(|r| Ok(r.value().clone())))
+            .collect()
+    }
+}
+
+impl Repository<InviteEntity, InMemoryBackend> for InMemoryInviteRepository {
+    fn backend(&self) -> InMemoryBackend {
+        self.0.clone()
+    }
+
+    fn get(&self, code: String) -> GetEntityFuture<'_, InviteEntity, InMemoryBackendError> {
+        future::ok((self.0).0.invites.get(&code).map(|r| r.value().clone())).boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, InviteEntity, InMemoryBackendError> {
+        let stream = stream::iter((self.0).0.invites.iter().map(|r| Ok(r.value().clone()))).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn remove(&self, code: String) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        if let Some((_, invite)) = (self.0).0.invites.remove(&code) {
+            if let Some(mut channel_invites) =
+                (self.0).0.channel_invites.get_mut(&invite.channel_id)
+            {
+                channel_invites.remove(&code);
+            }
+
+            if let Some(mut guild_invites) = (self.0).0.guild_invites.get_mut(&invite.guild_id) {
+                guild_invites.remove(&code);
+            }
+        }
+
+        future::ok(()).boxed()
+    }
+
+    fn upsert(&self, entity: InviteEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if !(self.0)
+            .0
+            .config
+            .entity_types()
+            .contains(EntityType::INVITE)
+        {
+            return future::ok(()).boxed();
+        }
+
+        (self.0)
+            .0
+            .channel_invites
+            .entry(entity.channel_id)
+            .or_default()
+            .insert(entity.code.clone());
+
+        (self.0)
+            .0
+            .guild_invites
+            .entry(entity.guild_id)
+            .or_default()
+            .insert(entity.code.clone());
+
+        (self.0).0.invites.insert(entity.id(), entity);
+
+        future::ok(()).boxed()
+    }
+}
+
+impl InviteRepository<InMemoryBackend> for InMemoryInviteRepository {
+    fn channel_invites(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, InMemoryBackendError> {
+        let codes = (self.0)
+            .0
+            .channel_invites
+            .get(&channel_id)
+            .map(|r| r.value().clone());
+        let stream = stream::iter(self.invites(codes)).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn guild(&self, code: String) -> GetEntityFuture<'_, GuildEntity, InMemoryBackendError> {
+        let guild = (self.0)
+            .0
+            .invites
+            .get(&code)
+            .and_then(|invite| (self.0).0.guilds.get(&invite.guild_id))
+            .map(|r| r.value().clone());
+
+        future::ok(guild).boxed()
+    }
+
+    fn guild_invites(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, InMemoryBackendError> {
+        let codes = (self.0)
+            .0
+            .guild_invites
+            .get(&guild_id)
+            .map(|r| r.value().clone());
+        let stream = stream::iter(self.invites(codes)).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn inviter(&self, code: String) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        let user = (self.0)
+            .0
+            .invites
+            .get(&code)
+            .and_then(|invite| invite.inviter_id)
+            .and_then(|id| (self.0).0.users.get(&id))
+            .map(|r| r.value().clone());
+
+        future::ok(user).boxed()
+    }
+}
+
+impl InMemoryInviteRepository {
+    /// Retrieve a stream of the invites to a channel.
+    ///
+    /// Expired invites are included.
+    pub fn channel_invites(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, InMemoryBackendError> {
+        InviteRepository::channel_invites(self, channel_id)
+    }
+
+    /// Retrieve the guild associated with an invite.
+    pub fn guild(&self, code: String) -> GetEntityFuture<'_, GuildEntity, InMemoryBackendError> {
+        InviteRepository::guild(self, code)
+    }
+
+    /// Retrieve a stream of the invites within a guild.
+    ///
+    /// Expired invites are included.
+    ///
+    /// # Examples
+    ///
+    /// Print the use counts of a guild's unexpired invites:
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut invites = cache.invites.guild_invites(GuildId(123456)).await?;
+    ///
+    /// while let Some(invite) = invites.try_next().await? {
+    ///     if !invite.is_expired() {
+    ///         println!("{} has been used {} times", invite.code, invite.uses);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn guild_invites(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, InMemoryBackendError> {
+        InviteRepository::guild_invites(self, guild_id)
+    }
+
+    /// Retrieve the user who created an invite.
+    pub fn inviter(&self, code: String) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        InviteRepository::inviter(self, code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InMemoryBackend, InMemoryInviteRepository, InviteEntity, InviteRepository, Repository,
+    };
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::Backend;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+    use twilight_model::id::{ChannelId, GuildId};
+
+    assert_impl_all!(
+        InMemoryInviteRepository:
+        InviteRepository<InMemoryBackend>,
+        Clone,
+        Debug,
+        Repository<InviteEntity, InMemoryBackend>,
+        Send,
+        Sync,
+    );
+    assert_obj_safe!(InMemoryInviteRepository);
+
+    fn invite(code: &str, channel_id: u64) -> InviteEntity {
+        InviteEntity {
+            channel_id: ChannelId(channel_id),
+            code: code.to_owned(),
+            created_at: "2020-05-30T20:52:43.457000+00:00".to_owned(),
+            guild_id: GuildId(1),
+            inviter_id: None,
+            max_age: 0,
+            max_uses: 0,
+            target_user_type: None,
+            temporary: false,
+            uses: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_invites() -> Result<(), Box<dyn std::error::Error>> {
+        let repo = InMemoryBackend::new().invites();
+        repo.upsert(invite("a", 2)).await?;
+        repo.upsert(invite("b", 2)).await?;
+        repo.upsert(invite("c", 3)).await?;
+        repo.remove("b".to_owned()).await?;
+
+        let codes = repo
+            .channel_invites(ChannelId(2))
+            .await?
+            .map_ok(|invite| invite.code)
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec!["a".to_owned()], codes);
+
+        let mut codes = repo
+            .guild_invites(GuildId(1))
+            .await?
+            .map_ok(|invite| invite.code)
+            .try_collect::<Vec<_>>()
+            .await?;
+        codes.sort();
+        assert_eq!(vec!["a".to_owned(), "c".to_owned()], codes);
+
+        Ok(())
+    }
+}
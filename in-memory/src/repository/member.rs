@@ -6,13 +6,18 @@ use futures_util::{
 use rarity_cache::{
     entity::{
         guild::{MemberEntity, MemberRepository, RoleEntity},
-        Entity,
+        Entity, EntityField,
     },
+    permission,
     repository::{
-        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpdateEntityFuture,
+        UpsertEntityFuture,
     },
 };
-use twilight_model::id::{GuildId, UserId};
+use twilight_model::{
+    guild::Member,
+    id::{GuildId, UserId},
+};
 
 /// Repository to retrieve and work with members and their related entities.
 #[derive(Clone, Debug)]
@@ -37,11 +42,36 @@ impl Repository<MemberEntity, InMemoryBackend> for InMemoryMemberRepository {
     }
 
     fn remove(&self, id: (GuildId, UserId)) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let (guild_id, user_id) = id;
+
+        if let Some(mut guild_members) = (self.0).0.guild_members.get_mut(&guild_id) {
+            guild_members.remove(&user_id);
+        }
+
+        if let Some(mut user_guilds) = (self.0).0.user_guilds.get_mut(&user_id) {
+            user_guilds.retain(|id| *id != guild_id);
+        }
+
         (self.0).0.members.remove(&id);
 
         future::ok(()).boxed()
     }
 
+    fn update_field<F: EntityField<MemberEntity>>(
+        &self,
+        member_id: (GuildId, UserId),
+        field: F,
+    ) -> UpdateEntityFuture<'_, InMemoryBackendError> {
+        let updated = (self.0)
+            .0
+            .members
+            .get_mut(&member_id)
+            .map(|mut member| field.apply(&mut member))
+            .is_some();
+
+        future::ok(updated).boxed()
+    }
+
     fn upsert(&self, entity: MemberEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
         if !(self.0)
             .0
@@ -52,13 +82,83 @@ impl Repository<MemberEntity, InMemoryBackend> for InMemoryMemberRepository {
             return future::ok(()).boxed();
         }
 
-        (self.0).0.members.insert(entity.id(), entity);
+        let (guild_id, user_id) = entity.id();
+
+        (self.0).0.members.insert((guild_id, user_id), entity);
+
+        (self.0)
+            .0
+            .guild_members
+            .entry(guild_id)
+            .or_default()
+            .insert(user_id);
+
+        let mut user_guilds = (self.0).0.user_guilds.entry(user_id).or_default();
+
+        if !user_guilds.contains(&guild_id) {
+            user_guilds.push(guild_id);
+        }
 
         future::ok(()).boxed()
     }
 }
 
 impl MemberRepository<InMemoryBackend> for InMemoryMemberRepository {
+    fn can_manage(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        let Some(owner_id) = (self.0).0.guilds.get(&guild_id).map(|guild| guild.owner_id) else {
+            return future::ok(None).boxed();
+        };
+
+        let (Some(roles), Some(target_roles)) = (
+            self.member_roles(guild_id, user_id),
+            self.member_roles(guild_id, target_user_id),
+        ) else {
+            return future::ok(None).boxed();
+        };
+
+        let can_manage = if user_id == target_user_id || owner_id == target_user_id {
+            false
+        } else if owner_id == user_id {
+            true
+        } else {
+            permission::is_above(
+                permission::highest_role(&roles),
+                permission::highest_role(&target_roles),
+            )
+        };
+
+        future::ok(Some(can_manage)).boxed()
+    }
+
+    fn color(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, u32, InMemoryBackendError> {
+        let color = self
+            .member_roles(guild_id, user_id)
+            .and_then(|roles| permission::color(&roles));
+
+        future::ok(color).boxed()
+    }
+
+    fn highest_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, RoleEntity, InMemoryBackendError> {
+        let role = self
+            .member_roles(guild_id, user_id)
+            .and_then(|roles| permission::highest_role(&roles).cloned());
+
+        future::ok(role).boxed()
+    }
+
     fn hoisted_role(
         &self,
         guild_id: GuildId,
@@ -96,6 +196,80 @@ impl MemberRepository<InMemoryBackend> for InMemoryMemberRepository {
 }
 
 impl InMemoryMemberRepository {
+    /// Whether a member can manage another member based on the role
+    /// hierarchy.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{GuildId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if cache.members.can_manage(GuildId(1), UserId(2), UserId(3)).await? == Some(true) {
+    ///     println!("user 2 is above user 3 in the role hierarchy");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn can_manage(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        MemberRepository::can_manage(self, guild_id, user_id, target_user_id)
+    }
+
+    /// Retrieve the colour that a member is displayed with.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{GuildId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(color) = cache.members.color(GuildId(1), UserId(2)).await? {
+    ///     println!("the member's colour is #{:06x}", color);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn color(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, u32, InMemoryBackendError> {
+        MemberRepository::color(self, guild_id, user_id)
+    }
+
+    /// Retrieve the highest role of a member in the role hierarchy.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{GuildId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(role) = cache.members.highest_role(GuildId(1), UserId(2)).await? {
+    ///     println!("the highest role's name is {}", role.name);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn highest_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, RoleEntity, InMemoryBackendError> {
+        MemberRepository::highest_role(self, guild_id, user_id)
+    }
+
     /// Retrieve the hoisted role of a member.
     ///
     /// # Examples
@@ -120,6 +294,30 @@ impl InMemoryMemberRepository {
         MemberRepository::hoisted_role(self, guild_id, user_id)
     }
 
+    /// Retrieve a member along with its user as a twilight `Member`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{GuildId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(member) = cache.members.hydrate(GuildId(1), UserId(2)).await? {
+    ///     println!("the member's username is {}", member.user.name);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn hydrate(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, Member, InMemoryBackendError> {
+        MemberRepository::hydrate(self, guild_id, user_id)
+    }
+
     pub fn roles(
         &self,
         guild_id: GuildId,
@@ -127,6 +325,20 @@ impl InMemoryMemberRepository {
     ) -> ListEntitiesFuture<'_, RoleEntity, InMemoryBackendError> {
         MemberRepository::roles(self, guild_id, user_id)
     }
+
+    /// Clone the cached roles of a member, or return `None` if the member
+    /// isn't cached.
+    fn member_roles(&self, guild_id: GuildId, user_id: UserId) -> Option<Vec<RoleEntity>> {
+        let member = (self.0).0.members.get(&(guild_id, user_id))?;
+
+        let roles = member
+            .role_ids
+            .iter()
+            .filter_map(|id| (self.0).0.roles.get(id).map(|r| r.value().clone()))
+            .collect();
+
+        Some(roles)
+    }
 }
 
 #[cfg(test)]
@@ -7,17 +7,24 @@ use rarity_cache::{
     entity::{
         channel::{
             AttachmentEntity, ChannelEntity, GuildChannelEntity, MessageEntity, MessageRepository,
-            TextChannelEntity,
+            TextChannelEntity, WebhookEntity,
         },
         guild::{GuildEntity, RoleEntity},
         user::UserEntity,
-        Entity,
+        Entity, EntityField,
     },
     repository::{
-        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntitiesFuture,
+        RemoveEntityFuture, Repository, UpdateEntityFuture, UpsertEntityFuture,
     },
 };
-use twilight_model::id::{ChannelId, MessageId};
+use twilight_model::{
+    channel::{
+        message::{Message, MessageReaction},
+        ReactionType,
+    },
+    id::{ChannelId, MessageId, UserId},
+};
 
 /// Repository to retrieve and work with messages and their related entities.
 #[derive(Clone, Debug)]
@@ -57,6 +64,19 @@ impl InMemoryMessageRepository {
             (self.0).0.messages.remove(&oldest_message_id);
         }
     }
+
+    /// Remove the attachments of a message, leaving the message itself in
+    /// the cache.
+    pub(super) fn remove_attachments(&self, message_id: MessageId) {
+        let attachment_ids = match (self.0).0.messages.get(&message_id) {
+            Some(message) => message.attachments.clone(),
+            None => return,
+        };
+
+        for attachment_id in attachment_ids {
+            (self.0).0.attachments.remove(&attachment_id);
+        }
+    }
 }
 
 impl Repository<MessageEntity, InMemoryBackend> for InMemoryMessageRepository {
@@ -97,6 +117,21 @@ impl Repository<MessageEntity, InMemoryBackend> for InMemoryMessageRepository {
         future::ok(()).boxed()
     }
 
+    fn update_field<F: EntityField<MessageEntity>>(
+        &self,
+        message_id: MessageId,
+        field: F,
+    ) -> UpdateEntityFuture<'_, InMemoryBackendError> {
+        let updated = (self.0)
+            .0
+            .messages
+            .get_mut(&message_id)
+            .map(|mut message| field.apply(&mut message))
+            .is_some();
+
+        future::ok(updated).boxed()
+    }
+
     fn upsert(&self, entity: MessageEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
         if !(self.0)
             .0
@@ -257,6 +292,68 @@ impl MessageRepository<InMemoryBackend> for InMemoryMessageRepository {
 
         future::ok(stream).boxed()
     }
+
+    fn reactions(
+        &self,
+        message_id: MessageId,
+    ) -> ListEntitiesFuture<'_, MessageReaction, InMemoryBackendError> {
+        let reactions = match (self.0).0.messages.get(&message_id) {
+            Some(message) => message.reactions.clone(),
+            None => return future::ok(stream::empty().boxed()).boxed(),
+        };
+
+        future::ok(stream::iter(reactions.into_iter().map(Ok)).boxed()).boxed()
+    }
+
+    fn reaction_user_ids(
+        &self,
+        message_id: MessageId,
+        emoji: ReactionType,
+    ) -> ListEntityIdsFuture<'_, UserId, InMemoryBackendError> {
+        let user_ids = match (self.0).0.messages.get(&message_id) {
+            Some(message) => message.reaction_user_ids(&emoji).to_vec(),
+            None => return future::ok(stream::empty().boxed()).boxed(),
+        };
+
+        future::ok(stream::iter(user_ids.into_iter().map(Ok)).boxed()).boxed()
+    }
+
+    fn remove_cascade(
+        &self,
+        message_id: MessageId,
+    ) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        self.remove_attachments(message_id);
+
+        self.remove(message_id)
+    }
+
+    fn remove_cascade_bulk<T: Iterator<Item = MessageId>>(
+        &self,
+        message_ids: T,
+    ) -> RemoveEntitiesFuture<'_, InMemoryBackendError> {
+        let message_ids = message_ids.collect::<Vec<_>>();
+
+        for message_id in &message_ids {
+            self.remove_attachments(*message_id);
+        }
+
+        self.remove_bulk(message_ids.into_iter())
+    }
+
+    fn webhook(
+        &self,
+        message_id: MessageId,
+    ) -> GetEntityFuture<'_, WebhookEntity, InMemoryBackendError> {
+        let webhook = (self.0)
+            .0
+            .messages
+            .get(&message_id)
+            .and_then(|message| message.webhook_id)
+            .and_then(|id| (self.0).0.webhooks.get(&id))
+            .map(|r| r.value().clone());
+
+        future::ok(webhook).boxed()
+    }
 }
 
 impl InMemoryMessageRepository {
@@ -338,6 +435,30 @@ impl InMemoryMessageRepository {
         MessageRepository::guild(self, message_id)
     }
 
+    /// Retrieve a message along with its author and attachments as a twilight
+    /// `Message`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::MessageId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if let Some(message) = cache.messages.hydrate(MessageId(123456)).await? {
+    ///     println!("{} said {}", message.author.name, message.content);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn hydrate(
+        &self,
+        message_id: MessageId,
+    ) -> GetEntityFuture<'_, Message, InMemoryBackendError> {
+        MessageRepository::hydrate(self, message_id)
+    }
+
     pub fn mention_channels(
         &self,
         message_id: MessageId,
@@ -358,6 +479,77 @@ impl InMemoryMessageRepository {
     ) -> ListEntitiesFuture<'_, UserEntity, InMemoryBackendError> {
         MessageRepository::mentions(self, message_id)
     }
+
+    /// Retrieve a stream of the reactions on a message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::MessageId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut reactions = cache.messages.reactions(MessageId(123456)).await?;
+    ///
+    /// while let Some(reaction) = reactions.try_next().await? {
+    ///     println!("{:?}: {}", reaction.emoji, reaction.count);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn reactions(
+        &self,
+        message_id: MessageId,
+    ) -> ListEntitiesFuture<'_, MessageReaction, InMemoryBackendError> {
+        MessageRepository::reactions(self, message_id)
+    }
+
+    /// Retrieve a stream of the IDs of users that reacted to a message with an
+    /// emoji while the message was cached.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::{channel::ReactionType, id::MessageId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let emoji = ReactionType::Unicode {
+    ///     name: "👍".to_owned(),
+    /// };
+    /// let user_ids = cache
+    ///     .messages
+    ///     .reaction_user_ids(MessageId(123456), emoji)
+    ///     .await?
+    ///     .try_collect::<Vec<_>>()
+    ///     .await?;
+    ///
+    /// println!("{} users agree", user_ids.len());
+    /// # Ok(()) }
+    /// ```
+    pub fn reaction_user_ids(
+        &self,
+        message_id: MessageId,
+        emoji: ReactionType,
+    ) -> ListEntityIdsFuture<'_, UserId, InMemoryBackendError> {
+        MessageRepository::reaction_user_ids(self, message_id, emoji)
+    }
+
+    /// Retrieve the webhook that sent a message.
+    ///
+    /// Returns `None` if the message wasn't sent by a webhook or the webhook
+    /// isn't in the cache.
+    pub fn webhook(
+        &self,
+        message_id: MessageId,
+    ) -> GetEntityFuture<'_, WebhookEntity, InMemoryBackendError> {
+        MessageRepository::webhook(self, message_id)
+    }
 }
 
 #[cfg(test)]
@@ -1,26 +1,34 @@
 //! Repository implementations for working with the in memory cache.
 
 mod attachment;
+mod ban;
 mod category_channel;
+mod current_user;
 mod emoji;
 mod group;
 mod guild;
+mod invite;
 mod member;
 mod message;
 mod presence;
 mod private_channel;
 mod role;
 mod text_channel;
+mod typing;
 mod user;
 mod voice_channel;
 mod voice_state;
+mod webhook;
 
 pub use self::{
-    attachment::InMemoryAttachmentRepository, category_channel::InMemoryCategoryChannelRepository,
-    emoji::InMemoryEmojiRepository, group::InMemoryGroupRepository, guild::InMemoryGuildRepository,
-    member::InMemoryMemberRepository, message::InMemoryMessageRepository,
-    presence::InMemoryPresenceRepository, private_channel::InMemoryPrivateChannelRepository,
-    role::InMemoryRoleRepository, text_channel::InMemoryTextChannelRepository,
+    attachment::InMemoryAttachmentRepository, ban::InMemoryBanRepository,
+    category_channel::InMemoryCategoryChannelRepository,
+    current_user::InMemoryCurrentUserRepository, emoji::InMemoryEmojiRepository,
+    group::InMemoryGroupRepository, guild::InMemoryGuildRepository,
+    invite::InMemoryInviteRepository, member::InMemoryMemberRepository,
+    message::InMemoryMessageRepository, presence::InMemoryPresenceRepository,
+    private_channel::InMemoryPrivateChannelRepository, role::InMemoryRoleRepository,
+    text_channel::InMemoryTextChannelRepository, typing::InMemoryTypingRepository,
     user::InMemoryUserRepository, voice_channel::InMemoryVoiceChannelRepository,
-    voice_state::InMemoryVoiceStateRepository,
+    voice_state::InMemoryVoiceStateRepository, webhook::InMemoryWebhookRepository,
 };
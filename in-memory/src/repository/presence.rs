@@ -48,6 +48,12 @@ impl Repository<PresenceEntity, InMemoryBackend> for InMemoryPresenceRepository
         &self,
         presence_id: (GuildId, UserId),
     ) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let (guild_id, user_id) = presence_id;
+
+        if let Some(mut guild_presences) = (self.0).0.guild_presences.get_mut(&guild_id) {
+            guild_presences.remove(&user_id);
+        }
+
         (self.0).0.presences.remove(&presence_id);
 
         future::ok(()).boxed()
@@ -63,13 +69,116 @@ impl Repository<PresenceEntity, InMemoryBackend> for InMemoryPresenceRepository
             return future::ok(()).boxed();
         }
 
-        (self.0).0.presences.insert(entity.id(), entity);
+        let (guild_id, user_id) = entity.id();
+
+        (self.0).0.presences.insert((guild_id, user_id), entity);
+
+        (self.0)
+            .0
+            .guild_presences
+            .entry(guild_id)
+            .or_default()
+            .insert(user_id);
 
         future::ok(()).boxed()
     }
 }
 
-impl PresenceRepository<InMemoryBackend> for InMemoryPresenceRepository {}
+impl PresenceRepository<InMemoryBackend> for InMemoryPresenceRepository {
+    fn online(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, PresenceEntity, InMemoryBackendError> {
+        self.filter_guild(guild_id, PresenceEntity::is_online)
+    }
+
+    fn with_activity<'a>(
+        &'a self,
+        guild_id: GuildId,
+        name: &'a str,
+    ) -> ListEntitiesFuture<'a, PresenceEntity, InMemoryBackendError> {
+        self.filter_guild(guild_id, |presence| presence.has_activity(name))
+    }
+}
+
+impl InMemoryPresenceRepository {
+    /// Retrieve a stream of presences in a guild of users who are online.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut presences = cache.presences.online(GuildId(1)).await?;
+    ///
+    /// while let Some(presence) = presences.next().await {
+    ///     println!("user {} is online", presence?.user_id);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn online(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, PresenceEntity, InMemoryBackendError> {
+        PresenceRepository::online(self, guild_id)
+    }
+
+    /// Retrieve a stream of presences in a guild of users who have an
+    /// activity with the given name.
+    ///
+    /// # Examples
+    ///
+    /// Count the number of members in a guild playing a game:
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::GuildId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let presences = cache.presences.with_activity(GuildId(1), "Minecraft").await?;
+    /// let players = presences.try_collect::<Vec<_>>().await?;
+    ///
+    /// println!("{} members are playing Minecraft", players.len());
+    /// # Ok(()) }
+    /// ```
+    pub fn with_activity<'a>(
+        &'a self,
+        guild_id: GuildId,
+        name: &'a str,
+    ) -> ListEntitiesFuture<'a, PresenceEntity, InMemoryBackendError> {
+        PresenceRepository::with_activity(self, guild_id, name)
+    }
+
+    fn filter_guild(
+        &self,
+        guild_id: GuildId,
+        predicate: impl Fn(&PresenceEntity) -> bool,
+    ) -> ListEntitiesFuture<'_, PresenceEntity, InMemoryBackendError> {
+        let presences = (self.0)
+            .0
+            .guild_presences
+            .get(&guild_id)
+            .map(|user_ids| {
+                user_ids
+                    .iter()
+                    .filter_map(|user_id| (self.0).0.presences.get(&(guild_id, *user_id)))
+                    .filter(|presence| predicate(presence.value()))
+                    .map(|presence| Ok(presence.value().clone()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        future::ok(stream::iter(presences).boxed()).boxed()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -7,10 +7,11 @@ use rarity_cache::{
     entity::{
         channel::{MessageEntity, PrivateChannelEntity, PrivateChannelRepository},
         user::UserEntity,
-        Entity,
+        Entity, EntityField,
     },
     repository::{
-        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpdateEntityFuture,
+        UpsertEntityFuture,
     },
 };
 use twilight_model::id::ChannelId;
@@ -58,6 +59,21 @@ impl Repository<PrivateChannelEntity, InMemoryBackend> for InMemoryPrivateChanne
         future::ok(()).boxed()
     }
 
+    fn update_field<F: EntityField<PrivateChannelEntity>>(
+        &self,
+        channel_id: ChannelId,
+        field: F,
+    ) -> UpdateEntityFuture<'_, InMemoryBackendError> {
+        let updated = (self.0)
+            .0
+            .channels_private
+            .get_mut(&channel_id)
+            .map(|mut channel| field.apply(&mut channel))
+            .is_some();
+
+        future::ok(updated).boxed()
+    }
+
     fn upsert(&self, entity: PrivateChannelEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
         if !self
             .0
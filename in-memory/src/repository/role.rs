@@ -8,11 +8,12 @@ use rarity_cache::{
         guild::{GuildEntity, RoleEntity, RoleRepository},
         Entity,
     },
+    permission,
     repository::{
         GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
     },
 };
-use twilight_model::id::RoleId;
+use twilight_model::id::{RoleId, UserId};
 
 /// Repository to retrieve and work with roles and their related entities.
 #[derive(Clone, Debug)]
@@ -34,6 +35,12 @@ impl Repository<RoleEntity, InMemoryBackend> for InMemoryRoleRepository {
     }
 
     fn remove(&self, role_id: RoleId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let guild_id = (self.0).0.roles.get(&role_id).map(|role| role.guild_id);
+
+        if let Some(mut guild_roles) = guild_id.and_then(|id| (self.0).0.guild_roles.get_mut(&id)) {
+            guild_roles.remove(&role_id);
+        }
+
         (self.0).0.roles.remove(&role_id);
 
         future::ok(()).boxed()
@@ -44,7 +51,17 @@ impl Repository<RoleEntity, InMemoryBackend> for InMemoryRoleRepository {
             return future::ok(()).boxed();
         }
 
-        (self.0).0.roles.insert(entity.id(), entity);
+        let guild_id = entity.guild_id;
+        let role_id = entity.id();
+
+        (self.0).0.roles.insert(role_id, entity);
+
+        (self.0)
+            .0
+            .guild_roles
+            .entry(guild_id)
+            .or_default()
+            .insert(role_id);
 
         future::ok(()).boxed()
     }
@@ -63,12 +80,77 @@ impl RoleRepository<InMemoryBackend> for InMemoryRoleRepository {
 
         future::ok(guild).boxed()
     }
+
+    fn manageable_by(
+        &self,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        let Some(role) = (self.0).0.roles.get(&role_id).map(|r| r.value().clone()) else {
+            return future::ok(None).boxed();
+        };
+
+        let Some(owner_id) = (self.0)
+            .0
+            .guilds
+            .get(&role.guild_id)
+            .map(|guild| guild.owner_id)
+        else {
+            return future::ok(None).boxed();
+        };
+
+        let Some(role_ids) = (self.0)
+            .0
+            .members
+            .get(&(role.guild_id, user_id))
+            .map(|member| member.role_ids.clone())
+        else {
+            return future::ok(None).boxed();
+        };
+
+        if owner_id == user_id {
+            return future::ok(Some(true)).boxed();
+        }
+
+        let roles = role_ids
+            .iter()
+            .filter_map(|id| (self.0).0.roles.get(id).map(|r| r.value().clone()))
+            .collect::<Vec<_>>();
+
+        let manageable = permission::is_above(permission::highest_role(&roles), Some(&role));
+
+        future::ok(Some(manageable)).boxed()
+    }
 }
 
 impl InMemoryRoleRepository {
     pub fn guild(&self, role_id: RoleId) -> GetEntityFuture<'_, GuildEntity, InMemoryBackendError> {
         RoleRepository::guild(self, role_id)
     }
+
+    /// Whether a member can manage a role based on the role hierarchy.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::{RoleId, UserId};
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if cache.roles.manageable_by(RoleId(1), UserId(2)).await? == Some(true) {
+    ///     println!("the member can assign the role");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn manageable_by(
+        &self,
+        role_id: RoleId,
+        user_id: UserId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        RoleRepository::manageable_by(self, role_id, user_id)
+    }
 }
 
 #[cfg(test)]
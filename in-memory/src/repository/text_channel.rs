@@ -1,3 +1,4 @@
+use super::{InMemoryMessageRepository, InMemoryWebhookRepository};
 use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
 use futures_util::{
     future::{self, FutureExt},
@@ -7,19 +8,37 @@ use rarity_cache::{
     entity::{
         channel::{CategoryChannelEntity, MessageEntity, TextChannelEntity, TextChannelRepository},
         guild::GuildEntity,
-        Entity,
+        Entity, EntityField,
     },
     repository::{
-        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture, Repository,
+        UpdateEntityFuture, UpsertEntityFuture,
     },
 };
-use twilight_model::id::ChannelId;
+use twilight_model::id::{ChannelId, MessageId};
 
 /// Repository to retrieve and work with text channels and their related
 /// entities.
 #[derive(Clone, Debug)]
 pub struct InMemoryTextChannelRepository(pub(crate) InMemoryBackend);
 
+impl InMemoryTextChannelRepository {
+    /// Remove the messages of a text channel and their attachments, leaving
+    /// the channel itself in the cache.
+    pub(super) fn remove_messages(&self, channel_id: ChannelId) {
+        let Some((_, message_ids)) = (self.0).0.channel_messages.remove(&channel_id) else {
+            return;
+        };
+
+        let messages = InMemoryMessageRepository(self.0.clone());
+
+        for message_id in message_ids {
+            messages.remove_attachments(message_id);
+            (self.0).0.messages.remove(&message_id);
+        }
+    }
+}
+
 impl Repository<TextChannelEntity, InMemoryBackend> for InMemoryTextChannelRepository {
     fn backend(&self) -> InMemoryBackend {
         self.0.clone()
@@ -53,11 +72,45 @@ impl Repository<TextChannelEntity, InMemoryBackend> for InMemoryTextChannelRepos
     }
 
     fn remove(&self, channel_id: ChannelId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let guild_id = (self.0)
+            .0
+            .channels_text
+            .get(&channel_id)
+            .and_then(|channel| channel.guild_id);
+
+        // Guild channels of every kind share the guild's channel index, so it
+        // keeps the channel while another kind of channel with its ID is
+        // cached, such as after the channel changed kind.
+        let other_kind = (self.0).0.channels_category.contains_key(&channel_id)
+            || (self.0).0.channels_voice.contains_key(&channel_id);
+
+        if let Some(mut guild_channels) = guild_id
+            .filter(|_| !other_kind)
+            .and_then(|id| (self.0).0.guild_channels.get_mut(&id))
+        {
+            guild_channels.remove(&channel_id);
+        }
+
         (self.0).0.channels_text.remove(&channel_id);
 
         future::ok(()).boxed()
     }
 
+    fn update_field<F: EntityField<TextChannelEntity>>(
+        &self,
+        channel_id: ChannelId,
+        field: F,
+    ) -> UpdateEntityFuture<'_, InMemoryBackendError> {
+        let updated = (self.0)
+            .0
+            .channels_text
+            .get_mut(&channel_id)
+            .map(|mut channel| field.apply(&mut channel))
+            .is_some();
+
+        future::ok(updated).boxed()
+    }
+
     fn upsert(&self, entity: TextChannelEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
         if !self
             .0
@@ -69,7 +122,29 @@ impl Repository<TextChannelEntity, InMemoryBackend> for InMemoryTextChannelRepos
             return future::ok(()).boxed();
         }
 
-        (self.0).0.channels_text.insert(entity.id(), entity);
+        let channel_id = entity.id();
+        let guild_id = entity.guild_id;
+
+        let old = (self.0).0.channels_text.insert(channel_id, entity);
+
+        if let Some(guild_id) = guild_id {
+            (self.0)
+                .0
+                .guild_channels
+                .entry(guild_id)
+                .or_default()
+                .insert(channel_id);
+        }
+
+        // If the channel moved out of a guild then it needs to be removed from
+        // the old guild's set of channels.
+        if let Some(old_guild_id) = old.and_then(|channel| channel.guild_id) {
+            if Some(old_guild_id) != guild_id {
+                if let Some(mut guild_channels) = (self.0).0.guild_channels.get_mut(&old_guild_id) {
+                    guild_channels.remove(&channel_id);
+                }
+            }
+        }
 
         future::ok(()).boxed()
     }
@@ -108,6 +183,35 @@ impl TextChannelRepository<InMemoryBackend> for InMemoryTextChannelRepository {
         future::ok(message).boxed()
     }
 
+    fn message_ids(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntityIdsFuture<'_, MessageId, InMemoryBackendError> {
+        let stream = (self.0).0.channel_messages.get(&channel_id).map_or_else(
+            || stream::empty().boxed(),
+            |set| stream::iter(set.iter().map(|x| Ok(*x)).collect::<Vec<_>>()).boxed(),
+        );
+
+        future::ok(stream).boxed()
+    }
+
+    fn messages(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, MessageEntity, InMemoryBackendError> {
+        let message_ids = match (self.0).0.channel_messages.get(&channel_id) {
+            Some(channel_messages) => channel_messages.clone(),
+            None => return future::ok(stream::empty().boxed()).boxed(),
+        };
+
+        let iter = message_ids
+            .into_iter()
+            .filter_map(move |id| (self.0).0.messages.get(&id).map(|r| Ok(r.value().clone())));
+        let stream = stream::iter(iter).boxed();
+
+        future::ok(stream).boxed()
+    }
+
     fn parent(
         &self,
         channel_id: ChannelId,
@@ -123,6 +227,16 @@ impl TextChannelRepository<InMemoryBackend> for InMemoryTextChannelRepository {
 
         future::ok(parent).boxed()
     }
+
+    fn remove_cascade(
+        &self,
+        channel_id: ChannelId,
+    ) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        self.remove_messages(channel_id);
+        InMemoryWebhookRepository(self.0.clone()).remove_channel_webhooks(channel_id);
+
+        self.remove(channel_id)
+    }
 }
 
 impl InMemoryTextChannelRepository {
@@ -199,11 +313,23 @@ impl InMemoryTextChannelRepository {
 #[cfg(test)]
 mod tests {
     use super::{
-        InMemoryBackend, InMemoryTextChannelRepository, Repository, TextChannelEntity,
-        TextChannelRepository,
+        CategoryChannelEntity, InMemoryBackend, InMemoryTextChannelRepository, Repository,
+        TextChannelEntity, TextChannelRepository,
+    };
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::{
+        entity::{
+            channel::{AttachmentEntity, MessageEntity, MessageRepository},
+            guild::GuildRepository,
+        },
+        Backend,
     };
     use static_assertions::{assert_impl_all, assert_obj_safe};
     use std::fmt::Debug;
+    use twilight_model::{
+        channel::{message::MessageType, ChannelType},
+        id::{AttachmentId, ChannelId, GuildId, MessageId, UserId},
+    };
 
     assert_impl_all!(
         InMemoryTextChannelRepository:
@@ -215,4 +341,137 @@ mod tests {
         Sync,
     );
     assert_obj_safe!(InMemoryTextChannelRepository);
+
+    fn channel() -> TextChannelEntity {
+        TextChannelEntity {
+            guild_id: None,
+            id: ChannelId(1),
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "channel".to_owned(),
+            nsfw: false,
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        }
+    }
+
+    fn message(message_id: u64, attachment_id: u64) -> MessageEntity {
+        MessageEntity {
+            application_id: None,
+            attachments: vec![AttachmentId(attachment_id)],
+            author_id: UserId(2),
+            channel_id: ChannelId(1),
+            content: "ping".to_owned(),
+            edited_timestamp: None,
+            embeds: Vec::new(),
+            flags: None,
+            guild_id: None,
+            id: MessageId(message_id),
+            kind: MessageType::Regular,
+            mention_channels: Vec::new(),
+            mention_everyone: false,
+            mention_roles: Vec::new(),
+            mentions: Vec::new(),
+            pinned: false,
+            reactions: Vec::new(),
+            reaction_users: Vec::new(),
+            timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+            tts: false,
+            webhook_id: None,
+        }
+    }
+
+    fn attachment(message_id: u64, attachment_id: u64) -> AttachmentEntity {
+        AttachmentEntity {
+            filename: "image.png".to_owned(),
+            height: None,
+            id: AttachmentId(attachment_id),
+            message_id: MessageId(message_id),
+            proxy_url: "https://media.example.com/image.png".to_owned(),
+            size: 1024,
+            url: "https://cdn.example.com/image.png".to_owned(),
+            width: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remove_cascade() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let attachments = backend.attachments();
+        let channels = backend.text_channels();
+        let messages = backend.messages();
+
+        channels.upsert(channel()).await?;
+        messages.upsert(message(3, 4)).await?;
+        messages.upsert(message(5, 6)).await?;
+        attachments.upsert(attachment(3, 4)).await?;
+        attachments.upsert(attachment(5, 6)).await?;
+
+        let ids = channels
+            .message_ids(ChannelId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![MessageId(3), MessageId(5)], ids);
+
+        messages.remove_cascade(MessageId(3)).await?;
+        assert!(attachments.get(AttachmentId(4)).await?.is_none());
+        let found = channels
+            .messages(ChannelId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![message(5, 6)], found);
+
+        channels.remove_cascade(ChannelId(1)).await?;
+        assert!(channels.get(ChannelId(1)).await?.is_none());
+        assert!(messages.get(MessageId(5)).await?.is_none());
+        assert!(attachments.get(AttachmentId(6)).await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remove_keeps_other_kind_indexed() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let categories = backend.category_channels();
+        let channels = backend.text_channels();
+        let guilds = backend.guilds();
+
+        let mut text = channel();
+        text.guild_id = Some(GuildId(2));
+        channels.upsert(text).await?;
+        categories
+            .upsert(CategoryChannelEntity {
+                guild_id: Some(GuildId(2)),
+                id: ChannelId(1),
+                kind: ChannelType::GuildCategory,
+                name: "category".to_owned(),
+                permission_overwrites: Vec::new(),
+                position: 0,
+            })
+            .await?;
+
+        channels.remove(ChannelId(1)).await?;
+        let ids = guilds
+            .channel_ids(GuildId(2))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![ChannelId(1)], ids);
+
+        categories.remove(ChannelId(1)).await?;
+        let ids = guilds
+            .channel_ids(GuildId(2))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(ids.is_empty());
+
+        Ok(())
+    }
 }
@@ -0,0 +1,235 @@
+use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        gateway::{TypingEntity, TypingRepository},
+        user::UserEntity,
+        Entity,
+    },
+    now,
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, Repository, UpsertEntityFuture,
+    },
+};
+use twilight_model::id::{ChannelId, UserId};
+
+/// Repository to retrieve and work with users typing in channels.
+///
+/// Expired typing states are never returned and are removed from the cache
+/// when they're encountered.
+#[derive(Clone, Debug)]
+pub struct InMemoryTypingRepository(pub(crate) InMemoryBackend);
+
+impl InMemoryTypingRepository {
+    /// Return the live typing states in a channel, removing expired ones.
+    fn live(&self, channel_id: ChannelId) -> Vec<TypingEntity> {
+        let user_ids = (self.0)
+            .0
+            .channel_typing
+            .get(&channel_id)
+            .map(|r| r.value().clone())
+            .unwrap_or_default();
+
+        let now = now();
+        let mut live = Vec::new();
+
+        for user_id in user_ids {
+            let typing = (self.0)
+                .0
+                .typing
+                .get(&(channel_id, user_id))
+                .map(|r| r.value().clone());
+
+            match typing {
+                Some(typing) if !typing.is_expired_at(now) => live.push(typing),
+                _ => self.remove_entry(channel_id, user_id),
+            }
+        }
+
+        live
+    }
+
+    fn remove_entry(&self, channel_id: ChannelId, user_id: UserId) {
+        (self.0).0.typing.remove(&(channel_id, user_id));
+
+        if let Some(mut channel_typing) = (self.0).0.channel_typing.get_mut(&channel_id) {
+            channel_typing.remove(&user_id);
+        }
+    }
+}
+
+impl Repository<TypingEntity, InMemoryBackend> for InMemoryTypingRepository {
+    fn backend(&self) -> InMemoryBackend {
+        self.0.clone()
+    }
+
+    fn get(
+        &self,
+        typing_id: (ChannelId, UserId),
+    ) -> GetEntityFuture<'_, TypingEntity, InMemoryBackendError> {
+        let typing = (self.0).0.typing.get(&typing_id).map(|r| r.value().clone());
+
+        let typing = match typing {
+            Some(typing) if typing.is_expired() => {
+                self.remove_entry(typing.channel_id, typing.user_id);
+
+                None
+            }
+            typing => typing,
+        };
+
+        future::ok(typing).boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, TypingEntity, InMemoryBackendError> {
+        let now = now();
+        let typing = (self.0)
+            .0
+            .typing
+            .iter()
+            .filter(|r| !r.value().is_expired_at(now))
+            .map(|r| Ok(r.value().clone()))
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(typing).boxed()).boxed()
+    }
+
+    fn remove(
+        &self,
+        typing_id: (ChannelId, UserId),
+    ) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let (channel_id, user_id) = typing_id;
+        self.remove_entry(channel_id, user_id);
+
+        future::ok(()).boxed()
+    }
+
+    fn upsert(&self, entity: TypingEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if !(self.0)
+            .0
+            .config
+            .entity_types()
+            .contains(EntityType::TYPING)
+        {
+            return future::ok(()).boxed();
+        }
+
+        // Clear out any states in the channel that have since expired so that
+        // channels which are never read don't grow unbounded.
+        self.live(entity.channel_id);
+
+        (self.0)
+            .0
+            .channel_typing
+            .entry(entity.channel_id)
+            .or_default()
+            .insert(entity.user_id);
+
+        (self.0).0.typing.insert(entity.id(), entity);
+
+        future::ok(()).boxed()
+    }
+}
+
+impl TypingRepository<InMemoryBackend> for InMemoryTypingRepository {
+    fn channel_typing(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, TypingEntity, InMemoryBackendError> {
+        let typing = self.live(channel_id).into_iter().map(Ok);
+
+        future::ok(stream::iter(typing).boxed()).boxed()
+    }
+}
+
+impl InMemoryTypingRepository {
+    /// Retrieve a stream of the users currently typing in a channel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::TryStreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::ChannelId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut typing = cache.typing.channel_typing(ChannelId(123456)).await?;
+    ///
+    /// while let Some(typing) = typing.try_next().await? {
+    ///     println!("user {} is typing", typing.user_id);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn channel_typing(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, TypingEntity, InMemoryBackendError> {
+        TypingRepository::channel_typing(self, channel_id)
+    }
+
+    /// Retrieve the user associated with a typing state.
+    pub fn user(
+        &self,
+        typing_id: (ChannelId, UserId),
+    ) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        TypingRepository::user(self, typing_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        now, InMemoryBackend, InMemoryTypingRepository, Repository, TypingEntity, TypingRepository,
+    };
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::Backend;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+    use twilight_model::id::{ChannelId, UserId};
+
+    assert_impl_all!(
+        InMemoryTypingRepository:
+        TypingRepository<InMemoryBackend>,
+        Clone,
+        Debug,
+        Repository<TypingEntity, InMemoryBackend>,
+        Send,
+        Sync,
+    );
+    assert_obj_safe!(InMemoryTypingRepository);
+
+    fn typing(user_id: u64, timestamp: u64) -> TypingEntity {
+        TypingEntity {
+            channel_id: ChannelId(1),
+            guild_id: None,
+            timestamp,
+            user_id: UserId(user_id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_removed() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let repo = backend.typing();
+        let now = now();
+        repo.upsert(typing(2, now - TypingEntity::DURATION)).await?;
+        repo.upsert(typing(3, now)).await?;
+
+        let user_ids = repo
+            .channel_typing(ChannelId(1))
+            .await?
+            .map_ok(|typing| typing.user_id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![UserId(3)], user_ids);
+        assert!(repo.get((ChannelId(1), UserId(2))).await?.is_none());
+        assert!(!backend.0.typing.contains_key(&(ChannelId(1), UserId(2))));
+
+        Ok(())
+    }
+}
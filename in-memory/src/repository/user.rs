@@ -96,8 +96,11 @@ impl InMemoryUserRepository {
 #[cfg(test)]
 mod tests {
     use super::{InMemoryBackend, InMemoryUserRepository, Repository, UserEntity, UserRepository};
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::{entity::guild::MemberEntity, Backend};
     use static_assertions::{assert_impl_all, assert_obj_safe};
     use std::fmt::Debug;
+    use twilight_model::id::{GuildId, UserId};
 
     assert_impl_all!(
         InMemoryUserRepository:
@@ -109,4 +112,47 @@ mod tests {
         Sync,
     );
     assert_obj_safe!(InMemoryUserRepository);
+
+    fn member(guild_id: u64) -> MemberEntity {
+        MemberEntity {
+            deaf: false,
+            guild_id: GuildId(guild_id),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids: Vec::new(),
+            user_id: UserId(1),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_guild_ids() -> Result<(), Box<dyn std::error::Error>> {
+        let backend = InMemoryBackend::new();
+        let members = backend.members();
+        let users = backend.users();
+
+        members.upsert(member(2)).await?;
+        members.upsert(member(3)).await?;
+        // Upserting a member again mustn't duplicate the guild.
+        members.upsert(member(2)).await?;
+
+        let ids = users
+            .guild_ids(UserId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![GuildId(2), GuildId(3)], ids);
+
+        members.remove((GuildId(2), UserId(1))).await?;
+        let ids = users
+            .guild_ids(UserId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![GuildId(3)], ids);
+
+        Ok(())
+    }
 }
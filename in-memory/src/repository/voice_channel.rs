@@ -6,7 +6,8 @@ use futures_util::{
 use rarity_cache::{
     entity::{
         channel::{CategoryChannelEntity, VoiceChannelEntity, VoiceChannelRepository},
-        guild::GuildEntity,
+        guild::{GuildEntity, MemberEntity},
+        voice::VoiceStateEntity,
         Entity,
     },
     repository::{
@@ -52,8 +53,27 @@ impl Repository<VoiceChannelEntity, InMemoryBackend> for InMemoryVoiceChannelRep
         future::ok(stream).boxed()
     }
 
-    fn remove(&self, user_id: ChannelId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
-        (self.0).0.channels_voice.remove(&user_id);
+    fn remove(&self, channel_id: ChannelId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let guild_id = (self.0)
+            .0
+            .channels_voice
+            .get(&channel_id)
+            .and_then(|channel| channel.guild_id);
+
+        // Guild channels of every kind share the guild's channel index, so it
+        // keeps the channel while another kind of channel with its ID is
+        // cached, such as after the channel changed kind.
+        let other_kind = (self.0).0.channels_category.contains_key(&channel_id)
+            || (self.0).0.channels_text.contains_key(&channel_id);
+
+        if let Some(mut guild_channels) = guild_id
+            .filter(|_| !other_kind)
+            .and_then(|id| (self.0).0.guild_channels.get_mut(&id))
+        {
+            guild_channels.remove(&channel_id);
+        }
+
+        (self.0).0.channels_voice.remove(&channel_id);
 
         future::ok(()).boxed()
     }
@@ -69,7 +89,29 @@ impl Repository<VoiceChannelEntity, InMemoryBackend> for InMemoryVoiceChannelRep
             return future::ok(()).boxed();
         }
 
-        (self.0).0.channels_voice.insert(entity.id(), entity);
+        let channel_id = entity.id();
+        let guild_id = entity.guild_id;
+
+        let old = (self.0).0.channels_voice.insert(channel_id, entity);
+
+        if let Some(guild_id) = guild_id {
+            (self.0)
+                .0
+                .guild_channels
+                .entry(guild_id)
+                .or_default()
+                .insert(channel_id);
+        }
+
+        // If the channel moved out of a guild then it needs to be removed from
+        // the old guild's set of channels.
+        if let Some(old_guild_id) = old.and_then(|channel| channel.guild_id) {
+            if Some(old_guild_id) != guild_id {
+                if let Some(mut guild_channels) = (self.0).0.guild_channels.get_mut(&old_guild_id) {
+                    guild_channels.remove(&channel_id);
+                }
+            }
+        }
 
         future::ok(()).boxed()
     }
@@ -107,6 +149,34 @@ impl VoiceChannelRepository<InMemoryBackend> for InMemoryVoiceChannelRepository
 
         future::ok(parent).boxed()
     }
+
+    fn members(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, MemberEntity, InMemoryBackendError> {
+        let members = self
+            .connected(channel_id)
+            .into_iter()
+            .filter_map(|state| {
+                (self.0)
+                    .0
+                    .members
+                    .get(&(state.guild_id, state.user_id))
+                    .map(|r| Ok(r.value().clone()))
+            })
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(members).boxed()).boxed()
+    }
+
+    fn voice_states(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, VoiceStateEntity, InMemoryBackendError> {
+        let states = self.connected(channel_id).into_iter().map(Ok);
+
+        future::ok(stream::iter(states).boxed()).boxed()
+    }
 }
 
 impl InMemoryVoiceChannelRepository {
@@ -155,6 +225,83 @@ impl InMemoryVoiceChannelRepository {
     ) -> GetEntityFuture<'_, CategoryChannelEntity, InMemoryBackendError> {
         VoiceChannelRepository::parent(self, channel_id)
     }
+
+    /// Retrieve a stream of members connected to a voice channel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::ChannelId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut members = cache.voice_channels.members(ChannelId(1)).await?;
+    ///
+    /// while let Some(member) = members.next().await {
+    ///     println!("user {} is connected", member?.user_id);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn members(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, MemberEntity, InMemoryBackendError> {
+        VoiceChannelRepository::members(self, channel_id)
+    }
+
+    /// Retrieve a stream of voice states of users connected to a voice
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures_util::stream::StreamExt;
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::ChannelId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// let mut states = cache.voice_channels.voice_states(ChannelId(1)).await?;
+    ///
+    /// while let Some(state) = states.next().await {
+    ///     let state = state?;
+    ///     println!("user {} is muted: {}", state.user_id, state.self_mute);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn voice_states(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, VoiceStateEntity, InMemoryBackendError> {
+        VoiceChannelRepository::voice_states(self, channel_id)
+    }
+
+    /// Clone the voice states of users connected to a voice channel.
+    fn connected(&self, channel_id: ChannelId) -> Vec<VoiceStateEntity> {
+        let Some(guild_id) = (self.0)
+            .0
+            .channels_voice
+            .get(&channel_id)
+            .and_then(|channel| channel.guild_id)
+        else {
+            return Vec::new();
+        };
+
+        let Some(user_ids) = (self.0).0.guild_voice_states.get(&guild_id) else {
+            return Vec::new();
+        };
+
+        user_ids
+            .iter()
+            .filter_map(|user_id| (self.0).0.voice_states.get(&(guild_id, *user_id)))
+            .filter(|state| state.channel_id == Some(channel_id))
+            .map(|state| state.value().clone())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -56,6 +56,12 @@ impl Repository<VoiceStateEntity, InMemoryBackend> for InMemoryVoiceStateReposit
         &self,
         voice_state_id: (GuildId, UserId),
     ) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        let (guild_id, user_id) = voice_state_id;
+
+        if let Some(mut guild_voice_states) = (self.0).0.guild_voice_states.get_mut(&guild_id) {
+            guild_voice_states.remove(&user_id);
+        }
+
         (self.0).0.voice_states.remove(&voice_state_id);
 
         future::ok(()).boxed()
@@ -72,7 +78,16 @@ impl Repository<VoiceStateEntity, InMemoryBackend> for InMemoryVoiceStateReposit
             return future::ok(()).boxed();
         }
 
-        (self.0).0.voice_states.insert(entity.id(), entity);
+        let (guild_id, user_id) = entity.id();
+
+        (self.0).0.voice_states.insert((guild_id, user_id), entity);
+
+        (self.0)
+            .0
+            .guild_voice_states
+            .entry(guild_id)
+            .or_default()
+            .insert(user_id);
 
         future::ok(()).boxed()
     }
@@ -0,0 +1,388 @@
+use crate::{config::EntityType, InMemoryBackend, InMemoryBackendError};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        channel::{TextChannelEntity, WebhookEntity, WebhookRepository},
+        guild::GuildEntity,
+        user::UserEntity,
+    },
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture, Repository,
+        UpsertEntitiesFuture, UpsertEntityFuture,
+    },
+};
+use std::collections::HashSet;
+use twilight_model::id::{ChannelId, GuildId, WebhookId};
+
+/// Repository to retrieve and work with webhooks and their related entities.
+#[derive(Clone, Debug)]
+pub struct InMemoryWebhookRepository(pub(crate) InMemoryBackend);
+
+impl InMemoryWebhookRepository {
+    fn webhooks(
+        &self,
+        ids: Option<HashSet<WebhookId>>,
+    ) -> Vec<Result<WebhookEntity, InMemoryBackendError>> {
+        ids.into_iter()
+            .flatten()
+            .filter_map(|id| (self.0).0.webhooks.get(&id).map(|r| Ok(r.value().clone())))
+            .collect()
+    }
+
+    /// Remove the webhooks of a channel along with its stale marker.
+    pub(super) fn remove_channel_webhooks(&self, channel_id: ChannelId) {
+        (self.0).0.webhooks_stale.remove(&channel_id);
+
+        let Some((_, webhook_ids)) = (self.0).0.channel_webhooks.remove(&channel_id) else {
+            return;
+        };
+
+        for webhook_id in webhook_ids {
+            let Some((_, webhook)) = (self.0).0.webhooks.remove(&webhook_id) else {
+                continue;
+            };
+
+            if let Some(guild_id) = webhook.guild_id {
+                if let Some(mut guild_webhooks) = (self.0).0.guild_webhooks.get_mut(&guild_id) {
+                    guild_webhooks.remove(&webhook_id);
+                }
+            }
+        }
+    }
+}
+
+impl Repository<WebhookEntity, InMemoryBackend> for InMemoryWebhookRepository {
+    fn backend(&self) -> InMemoryBackend {
+        self.0.clone()
+    }
+
+    fn get(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, WebhookEntity, InMemoryBackendError> {
+        future::ok(
+            (self.0)
+                .0
+                .webhooks
+                .get(&webhook_id)
+                .map(|r| r.value().clone()),
+        )
+        .boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, WebhookEntity, InMemoryBackendError> {
+        let stream =
+            stream::iter((self.0).0.webhooks.iter().map(|r| Ok(r.value().clone()))).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn remove(&self, webhook_id: WebhookId) -> RemoveEntityFuture<'_, InMemoryBackendError> {
+        if let Some((_, webhook)) = (self.0).0.webhooks.remove(&webhook_id) {
+            if let Some(mut channel_webhooks) =
+                (self.0).0.channel_webhooks.get_mut(&webhook.channel_id)
+            {
+                channel_webhooks.remove(&webhook_id);
+            }
+
+            if let Some(guild_id) = webhook.guild_id {
+                if let Some(mut guild_webhooks) = (self.0).0.guild_webhooks.get_mut(&guild_id) {
+                    guild_webhooks.remove(&webhook_id);
+                }
+            }
+        }
+
+        future::ok(()).boxed()
+    }
+
+    fn upsert(&self, entity: WebhookEntity) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if !(self.0)
+            .0
+            .config
+            .entity_types()
+            .contains(EntityType::WEBHOOK)
+        {
+            return future::ok(()).boxed();
+        }
+
+        (self.0)
+            .0
+            .channel_webhooks
+            .entry(entity.channel_id)
+            .or_default()
+            .insert(entity.id);
+
+        if let Some(guild_id) = entity.guild_id {
+            (self.0)
+                .0
+                .guild_webhooks
+                .entry(guild_id)
+                .or_default()
+                .insert(entity.id);
+        }
+
+        (self.0).0.webhooks.insert(entity.id, entity);
+
+        future::ok(()).boxed()
+    }
+}
+
+impl WebhookRepository<InMemoryBackend> for InMemoryWebhookRepository {
+    fn channel(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, TextChannelEntity, InMemoryBackendError> {
+        let channel = (self.0)
+            .0
+            .webhooks
+            .get(&webhook_id)
+            .and_then(|webhook| (self.0).0.channels_text.get(&webhook.channel_id))
+            .map(|r| r.value().clone());
+
+        future::ok(channel).boxed()
+    }
+
+    fn channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, InMemoryBackendError> {
+        let ids = (self.0)
+            .0
+            .channel_webhooks
+            .get(&channel_id)
+            .map(|r| r.value().clone());
+        let stream = stream::iter(self.webhooks(ids)).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn guild(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, GuildEntity, InMemoryBackendError> {
+        let guild = (self.0)
+            .0
+            .webhooks
+            .get(&webhook_id)
+            .and_then(|webhook| webhook.guild_id)
+            .and_then(|id| (self.0).0.guilds.get(&id))
+            .map(|r| r.value().clone());
+
+        future::ok(guild).boxed()
+    }
+
+    fn guild_webhooks(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, InMemoryBackendError> {
+        let ids = (self.0)
+            .0
+            .guild_webhooks
+            .get(&guild_id)
+            .map(|r| r.value().clone());
+        let stream = stream::iter(self.webhooks(ids)).boxed();
+
+        future::ok(stream).boxed()
+    }
+
+    fn is_stale(&self, channel_id: ChannelId) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        if !(self.0).0.channels_text.contains_key(&channel_id) {
+            return future::ok(None).boxed();
+        }
+
+        let stale = (self.0).0.webhooks_stale.contains_key(&channel_id);
+
+        future::ok(Some(stale)).boxed()
+    }
+
+    fn set_stale(
+        &self,
+        channel_id: ChannelId,
+        stale: bool,
+    ) -> UpsertEntityFuture<'_, InMemoryBackendError> {
+        if stale {
+            (self.0).0.webhooks_stale.insert(channel_id, ());
+        } else {
+            (self.0).0.webhooks_stale.remove(&channel_id);
+        }
+
+        future::ok(()).boxed()
+    }
+
+    fn stale_channel_ids(&self) -> ListEntityIdsFuture<'_, ChannelId, InMemoryBackendError> {
+        let ids = (self.0)
+            .0
+            .webhooks_stale
+            .iter()
+            .map(|r| Ok(*r.key()))
+            .collect::<Vec<_>>();
+
+        future::ok(stream::iter(ids).boxed()).boxed()
+    }
+
+    fn user(&self, webhook_id: WebhookId) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        let user = (self.0)
+            .0
+            .webhooks
+            .get(&webhook_id)
+            .and_then(|webhook| webhook.user_id)
+            .and_then(|id| (self.0).0.users.get(&id))
+            .map(|r| r.value().clone());
+
+        future::ok(user).boxed()
+    }
+}
+
+impl InMemoryWebhookRepository {
+    /// Retrieve the channel associated with a webhook.
+    pub fn channel(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, TextChannelEntity, InMemoryBackendError> {
+        WebhookRepository::channel(self, webhook_id)
+    }
+
+    /// Retrieve a stream of the webhooks of a channel.
+    pub fn channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, InMemoryBackendError> {
+        WebhookRepository::channel_webhooks(self, channel_id)
+    }
+
+    /// Retrieve the guild associated with a webhook.
+    pub fn guild(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, GuildEntity, InMemoryBackendError> {
+        WebhookRepository::guild(self, webhook_id)
+    }
+
+    /// Retrieve a stream of the webhooks within a guild.
+    pub fn guild_webhooks(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, InMemoryBackendError> {
+        WebhookRepository::guild_webhooks(self, guild_id)
+    }
+
+    /// Retrieve whether the webhooks of a channel are stale and need to be
+    /// refetched.
+    ///
+    /// Returns `None` if the channel isn't in the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_inmemory::InMemoryCache;
+    /// use twilight_model::id::ChannelId;
+    ///
+    /// # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = InMemoryCache::new();
+    ///
+    /// if cache.webhooks.is_stale(ChannelId(123)).await? == Some(true) {
+    ///     // fetch the channel's webhooks over HTTP and pass them to
+    ///     // `cache.webhooks.refresh`
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn is_stale(
+        &self,
+        channel_id: ChannelId,
+    ) -> GetEntityFuture<'_, bool, InMemoryBackendError> {
+        WebhookRepository::is_stale(self, channel_id)
+    }
+
+    /// Replace the cached webhooks of a channel with a freshly fetched set,
+    /// marking the channel's webhooks as no longer stale.
+    pub fn refresh(
+        &self,
+        channel_id: ChannelId,
+        webhooks: Vec<WebhookEntity>,
+    ) -> UpsertEntitiesFuture<'_, InMemoryBackendError> {
+        WebhookRepository::refresh(self, channel_id, webhooks)
+    }
+
+    /// Retrieve the user who created a webhook.
+    pub fn user(
+        &self,
+        webhook_id: WebhookId,
+    ) -> GetEntityFuture<'_, UserEntity, InMemoryBackendError> {
+        WebhookRepository::user(self, webhook_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InMemoryBackend, InMemoryWebhookRepository, Repository, WebhookEntity, WebhookRepository,
+    };
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::Backend;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+    use twilight_model::{
+        channel::WebhookType,
+        id::{ChannelId, GuildId, WebhookId},
+    };
+
+    assert_impl_all!(
+        InMemoryWebhookRepository:
+        WebhookRepository<InMemoryBackend>,
+        Clone,
+        Debug,
+        Repository<WebhookEntity, InMemoryBackend>,
+        Send,
+        Sync,
+    );
+    assert_obj_safe!(InMemoryWebhookRepository);
+
+    fn webhook(id: u64, channel_id: u64) -> WebhookEntity {
+        WebhookEntity {
+            avatar: None,
+            channel_id: ChannelId(channel_id),
+            guild_id: Some(GuildId(1)),
+            id: WebhookId(id),
+            kind: WebhookType::Incoming,
+            name: None,
+            token: None,
+            user_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh() -> Result<(), Box<dyn std::error::Error>> {
+        let repo = InMemoryBackend::new().webhooks();
+        repo.upsert(webhook(3, 2)).await?;
+        repo.upsert(webhook(4, 2)).await?;
+        repo.set_stale(ChannelId(2), true).await?;
+
+        repo.refresh(ChannelId(2), vec![webhook(5, 2)]).await?;
+
+        let ids = repo
+            .channel_webhooks(ChannelId(2))
+            .await?
+            .map_ok(|webhook| webhook.id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![WebhookId(5)], ids);
+        let ids = repo
+            .guild_webhooks(GuildId(1))
+            .await?
+            .map_ok(|webhook| webhook.id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![WebhookId(5)], ids);
+        assert!(repo
+            .stale_channel_ids()
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+use rarity_cache::conformance::Suite;
+use rarity_cache_inmemory::InMemoryBackend;
+
+#[tokio::test]
+async fn test_conformance() -> Result<(), Box<dyn std::error::Error>> {
+    Suite::new(InMemoryBackend::new).run().await?;
+
+    Ok(())
+}
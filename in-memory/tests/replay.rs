@@ -0,0 +1,229 @@
+use rarity_cache::replay::{self, EventReader, EventRecorder, Snapshot};
+use rarity_cache_inmemory::InMemoryCache;
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{
+        message::{Message, MessageType},
+        ChannelType, GuildChannel, TextChannel,
+    },
+    gateway::{
+        event::Event,
+        payload::{GuildCreate, MemberRemove, MessageCreate},
+    },
+    guild::{
+        DefaultMessageNotificationLevel, ExplicitContentFilter, Guild, Member, MfaLevel,
+        Permissions, PremiumTier, Role, SystemChannelFlags, VerificationLevel,
+    },
+    id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    user::User,
+};
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn guild() -> Guild {
+    let mut channels = HashMap::new();
+    channels.insert(
+        ChannelId(2),
+        GuildChannel::Text(TextChannel {
+            guild_id: Some(GuildId(1)),
+            id: ChannelId(2),
+            kind: ChannelType::GuildText,
+            last_message_id: None,
+            last_pin_timestamp: None,
+            name: "general".to_owned(),
+            nsfw: false,
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            position: 0,
+            rate_limit_per_user: None,
+            topic: None,
+        }),
+    );
+
+    let mut members = HashMap::new();
+    for user_id in 3..=4 {
+        members.insert(
+            UserId(user_id),
+            Member {
+                deaf: false,
+                guild_id: GuildId(1),
+                hoisted_role: None,
+                joined_at: None,
+                mute: false,
+                nick: None,
+                premium_since: None,
+                roles: vec![RoleId(1)],
+                user: user(user_id),
+            },
+        );
+    }
+
+    let mut roles = HashMap::new();
+    roles.insert(
+        RoleId(1),
+        Role {
+            color: 0,
+            hoist: false,
+            id: RoleId(1),
+            managed: false,
+            mentionable: false,
+            name: "@everyone".to_owned(),
+            permissions: Permissions::SEND_MESSAGES,
+            position: 0,
+        },
+    );
+
+    Guild {
+        afk_channel_id: None,
+        afk_timeout: 300,
+        application_id: None,
+        approximate_member_count: None,
+        approximate_presence_count: None,
+        banner: None,
+        channels,
+        default_message_notifications: DefaultMessageNotificationLevel::Mentions,
+        description: None,
+        discovery_splash: None,
+        embed_channel_id: None,
+        embed_enabled: None,
+        emojis: HashMap::new(),
+        explicit_content_filter: ExplicitContentFilter::None,
+        features: Vec::new(),
+        icon: None,
+        id: GuildId(1),
+        joined_at: Some("2020-01-01T00:00:00+00:00".to_owned()),
+        large: false,
+        lazy: None,
+        max_members: None,
+        max_presences: None,
+        max_video_channel_users: None,
+        member_count: Some(2),
+        members,
+        mfa_level: MfaLevel::None,
+        name: "guild".to_owned(),
+        owner_id: UserId(3),
+        owner: None,
+        permissions: None,
+        preferred_locale: "en-US".to_owned(),
+        premium_subscription_count: None,
+        premium_tier: PremiumTier::None,
+        presences: HashMap::new(),
+        region: "us-east".to_owned(),
+        roles,
+        rules_channel_id: None,
+        splash: None,
+        system_channel_flags: SystemChannelFlags::empty(),
+        system_channel_id: None,
+        unavailable: false,
+        vanity_url_code: None,
+        verification_level: VerificationLevel::None,
+        voice_states: HashMap::new(),
+        widget_channel_id: None,
+        widget_enabled: None,
+    }
+}
+
+fn message() -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: Vec::new(),
+        author: user(3),
+        channel_id: ChannelId(2),
+        content: "hello".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: Some(GuildId(1)),
+        id: MessageId(5),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+fn events() -> Vec<Event> {
+    vec![
+        Event::GuildCreate(Box::new(GuildCreate(guild()))),
+        Event::GatewayHeartbeatAck,
+        Event::MessageCreate(Box::new(MessageCreate(message()))),
+        Event::MemberRemove(MemberRemove {
+            guild_id: GuildId(1),
+            user: user(4),
+        }),
+    ]
+}
+
+async fn record_and_replay() -> Result<InMemoryCache, Box<dyn std::error::Error>> {
+    let mut recorder = EventRecorder::new(Vec::new());
+
+    for event in events() {
+        recorder.record(&event)?;
+    }
+
+    let recording = recorder.into_inner();
+    let cache = InMemoryCache::new();
+    let replayed = EventReader::new(recording.as_slice())
+        .replay(&cache)
+        .await?;
+    assert_eq!(3, replayed);
+
+    Ok(cache)
+}
+
+#[tokio::test]
+async fn test_replay() -> Result<(), Box<dyn std::error::Error>> {
+    let expected = InMemoryCache::new();
+
+    for event in events() {
+        expected.update(&event).await?;
+    }
+
+    let cache = record_and_replay().await?;
+    let snapshot = Snapshot::capture(&cache).await?;
+    replay::assert_snapshot_eq(&Snapshot::capture(&expected).await?, &snapshot);
+
+    assert_eq!(1, snapshot.guilds.len());
+    assert_eq!(1, snapshot.members.len());
+    assert_eq!(1, snapshot.messages.len());
+    assert_eq!(2, snapshot.users.len());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[should_panic(expected = "guild GuildId(1) changed: name expected \"renamed\", got \"guild\"")]
+async fn test_replay_snapshot_mismatch() {
+    let cache = record_and_replay().await.unwrap();
+    let mut expected = Snapshot::capture(&cache).await.unwrap();
+    expected.members.clear();
+    expected.guilds[0].name = "renamed".to_owned();
+
+    replay::assert_snapshot_eq(&expected, &Snapshot::capture(&cache).await.unwrap());
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+use serde_cbor::Error as CborError;
+
+/// Format to serialize entities with before storing them in Redis.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Format {
+    /// CBOR with named fields.
+    ///
+    /// This is the most portable format, since records can be read back
+    /// regardless of changes to the order of fields in entities.
+    #[default]
+    Cbor,
+    /// CBOR with fields identified by their index instead of their name.
+    ///
+    /// Records are smaller, but can't be read back if the order of fields in
+    /// an entity changes between versions.
+    PackedCbor,
+}
+
+impl Format {
+    pub(crate) fn serialize<T: Serialize>(self, value: &T) -> Result<Vec<u8>, CborError> {
+        match self {
+            Self::Cbor => serde_cbor::to_vec(value),
+            Self::PackedCbor => serde_cbor::ser::to_vec_packed(value),
+        }
+    }
+}
+
+/// Configuration for the Redis backend.
+///
+/// Refer to each setter method to know the default value.
+#[derive(Clone, Debug)]
+pub struct Config {
+    address: String,
+    format: Format,
+    namespace: String,
+}
+
+impl Config {
+    /// Create a new default configuration connecting to the given address.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            format: Format::default(),
+            namespace: String::from("rarity"),
+        }
+    }
+
+    /// Returns an immutable reference to the address of the Redis server.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns a mutable reference to the address of the Redis server.
+    ///
+    /// The address is in the form of `host:port`, such as `127.0.0.1:6379`.
+    pub fn address_mut(&mut self) -> &mut String {
+        &mut self.address
+    }
+
+    /// Returns the format entities are serialized with.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns a mutable reference to the format entities are serialized
+    /// with.
+    ///
+    /// Defaults to [`Format::Cbor`].
+    ///
+    /// [`Format::Cbor`]: enum.Format.html#variant.Cbor
+    pub fn format_mut(&mut self) -> &mut Format {
+        &mut self.format
+    }
+
+    /// Returns an immutable reference to the namespace keys are prefixed
+    /// with.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns a mutable reference to the namespace keys are prefixed with.
+    ///
+    /// Multiple caches can share a Redis database by using different
+    /// namespaces.
+    ///
+    /// Defaults to `rarity`.
+    pub fn namespace_mut(&mut self) -> &mut String {
+        &mut self.namespace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Format};
+    use rarity_cache::entity::guild::RoleEntity;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        guild::Permissions,
+        id::{GuildId, RoleId},
+    };
+
+    assert_impl_all!(Config: Clone, Debug, Send, Sync);
+    assert_impl_all!(Format: Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Send, Sync);
+    assert_obj_safe!(Config, Format);
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::new("127.0.0.1:6379");
+        assert_eq!("127.0.0.1:6379", config.address());
+        assert_eq!(Format::Cbor, config.format());
+        assert_eq!("rarity", config.namespace());
+    }
+
+    #[test]
+    fn test_formats_roundtrip() {
+        let role = RoleEntity {
+            color: 0,
+            guild_id: GuildId(1),
+            hoist: false,
+            id: RoleId(2),
+            managed: false,
+            mentionable: false,
+            name: "role".to_owned(),
+            permissions: Permissions::SEND_MESSAGES,
+            position: 0,
+        };
+
+        for format in &[Format::Cbor, Format::PackedCbor] {
+            let bytes = format.serialize(&role).expect("serializable");
+            let deserialized = serde_cbor::from_slice::<RoleEntity>(&bytes);
+            assert_eq!(role, deserialized.expect("deserializable"));
+        }
+
+        let named = Format::Cbor.serialize(&role).expect("serializable");
+        let packed = Format::PackedCbor.serialize(&role).expect("serializable");
+        assert!(packed.len() < named.len());
+    }
+}
@@ -0,0 +1,422 @@
+//! Minimal client for the Redis serialization protocol.
+//!
+//! A connection is owned by a dedicated thread which performs blocking IO and
+//! sends replies back over oneshot channels, which keeps the backend
+//! independent of any particular async runtime.
+//!
+//! Connecting, reading and writing time out so that an unresponsive server
+//! fails requests instead of blocking the thread, and the connection is
+//! reopened on the next request.
+
+use super::RedisBackendError;
+use futures_channel::oneshot;
+use std::{
+    convert::TryFrom,
+    io::{BufRead, BufReader, Error as IoError, ErrorKind, Result as IoResult, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+/// Time to wait for connecting to the server and for each read and write.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Arguments of a command, including the name of the command.
+pub type Command = Vec<Vec<u8>>;
+
+/// Reply to a command.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    Array(Vec<Value>),
+    Data(Vec<u8>),
+    Error(String),
+    Int(i64),
+    Nil,
+    Status(String),
+}
+
+impl Value {
+    /// Return an error if the value or one of its nested values is an error
+    /// reply.
+    pub fn check(self) -> Result<Self, RedisBackendError> {
+        match self {
+            Self::Error(message) => Err(RedisBackendError::Redis { message }),
+            Self::Array(values) => values
+                .into_iter()
+                .map(Self::check)
+                .collect::<Result<_, _>>()
+                .map(Self::Array),
+            other => Ok(other),
+        }
+    }
+}
+
+/// Function returning the commands to run in a transaction based on the
+/// current value of a watched key, or `None` to not run a transaction.
+pub type Update = Box<dyn FnMut(Value) -> Result<Option<Vec<Command>>, RedisBackendError> + Send>;
+
+enum Request {
+    Pipeline {
+        commands: Vec<Command>,
+        reply: oneshot::Sender<IoResult<Vec<Value>>>,
+    },
+    Update {
+        key: Vec<u8>,
+        update: Update,
+        reply: oneshot::Sender<Result<Option<Vec<Value>>, RedisBackendError>>,
+    },
+}
+
+/// Handle to the thread owning the connection.
+///
+/// The thread exits once all handles are dropped.
+#[derive(Debug)]
+pub struct Connection {
+    requests: Sender<Request>,
+}
+
+impl Connection {
+    /// Connect to a Redis server and spawn the thread owning the connection.
+    pub fn connect(address: String) -> IoResult<Self> {
+        let stream = open(&address)?;
+        let (tx, rx) = mpsc::channel();
+
+        thread::Builder::new()
+            .name(String::from("rarity-cache-redis"))
+            .spawn(move || run(&address, Some(stream), &rx))?;
+
+        Ok(Self { requests: tx })
+    }
+
+    /// Send a pipeline of commands, returning a reply for each command.
+    ///
+    /// Error replies are returned as values, not as errors.
+    pub async fn pipeline(&self, commands: Vec<Command>) -> Result<Vec<Value>, RedisBackendError> {
+        let (tx, rx) = oneshot::channel();
+
+        self.requests
+            .send(Request::Pipeline {
+                commands,
+                reply: tx,
+            })
+            .map_err(|_| RedisBackendError::ConnectionClosed)?;
+
+        rx.await
+            .map_err(|_| RedisBackendError::ConnectionClosed)?
+            .map_err(|source| RedisBackendError::Io { source })
+    }
+
+    /// Send a single command, returning an error if the reply is an error.
+    pub async fn query(&self, command: Command) -> Result<Value, RedisBackendError> {
+        let mut values = self.pipeline(vec![command]).await?;
+
+        values
+            .pop()
+            .ok_or(RedisBackendError::UnexpectedReply)?
+            .check()
+    }
+
+    /// Atomically update the value of a key with an optimistic
+    /// `WATCH`/`MULTI`/`EXEC` transaction.
+    ///
+    /// The update is called with the current value of the key and returns the
+    /// commands to run in the transaction. If the key is modified by another
+    /// client before the transaction runs then the update is retried with the
+    /// new value. No other requests are sent over the connection in the
+    /// meantime.
+    ///
+    /// Returns the replies of the transaction's commands, or `None` if the
+    /// update didn't return any commands.
+    pub async fn update(
+        &self,
+        key: Vec<u8>,
+        update: Update,
+    ) -> Result<Option<Vec<Value>>, RedisBackendError> {
+        let (tx, rx) = oneshot::channel();
+
+        self.requests
+            .send(Request::Update {
+                key,
+                update,
+                reply: tx,
+            })
+            .map_err(|_| RedisBackendError::ConnectionClosed)?;
+
+        rx.await.map_err(|_| RedisBackendError::ConnectionClosed)?
+    }
+}
+
+/// Open a stream to a Redis server with connect, read and write timeouts.
+///
+/// Each address the server's address resolves to is tried in order.
+fn open(address: &str) -> IoResult<TcpStream> {
+    let mut error = None;
+
+    for address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(TIMEOUT))?;
+                stream.set_write_timeout(Some(TIMEOUT))?;
+
+                return Ok(stream);
+            }
+            Err(source) => error = Some(source),
+        }
+    }
+
+    Err(error.unwrap_or_else(|| {
+        IoError::new(
+            ErrorKind::InvalidInput,
+            "address didn't resolve to any addresses",
+        )
+    }))
+}
+
+fn run(address: &str, mut stream: Option<TcpStream>, requests: &Receiver<Request>) {
+    for request in requests {
+        // The stream may be left in an inconsistent state, such as with a
+        // partially read reply, so reconnect on the next request.
+        //
+        // The requester not waiting for the reply anymore is fine.
+        match request {
+            Request::Pipeline { commands, reply } => {
+                let result = execute(address, &mut stream, &commands);
+
+                if result.is_err() {
+                    stream = None;
+                }
+
+                let _ = reply.send(result);
+            }
+            Request::Update {
+                key,
+                mut update,
+                reply,
+            } => {
+                let result = execute_update(address, &mut stream, &key, &mut *update);
+
+                // Error replies are read in full, so only they leave the
+                // stream usable.
+                match result {
+                    Ok(_) | Err(RedisBackendError::Redis { .. }) => {}
+                    Err(_) => stream = None,
+                }
+
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+fn execute(
+    address: &str,
+    stream: &mut Option<TcpStream>,
+    commands: &[Command],
+) -> IoResult<Vec<Value>> {
+    let stream = match stream {
+        Some(stream) => stream,
+        None => stream.insert(open(address)?),
+    };
+
+    let mut buf = Vec::new();
+
+    for command in commands {
+        encode(command, &mut buf);
+    }
+
+    stream.write_all(&buf)?;
+
+    // Redis doesn't send anything other than replies to our commands, so
+    // nothing will be left in the buffer once all replies are read.
+    let mut reader = BufReader::new(&*stream);
+
+    commands.iter().map(|_| decode(&mut reader)).collect()
+}
+
+fn execute_update(
+    address: &str,
+    stream: &mut Option<TcpStream>,
+    key: &[u8],
+    update: &mut (dyn FnMut(Value) -> Result<Option<Vec<Command>>, RedisBackendError> + Send),
+) -> Result<Option<Vec<Value>>, RedisBackendError> {
+    let io = |source| RedisBackendError::Io { source };
+    let watch = [
+        vec![b"WATCH".to_vec(), key.to_vec()],
+        vec![b"GET".to_vec(), key.to_vec()],
+    ];
+
+    loop {
+        let mut values = execute(address, stream, &watch).map_err(io)?.into_iter();
+
+        let commands = match (values.next(), values.next()) {
+            (Some(watched), Some(value)) => {
+                watched.check().and(value.check()).and_then(&mut *update)
+            }
+            _ => Err(RedisBackendError::UnexpectedReply),
+        };
+
+        let commands = match commands {
+            Ok(Some(commands)) => commands,
+            result => {
+                execute(address, stream, &[vec![b"UNWATCH".to_vec()]]).map_err(io)?;
+
+                return result.map(|_| None);
+            }
+        };
+
+        let mut transaction = vec![vec![b"MULTI".to_vec()]];
+        transaction.extend(commands);
+        transaction.push(vec![b"EXEC".to_vec()]);
+
+        let mut values = execute(address, stream, &transaction).map_err(io)?;
+        let exec = values.pop().ok_or(RedisBackendError::UnexpectedReply)?;
+
+        for value in values {
+            value.check()?;
+        }
+
+        match exec.check()? {
+            Value::Array(values) => return Ok(Some(values)),
+            // The key was modified after it was watched, so retry with its
+            // new value.
+            Value::Nil => {}
+            _ => return Err(RedisBackendError::UnexpectedReply),
+        }
+    }
+}
+
+/// Encode a command as an array of bulk strings.
+pub fn encode(command: &[Vec<u8>], buf: &mut Vec<u8>) {
+    buf.extend_from_slice(format!("*{}\r\n", command.len()).as_bytes());
+
+    for arg in command {
+        buf.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+        buf.extend_from_slice(arg);
+        buf.extend_from_slice(b"\r\n");
+    }
+}
+
+/// Decode a single reply.
+pub fn decode(reader: &mut impl BufRead) -> IoResult<Value> {
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line)?;
+
+    if !line.ends_with(b"\r\n") {
+        return Err(invalid("unterminated line"));
+    }
+
+    line.truncate(line.len() - 2);
+
+    let (kind, rest) = line.split_first().ok_or_else(|| invalid("empty line"))?;
+
+    match kind {
+        b'+' => Ok(Value::Status(String::from_utf8_lossy(rest).into_owned())),
+        b'-' => Ok(Value::Error(String::from_utf8_lossy(rest).into_owned())),
+        b':' => parse_int(rest).map(Value::Int),
+        b'$' => {
+            // Negative lengths represent nil replies.
+            let Ok(len) = usize::try_from(parse_int(rest)?) else {
+                return Ok(Value::Nil);
+            };
+
+            let mut data = vec![0; len + 2];
+            reader.read_exact(&mut data)?;
+
+            if !data.ends_with(b"\r\n") {
+                return Err(invalid("unterminated bulk string"));
+            }
+
+            data.truncate(len);
+
+            Ok(Value::Data(data))
+        }
+        b'*' => {
+            let Ok(len) = usize::try_from(parse_int(rest)?) else {
+                return Ok(Value::Nil);
+            };
+
+            (0..len)
+                .map(|_| decode(&mut *reader))
+                .collect::<IoResult<_>>()
+                .map(Value::Array)
+        }
+        _ => Err(invalid("unknown reply type")),
+    }
+}
+
+fn parse_int(bytes: &[u8]) -> IoResult<i64> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| invalid("invalid integer"))
+}
+
+fn invalid(message: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, Value};
+    use std::io::Cursor;
+
+    fn decode_all(bytes: &[u8]) -> Value {
+        decode(&mut Cursor::new(bytes)).expect("valid reply")
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut buf = Vec::new();
+        encode(&[b"GET".to_vec(), b"key".to_vec()], &mut buf);
+
+        assert_eq!(b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n".to_vec(), buf);
+    }
+
+    #[test]
+    fn test_decode_simple() {
+        assert_eq!(Value::Status("OK".to_owned()), decode_all(b"+OK\r\n"));
+        assert_eq!(
+            Value::Error("ERR unknown".to_owned()),
+            decode_all(b"-ERR unknown\r\n")
+        );
+        assert_eq!(Value::Int(-3), decode_all(b":-3\r\n"));
+    }
+
+    #[test]
+    fn test_decode_bulk() {
+        assert_eq!(
+            Value::Data(b"a\r\nb".to_vec()),
+            decode_all(b"$4\r\na\r\nb\r\n")
+        );
+        assert_eq!(Value::Data(Vec::new()), decode_all(b"$0\r\n\r\n"));
+        assert_eq!(Value::Nil, decode_all(b"$-1\r\n"));
+    }
+
+    #[test]
+    fn test_decode_array() {
+        assert_eq!(
+            Value::Array(vec![
+                Value::Data(b"1".to_vec()),
+                Value::Nil,
+                Value::Array(vec![Value::Int(2)]),
+            ]),
+            decode_all(b"*3\r\n$1\r\n1\r\n$-1\r\n*1\r\n:2\r\n")
+        );
+        assert_eq!(Value::Nil, decode_all(b"*-1\r\n"));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(decode(&mut Cursor::new(b"+OK".to_vec())).is_err());
+        assert!(decode(&mut Cursor::new(b"?\r\n".to_vec())).is_err());
+        assert!(decode(&mut Cursor::new(b"$5\r\nab\r\n".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let value = Value::Array(vec![Value::Int(1), Value::Error("ERR".to_owned())]);
+        assert!(value.check().is_err());
+        assert!(Value::Array(vec![Value::Nil]).check().is_ok());
+    }
+}
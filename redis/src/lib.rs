@@ -1,7 +1,632 @@
+//! # rarity-cache-redis
+//!
+//! `rarity-cache-redis` is a backend for `rarity-cache` storing entities in
+//! a Redis server.
+//!
+//! Entities are serialized with a configurable format and stored under keys
+//! prefixed with a configurable namespace, which allows multiple caches to
+//! share a database. Relationships between entities, such as the members of a
+//! guild, are kept in sets so that they can be retrieved without scanning the
+//! database.
+//!
+//! # Examples
+//!
+//! Connect to a local Redis server and get a guild's members:
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use rarity_cache_redis::{
+//!     cache::entity::guild::GuildRepository, RedisBackend, RedisCache, Repository,
+//! };
+//! use twilight_model::id::GuildId;
+//!
+//! # #[tokio::main] async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let backend = RedisBackend::new("127.0.0.1:6379")?;
+//! let cache = RedisCache::with_backend(backend);
+//!
+//! let guild_id = GuildId(123456789012345678);
+//!
+//! if let Some(guild) = cache.guilds.get(guild_id).await? {
+//!     println!("the guild's name is {}", guild.name);
+//! }
+//!
+//! let mut members = cache.guilds.members(guild_id).await?;
+//!
+//! while let Some(member) = members.next().await {
+//!     println!("the member's user id is {}", member?.user_id);
+//! }
+//! # Ok(()) }
+//! ```
+
+#![deny(
+    clippy::all,
+    clippy::pedantic,
+    future_incompatible,
+    nonstandard_style,
+    rust_2018_idioms,
+    unused,
+    warnings
+)]
+#![allow(clippy::module_name_repetitions, clippy::must_use_candidate)]
+
+pub extern crate rarity_cache as cache;
+
+pub mod config;
+pub mod prelude;
+pub mod repository;
+
+mod connection;
+
+#[doc(no_inline)]
+pub use rarity_cache::Repository;
+
+use self::{
+    config::{Config, Format},
+    connection::{Command, Connection, Value},
+    repository::{RedisEntity, RedisRepository},
+};
+use rarity_cache::{
+    entity::{
+        channel::{
+            AttachmentEntity, CategoryChannelEntity, GroupEntity, MessageEntity,
+            PrivateChannelEntity, TextChannelEntity, VoiceChannelEntity, WebhookEntity,
+        },
+        gateway::PresenceEntity,
+        guild::{BanEntity, EmojiEntity, GuildEntity, MemberEntity, RoleEntity},
+        invite::InviteEntity,
+        user::{CurrentUserEntity, UserEntity},
+        voice::VoiceStateEntity,
+        EntityField,
+    },
+    repository::NoopRepository,
+    Backend, Cache,
+};
+use serde::de::DeserializeOwned;
+use serde_cbor::Error as CborError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    io::Error as IoError,
+    sync::Arc,
+};
+
+/// Alias over `rarity_cache::Cache` which uses the [`RedisBackend`].
+///
+/// [`RedisBackend`]: struct.RedisBackend.html
+pub type RedisCache = Cache<RedisBackend>;
+
+/// Error returned from backend operations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RedisBackendError {
+    /// The connection to the Redis server was closed.
+    ConnectionClosed,
+    /// Deserializing an entity failed.
+    Deserializing {
+        /// Reason for the error.
+        source: CborError,
+    },
+    /// Communicating with the Redis server failed.
+    Io {
+        /// Reason for the error.
+        source: IoError,
+    },
+    /// The Redis server returned an error reply.
+    Redis {
+        /// Message of the error reply.
+        message: String,
+    },
+    /// Serializing an entity failed.
+    Serializing {
+        /// Reason for the error.
+        source: CborError,
+    },
+    /// The Redis server returned a reply of an unexpected type.
+    UnexpectedReply,
+}
+
+impl Display for RedisBackendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::ConnectionClosed => f.write_str("the connection to redis was closed"),
+            Self::Deserializing { .. } => f.write_str("failed to deserialize an entity"),
+            Self::Io { .. } => f.write_str("failed to communicate with redis"),
+            Self::Redis { message } => write!(f, "redis returned an error: {message}"),
+            Self::Serializing { .. } => f.write_str("failed to serialize an entity"),
+            Self::UnexpectedReply => f.write_str("redis returned an unexpected reply"),
+        }
+    }
+}
+
+impl Error for RedisBackendError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Deserializing { source } | Self::Serializing { source } => Some(source),
+            Self::Io { source } => Some(source),
+            Self::ConnectionClosed | Self::Redis { .. } | Self::UnexpectedReply => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RedisBackendRef {
+    config: Config,
+    connection: Connection,
+}
+
+/// Builder to create a configured [`RedisBackend`].
+///
+/// [`RedisBackend`]: struct.RedisBackend.html
+#[derive(Clone, Debug)]
+pub struct RedisBackendBuilder(Config);
+
+impl RedisBackendBuilder {
+    /// Create a new builder to connect to the Redis server at the given
+    /// address, such as `127.0.0.1:6379`.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self(Config::new(address))
+    }
+
+    /// Connect to the Redis server and create the backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedisBackendError::Io`] if connecting to the server failed.
+    ///
+    /// [`RedisBackendError::Io`]: enum.RedisBackendError.html#variant.Io
+    pub fn build(self) -> Result<RedisBackend, RedisBackendError> {
+        let connection = Connection::connect(self.0.address().to_owned())
+            .map_err(|source| RedisBackendError::Io { source })?;
+
+        Ok(RedisBackend(Arc::new(RedisBackendRef {
+            config: self.0,
+            connection,
+        })))
+    }
+
+    pub fn format(&mut self, format: Format) -> &mut Self {
+        *self.0.format_mut() = format;
+
+        self
+    }
+
+    pub fn namespace(&mut self, namespace: impl Into<String>) -> &mut Self {
+        *self.0.namespace_mut() = namespace.into();
+
+        self
+    }
+}
+
+/// Backend implementation to cache entities in a Redis server.
+///
+/// Cloning the backend is cheap and clones share the same connection.
+#[derive(Clone, Debug)]
+pub struct RedisBackend(Arc<RedisBackendRef>);
+
+impl RedisBackend {
+    /// Connect to the Redis server at the given address with the default
+    /// configuration.
+    ///
+    /// Read [`Config`] to know the default configuration.
+    ///
+    /// Use [`RedisBackend::builder`] to create a backend from a
+    /// configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rarity_cache_redis::{RedisBackend, RedisCache};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let backend = RedisBackend::new("127.0.0.1:6379")?;
+    /// let cache = RedisCache::with_backend(backend);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RedisBackendError::Io`] if connecting to the server failed.
+    ///
+    /// [`Config`]: config/struct.Config.html
+    /// [`RedisBackend::builder`]: #method.builder
+    /// [`RedisBackendError::Io`]: enum.RedisBackendError.html#variant.Io
+    pub fn new(address: impl Into<String>) -> Result<Self, RedisBackendError> {
+        Self::builder(address).build()
+    }
+
+    /// Create a builder to create a configured Redis backend.
+    ///
+    /// # Examples
+    ///
+    /// Create a backend which stores packed records under the `bot` namespace:
+    ///
+    /// ```no_run
+    /// use rarity_cache_redis::{config::Format, RedisBackend};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut builder = RedisBackend::builder("127.0.0.1:6379");
+    /// builder.format(Format::PackedCbor).namespace("bot");
+    ///
+    /// let backend = builder.build()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn builder(address: impl Into<String>) -> RedisBackendBuilder {
+        RedisBackendBuilder::new(address)
+    }
+
+    /// Return a copy of the backend configuration.
+    pub fn config(&self) -> Config {
+        self.0.config.clone()
+    }
+
+    fn key(&self, key: &str) -> Vec<u8> {
+        format!("{}:{}", self.0.config.namespace(), key).into_bytes()
+    }
+
+    fn entity_key<T: RedisEntity>(&self, id: &str) -> Vec<u8> {
+        self.key(&format!("{}:{}", T::PREFIX, id))
+    }
+
+    fn ids_key<T: RedisEntity>(&self) -> Vec<u8> {
+        self.key(&format!("{}:ids", T::PREFIX))
+    }
+
+    async fn get_entity<T: RedisEntity>(&self, id: String) -> Result<Option<T>, RedisBackendError> {
+        let value = self
+            .0
+            .connection
+            .query(vec![b"GET".to_vec(), self.entity_key::<T>(&id)])
+            .await?;
+
+        deserialize(value)
+    }
+
+    async fn get_entities<T: RedisEntity>(
+        &self,
+        ids: Vec<String>,
+    ) -> Result<Vec<T>, RedisBackendError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut command = vec![b"MGET".to_vec()];
+        command.extend(ids.iter().map(|id| self.entity_key::<T>(id)));
+
+        match self.0.connection.query(command).await? {
+            Value::Array(values) => values
+                .into_iter()
+                .filter_map(|value| deserialize(value).transpose())
+                .collect(),
+            _ => Err(RedisBackendError::UnexpectedReply),
+        }
+    }
+
+    async fn list_entities<T: RedisEntity>(&self) -> Result<Vec<T>, RedisBackendError> {
+        let ids = self.members(self.ids_key::<T>()).await?;
+
+        self.get_entities(ids).await
+    }
+
+    async fn set_members(&self, key: &str) -> Result<Vec<String>, RedisBackendError> {
+        self.members(self.key(key)).await
+    }
+
+    /// Add a member to a set, or remove it from the set.
+    async fn update_set(
+        &self,
+        key: &str,
+        member: String,
+        add: bool,
+    ) -> Result<(), RedisBackendError> {
+        let command: &[u8] = if add { b"SADD" } else { b"SREM" };
+
+        self.0
+            .connection
+            .query(vec![command.to_vec(), self.key(key), member.into_bytes()])
+            .await
+            .map(drop)
+    }
+
+    async fn members(&self, key: Vec<u8>) -> Result<Vec<String>, RedisBackendError> {
+        match self
+            .0
+            .connection
+            .query(vec![b"SMEMBERS".to_vec(), key])
+            .await?
+        {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Data(bytes) => {
+                        String::from_utf8(bytes).map_err(|_| RedisBackendError::UnexpectedReply)
+                    }
+                    _ => Err(RedisBackendError::UnexpectedReply),
+                })
+                .collect(),
+            _ => Err(RedisBackendError::UnexpectedReply),
+        }
+    }
+
+    /// Remove an entity along with its index set memberships.
+    ///
+    /// The entity's old indexes are read and removed in a single optimistic
+    /// transaction, so a concurrent update can't leave stale index members.
+    async fn remove_entity<T: RedisEntity>(&self, id: String) -> Result<(), RedisBackendError> {
+        let key = self.entity_key::<T>(&id);
+        let backend = self.clone();
+
+        let update = Box::new(move |value| {
+            let old = deserialize::<T>(value)?;
+
+            let mut commands = vec![
+                vec![b"DEL".to_vec(), backend.entity_key::<T>(&id)],
+                vec![
+                    b"SREM".to_vec(),
+                    backend.ids_key::<T>(),
+                    id.clone().into_bytes(),
+                ],
+            ];
+
+            for (key, member) in old.map(|old| old.indexes()).unwrap_or_default() {
+                commands.push(vec![
+                    b"SREM".to_vec(),
+                    backend.key(&key),
+                    member.into_bytes(),
+                ]);
+            }
+
+            Ok(Some(commands))
+        });
+
+        self.0.connection.update(key, update).await.map(|_| ())
+    }
+
+    /// Insert or replace an entity, moving it from its old index sets to its
+    /// new ones.
+    ///
+    /// Like [`remove_entity`], the old indexes are read and replaced in a
+    /// single optimistic transaction.
+    ///
+    /// [`remove_entity`]: #method.remove_entity
+    async fn upsert_entity<T: RedisEntity>(&self, entity: T) -> Result<(), RedisBackendError> {
+        let id = T::key_id(entity.id());
+        let bytes = self
+            .0
+            .config
+            .format()
+            .serialize(&entity)
+            .map_err(|source| RedisBackendError::Serializing { source })?;
+        let indexes = entity.indexes();
+        let key = self.entity_key::<T>(&id);
+        let backend = self.clone();
+
+        let update = Box::new(move |value| {
+            let old = deserialize::<T>(value)?;
+
+            let mut commands = vec![
+                vec![b"SET".to_vec(), backend.entity_key::<T>(&id), bytes.clone()],
+                vec![
+                    b"SADD".to_vec(),
+                    backend.ids_key::<T>(),
+                    id.clone().into_bytes(),
+                ],
+            ];
+            commands.extend(backend.index_commands(old.map(|old| old.indexes()), indexes.clone()));
+
+            Ok(Some(commands))
+        });
+
+        self.0.connection.update(key, update).await.map(|_| ())
+    }
+
+    /// Apply a field update to an entity in place, returning whether the
+    /// entity exists.
+    ///
+    /// The entity is read and written in a single optimistic transaction, so
+    /// concurrent updates of the entity from other clients aren't lost.
+    async fn update_entity<T: RedisEntity, F: EntityField<T>>(
+        &self,
+        id: String,
+        field: F,
+    ) -> Result<bool, RedisBackendError> {
+        let key = self.entity_key::<T>(&id);
+        let backend = self.clone();
+
+        let update = Box::new(move |value| {
+            let Some(mut entity) = deserialize::<T>(value)? else {
+                return Ok(None);
+            };
+
+            let old_indexes = entity.indexes();
+            field.clone().apply(&mut entity);
+
+            let bytes = backend
+                .0
+                .config
+                .format()
+                .serialize(&entity)
+                .map_err(|source| RedisBackendError::Serializing { source })?;
+
+            let mut commands = vec![vec![b"SET".to_vec(), backend.entity_key::<T>(&id), bytes]];
+            commands.extend(backend.index_commands(Some(old_indexes), entity.indexes()));
+
+            Ok(Some(commands))
+        });
+
+        Ok(self.0.connection.update(key, update).await?.is_some())
+    }
+
+    /// Commands to move an entity from its old index sets to its new ones.
+    fn index_commands(
+        &self,
+        old: Option<Vec<(String, String)>>,
+        new: Vec<(String, String)>,
+    ) -> Vec<Command> {
+        let mut commands = Vec::new();
+
+        for (key, member) in old.unwrap_or_default() {
+            if !new.contains(&(key.clone(), member.clone())) {
+                commands.push(vec![b"SREM".to_vec(), self.key(&key), member.into_bytes()]);
+            }
+        }
+
+        for (key, member) in new {
+            commands.push(vec![b"SADD".to_vec(), self.key(&key), member.into_bytes()]);
+        }
+
+        commands
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(value: Value) -> Result<Option<T>, RedisBackendError> {
+    match value {
+        Value::Data(bytes) => serde_cbor::from_slice(&bytes)
+            .map(Some)
+            .map_err(|source| RedisBackendError::Deserializing { source }),
+        Value::Nil => Ok(None),
+        _ => Err(RedisBackendError::UnexpectedReply),
+    }
+}
+
+/// Redis implementation of a `rarity_cache` backend.
+///
+/// **Note**: you should probably not be using the trait's methods directly, and
+/// should wrap a backend instance in `rarity_cache`'s `Cache` and use its
+/// methods and fields instead.
+impl Backend for RedisBackend {
+    type Error = RedisBackendError;
+    type AttachmentRepository = RedisRepository<AttachmentEntity>;
+    type BanRepository = RedisRepository<BanEntity>;
+    type CategoryChannelRepository = RedisRepository<CategoryChannelEntity>;
+    type CurrentUserRepository = RedisRepository<CurrentUserEntity>;
+    type EmojiRepository = RedisRepository<EmojiEntity>;
+    type GroupRepository = RedisRepository<GroupEntity>;
+    type GuildRepository = RedisRepository<GuildEntity>;
+    type InviteRepository = RedisRepository<InviteEntity>;
+    type MemberRepository = RedisRepository<MemberEntity>;
+    type MessageRepository = RedisRepository<MessageEntity>;
+    type PresenceRepository = RedisRepository<PresenceEntity>;
+    type PrivateChannelRepository = RedisRepository<PrivateChannelEntity>;
+    type RoleRepository = RedisRepository<RoleEntity>;
+    type TextChannelRepository = RedisRepository<TextChannelEntity>;
+    /// Typing states are too short-lived to be worth a round trip, so they
+    /// aren't stored.
+    type TypingRepository = NoopRepository<Self>;
+    type UserRepository = RedisRepository<UserEntity>;
+    type VoiceChannelRepository = RedisRepository<VoiceChannelEntity>;
+    type VoiceStateRepository = RedisRepository<VoiceStateEntity>;
+    type WebhookRepository = RedisRepository<WebhookEntity>;
+
+    /// A new instance of a repository for working with attachments.
+    fn attachments(&self) -> Self::AttachmentRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with bans.
+    fn bans(&self) -> Self::BanRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with guild category channels.
+    fn category_channels(&self) -> Self::CategoryChannelRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with the current user.
+    fn current_user(&self) -> Self::CurrentUserRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with emojis.
+    fn emojis(&self) -> Self::EmojiRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with groups.
+    fn groups(&self) -> Self::GroupRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with guilds.
+    fn guilds(&self) -> Self::GuildRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with invites.
+    fn invites(&self) -> Self::InviteRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with members.
+    fn members(&self) -> Self::MemberRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with messages.
+    fn messages(&self) -> Self::MessageRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with presences.
+    fn presences(&self) -> Self::PresenceRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with private channels.
+    fn private_channels(&self) -> Self::PrivateChannelRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with roles.
+    fn roles(&self) -> Self::RoleRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with guild text channels.
+    fn text_channels(&self) -> Self::TextChannelRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    fn typing(&self) -> Self::TypingRepository {
+        NoopRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with users.
+    fn users(&self) -> Self::UserRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with guild voice channels.
+    fn voice_channels(&self) -> Self::VoiceChannelRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with voice states.
+    fn voice_states(&self) -> Self::VoiceStateRepository {
+        RedisRepository::new(self.clone())
+    }
+
+    /// A new instance of a repository for working with webhooks.
+    fn webhooks(&self) -> Self::WebhookRepository {
+        RedisRepository::new(self.clone())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
+    use super::{RedisBackend, RedisBackendBuilder, RedisBackendError, RedisCache};
+    use rarity_cache::Backend;
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{error::Error, fmt::Debug};
+
+    assert_impl_all!(RedisBackendBuilder: Clone, Debug, Send, Sync);
+    assert_impl_all!(RedisBackendError: Debug, Error, Send, Sync);
+    assert_impl_all!(RedisBackend: Backend, Clone, Debug, Send, Sync);
+    assert_impl_all!(RedisCache: Clone, Debug, Send, Sync);
+    assert_obj_safe!(
+        RedisBackendBuilder,
+        RedisBackendError,
+        RedisBackend,
+        RedisCache
+    );
 }
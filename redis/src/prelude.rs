@@ -0,0 +1,6 @@
+//! Useful re-exports for working with the Redis cache.
+
+#[doc(no_inline)]
+pub use super::{RedisBackend, RedisBackendError, RedisCache};
+#[doc(no_inline)]
+pub use rarity_cache::{Backend, Cache, Repository};
@@ -0,0 +1,622 @@
+use super::{RedisBackend, RedisBackendError};
+use futures_util::{
+    future::FutureExt,
+    stream::{self, StreamExt},
+};
+use rarity_cache::{
+    entity::{
+        channel::{
+            AttachmentEntity, AttachmentRepository, CategoryChannelEntity,
+            CategoryChannelRepository, GroupEntity, GroupRepository, GuildChannelEntity,
+            MessageEntity, MessageRepository, PrivateChannelEntity, PrivateChannelRepository,
+            TextChannelEntity, TextChannelRepository, VoiceChannelEntity, VoiceChannelRepository,
+            WebhookEntity, WebhookRepository,
+        },
+        gateway::{PresenceEntity, PresenceRepository},
+        guild::{
+            BanEntity, BanRepository, EmojiEntity, EmojiRepository, GuildEntity, GuildRepository,
+            MemberEntity, MemberRepository, RoleEntity, RoleRepository,
+        },
+        invite::{InviteEntity, InviteRepository},
+        user::{CurrentUserEntity, CurrentUserRepository, UserEntity, UserRepository},
+        voice::{VoiceStateEntity, VoiceStateRepository},
+        Entity, EntityField,
+    },
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, RemoveEntityFuture,
+        UpdateEntityFuture, UpsertEntityFuture,
+    },
+    Repository,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+use twilight_model::id::{
+    AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId, WebhookId,
+};
+
+/// Entity which can be stored in Redis.
+///
+/// Entities are stored as a string key of `{namespace}:{prefix}:{id}`, and the
+/// IDs of all stored entities of a type are stored in the set
+/// `{namespace}:{prefix}:ids`.
+pub trait RedisEntity: DeserializeOwned + Entity + Serialize + Sync + 'static {
+    /// Prefix of the keys of the entity type, such as `g` for guilds.
+    const PREFIX: &'static str;
+
+    /// Format the ID of an entity for use in keys and index sets.
+    fn key_id(id: Self::Id) -> String;
+
+    /// Relationship index sets that the entity is a member of, as pairs of the
+    /// set's key (without the namespace) and the member.
+    ///
+    /// These are kept in sync when the entity is upserted or removed, and back
+    /// methods such as [`GuildRepository::member_ids`].
+    ///
+    /// [`GuildRepository::member_ids`]: ../../rarity_cache/entity/guild/trait.GuildRepository.html#tymethod.member_ids
+    fn indexes(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+fn guild_channel_indexes(guild_id: Option<GuildId>, id: ChannelId) -> Vec<(String, String)> {
+    guild_id
+        .map(|guild_id| (format!("g:{guild_id}:channels"), id.to_string()))
+        .into_iter()
+        .collect()
+}
+
+impl RedisEntity for AttachmentEntity {
+    const PREFIX: &'static str = "at";
+
+    fn key_id(id: AttachmentId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for BanEntity {
+    const PREFIX: &'static str = "b";
+
+    fn key_id((guild_id, user_id): (GuildId, UserId)) -> String {
+        format!("{guild_id}:{user_id}")
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(
+            format!("g:{}:bans", self.guild_id),
+            self.user_id.to_string(),
+        )]
+    }
+}
+
+impl RedisEntity for CategoryChannelEntity {
+    const PREFIX: &'static str = "cc";
+
+    fn key_id(id: ChannelId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
+}
+
+impl RedisEntity for CurrentUserEntity {
+    const PREFIX: &'static str = "uc";
+
+    fn key_id(id: UserId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for EmojiEntity {
+    const PREFIX: &'static str = "em";
+
+    fn key_id(id: EmojiId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(format!("g:{}:emojis", self.guild_id), self.id.to_string())]
+    }
+}
+
+impl RedisEntity for GroupEntity {
+    const PREFIX: &'static str = "gr";
+
+    fn key_id(id: ChannelId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for GuildEntity {
+    const PREFIX: &'static str = "g";
+
+    fn key_id(id: GuildId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for InviteEntity {
+    const PREFIX: &'static str = "i";
+
+    fn key_id(code: String) -> String {
+        code
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![
+            (format!("c:{}:invites", self.channel_id), self.code.clone()),
+            (format!("g:{}:invites", self.guild_id), self.code.clone()),
+        ]
+    }
+}
+
+impl RedisEntity for MemberEntity {
+    const PREFIX: &'static str = "m";
+
+    fn key_id((guild_id, user_id): (GuildId, UserId)) -> String {
+        format!("{guild_id}:{user_id}")
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                format!("g:{}:members", self.guild_id),
+                self.user_id.to_string(),
+            ),
+            (
+                format!("u:{}:guilds", self.user_id),
+                self.guild_id.to_string(),
+            ),
+        ]
+    }
+}
+
+impl RedisEntity for MessageEntity {
+    const PREFIX: &'static str = "ms";
+
+    fn key_id(id: MessageId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(
+            format!("c:{}:messages", self.channel_id),
+            self.id.to_string(),
+        )]
+    }
+}
+
+impl RedisEntity for PresenceEntity {
+    const PREFIX: &'static str = "pr";
+
+    fn key_id((guild_id, user_id): (GuildId, UserId)) -> String {
+        format!("{guild_id}:{user_id}")
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(
+            format!("g:{}:presences", self.guild_id),
+            self.user_id.to_string(),
+        )]
+    }
+}
+
+impl RedisEntity for PrivateChannelEntity {
+    const PREFIX: &'static str = "cp";
+
+    fn key_id(id: ChannelId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for RoleEntity {
+    const PREFIX: &'static str = "r";
+
+    fn key_id(id: RoleId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(format!("g:{}:roles", self.guild_id), self.id.to_string())]
+    }
+}
+
+impl RedisEntity for TextChannelEntity {
+    const PREFIX: &'static str = "ct";
+
+    fn key_id(id: ChannelId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
+}
+
+impl RedisEntity for UserEntity {
+    const PREFIX: &'static str = "u";
+
+    fn key_id(id: UserId) -> String {
+        id.to_string()
+    }
+}
+
+impl RedisEntity for VoiceChannelEntity {
+    const PREFIX: &'static str = "cv";
+
+    fn key_id(id: ChannelId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
+}
+
+impl RedisEntity for VoiceStateEntity {
+    const PREFIX: &'static str = "v";
+
+    fn key_id((guild_id, user_id): (GuildId, UserId)) -> String {
+        format!("{guild_id}:{user_id}")
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        vec![(
+            format!("g:{}:voice_states", self.guild_id),
+            self.user_id.to_string(),
+        )]
+    }
+}
+
+impl RedisEntity for WebhookEntity {
+    const PREFIX: &'static str = "wh";
+
+    fn key_id(id: WebhookId) -> String {
+        id.to_string()
+    }
+
+    fn indexes(&self) -> Vec<(String, String)> {
+        let mut indexes = vec![(
+            format!("c:{}:webhooks", self.channel_id),
+            self.id.to_string(),
+        )];
+
+        if let Some(guild_id) = self.guild_id {
+            indexes.push((format!("g:{guild_id}:webhooks"), self.id.to_string()));
+        }
+
+        indexes
+    }
+}
+
+/// Repository for working with entities of a type in Redis.
+#[derive(Clone, Debug)]
+pub struct RedisRepository<T>(RedisBackend, PhantomData<T>);
+
+impl<T> RedisRepository<T> {
+    pub(crate) fn new(backend: RedisBackend) -> Self {
+        Self(backend, PhantomData)
+    }
+}
+
+impl<T: RedisEntity> Repository<T, RedisBackend> for RedisRepository<T> {
+    fn backend(&self) -> RedisBackend {
+        self.0.clone()
+    }
+
+    fn get(&self, entity_id: T::Id) -> GetEntityFuture<'_, T, RedisBackendError> {
+        Box::pin(self.0.get_entity(T::key_id(entity_id)))
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, T, RedisBackendError> {
+        self.0
+            .list_entities()
+            .map(|result| result.map(|entities| stream::iter(entities.into_iter().map(Ok)).boxed()))
+            .boxed()
+    }
+
+    fn remove(&self, entity_id: T::Id) -> RemoveEntityFuture<'_, RedisBackendError> {
+        Box::pin(self.0.remove_entity::<T>(T::key_id(entity_id)))
+    }
+
+    fn update_field<F: EntityField<T>>(
+        &self,
+        entity_id: T::Id,
+        field: F,
+    ) -> UpdateEntityFuture<'_, RedisBackendError> {
+        Box::pin(self.0.update_entity(T::key_id(entity_id), field))
+    }
+
+    fn upsert(&self, entity: T) -> UpsertEntityFuture<'_, RedisBackendError> {
+        Box::pin(self.0.upsert_entity(entity))
+    }
+}
+
+/// Stream the IDs in an index set, parsing them with the given function.
+fn index_ids<'a, T: Send + 'a>(
+    backend: &'a RedisBackend,
+    key: String,
+    f: fn(u64) -> T,
+) -> ListEntityIdsFuture<'a, T, RedisBackendError> {
+    Box::pin(async move {
+        let members = backend.set_members(&key).await?;
+
+        Ok(stream::iter(members.into_iter().map(move |member| {
+            member
+                .parse()
+                .map(f)
+                .map_err(|_| RedisBackendError::UnexpectedReply)
+        }))
+        .boxed())
+    })
+}
+
+/// Stream the guild-scoped entities, such as members, whose user IDs are in an
+/// index set.
+fn index_guild_entities<T: RedisEntity>(
+    backend: &RedisBackend,
+    guild_id: GuildId,
+    key: String,
+) -> ListEntitiesFuture<'_, T, RedisBackendError> {
+    Box::pin(async move {
+        let ids = backend
+            .set_members(&key)
+            .await?
+            .into_iter()
+            .map(|user_id| format!("{guild_id}:{user_id}"))
+            .collect();
+        let entities = backend.get_entities::<T>(ids).await?;
+
+        Ok(stream::iter(entities.into_iter().map(Ok)).boxed())
+    })
+}
+
+/// Stream the entities whose keyed IDs, such as invite codes, are in an index
+/// set.
+fn index_entities<T: RedisEntity>(
+    backend: &RedisBackend,
+    key: String,
+) -> ListEntitiesFuture<'_, T, RedisBackendError> {
+    Box::pin(async move {
+        let ids = backend.set_members(&key).await?;
+        let entities = backend.get_entities::<T>(ids).await?;
+
+        Ok(stream::iter(entities.into_iter().map(Ok)).boxed())
+    })
+}
+
+impl AttachmentRepository<RedisBackend> for RedisRepository<AttachmentEntity> {}
+
+impl BanRepository<RedisBackend> for RedisRepository<BanEntity> {
+    fn bans(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, BanEntity, RedisBackendError> {
+        index_guild_entities(&self.0, guild_id, format!("g:{guild_id}:bans"))
+    }
+}
+
+impl CategoryChannelRepository<RedisBackend> for RedisRepository<CategoryChannelEntity> {}
+
+impl CurrentUserRepository<RedisBackend> for RedisRepository<CurrentUserEntity> {
+    fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, RedisBackendError> {
+        index_ids(&self.0, format!("{}:ids", GuildEntity::PREFIX), GuildId)
+    }
+}
+
+impl EmojiRepository<RedisBackend> for RedisRepository<EmojiEntity> {}
+
+impl GroupRepository<RedisBackend> for RedisRepository<GroupEntity> {}
+
+impl GuildRepository<RedisBackend> for RedisRepository<GuildEntity> {
+    fn channel_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, ChannelId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:channels"), ChannelId)
+    }
+
+    fn channels(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, GuildChannelEntity, RedisBackendError> {
+        let backend = &self.0;
+
+        Box::pin(async move {
+            let ids = backend
+                .set_members(&format!("g:{guild_id}:channels"))
+                .await?;
+
+            let categories = backend
+                .get_entities::<CategoryChannelEntity>(ids.clone())
+                .await?;
+            let texts = backend
+                .get_entities::<TextChannelEntity>(ids.clone())
+                .await?;
+            let voices = backend.get_entities::<VoiceChannelEntity>(ids).await?;
+
+            let channels = categories
+                .into_iter()
+                .map(GuildChannelEntity::Category)
+                .chain(texts.into_iter().map(GuildChannelEntity::Text))
+                .chain(voices.into_iter().map(GuildChannelEntity::Voice))
+                .map(Ok);
+
+            Ok(stream::iter(channels).boxed())
+        })
+    }
+
+    fn emoji_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, EmojiId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:emojis"), EmojiId)
+    }
+
+    fn member_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, UserId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:members"), UserId)
+    }
+
+    fn members(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, MemberEntity, RedisBackendError> {
+        index_guild_entities(&self.0, guild_id, format!("g:{guild_id}:members"))
+    }
+
+    fn presence_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, UserId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:presences"), UserId)
+    }
+
+    fn presences(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, PresenceEntity, RedisBackendError> {
+        index_guild_entities(&self.0, guild_id, format!("g:{guild_id}:presences"))
+    }
+
+    fn role_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, RoleId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:roles"), RoleId)
+    }
+
+    fn voice_state_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, UserId, RedisBackendError> {
+        index_ids(&self.0, format!("g:{guild_id}:voice_states"), UserId)
+    }
+
+    fn voice_states(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, VoiceStateEntity, RedisBackendError> {
+        index_guild_entities(&self.0, guild_id, format!("g:{guild_id}:voice_states"))
+    }
+}
+
+impl InviteRepository<RedisBackend> for RedisRepository<InviteEntity> {
+    fn channel_invites(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, RedisBackendError> {
+        index_entities(&self.0, format!("c:{channel_id}:invites"))
+    }
+
+    fn guild_invites(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, InviteEntity, RedisBackendError> {
+        index_entities(&self.0, format!("g:{guild_id}:invites"))
+    }
+}
+
+impl MemberRepository<RedisBackend> for RedisRepository<MemberEntity> {}
+
+impl MessageRepository<RedisBackend> for RedisRepository<MessageEntity> {}
+
+impl PresenceRepository<RedisBackend> for RedisRepository<PresenceEntity> {}
+
+impl PrivateChannelRepository<RedisBackend> for RedisRepository<PrivateChannelEntity> {}
+
+impl RoleRepository<RedisBackend> for RedisRepository<RoleEntity> {}
+
+impl TextChannelRepository<RedisBackend> for RedisRepository<TextChannelEntity> {
+    fn message_ids(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntityIdsFuture<'_, MessageId, RedisBackendError> {
+        index_ids(&self.0, format!("c:{channel_id}:messages"), MessageId)
+    }
+}
+
+impl UserRepository<RedisBackend> for RedisRepository<UserEntity> {
+    fn guild_ids(&self, user_id: UserId) -> ListEntityIdsFuture<'_, GuildId, RedisBackendError> {
+        index_ids(&self.0, format!("u:{user_id}:guilds"), GuildId)
+    }
+}
+
+impl VoiceChannelRepository<RedisBackend> for RedisRepository<VoiceChannelEntity> {}
+
+impl VoiceStateRepository<RedisBackend> for RedisRepository<VoiceStateEntity> {}
+
+impl WebhookRepository<RedisBackend> for RedisRepository<WebhookEntity> {
+    fn channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, RedisBackendError> {
+        index_entities(&self.0, format!("c:{channel_id}:webhooks"))
+    }
+
+    fn guild_webhooks(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, RedisBackendError> {
+        index_entities(&self.0, format!("g:{guild_id}:webhooks"))
+    }
+
+    fn set_stale(
+        &self,
+        channel_id: ChannelId,
+        stale: bool,
+    ) -> UpsertEntityFuture<'_, RedisBackendError> {
+        Box::pin(
+            self.0
+                .update_set("c:webhooks_stale", channel_id.to_string(), stale),
+        )
+    }
+
+    fn stale_channel_ids(&self) -> ListEntityIdsFuture<'_, ChannelId, RedisBackendError> {
+        index_ids(&self.0, "c:webhooks_stale".to_owned(), ChannelId)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RedisEntity, RedisRepository};
+    use crate::RedisBackend;
+    use rarity_cache::{
+        entity::{
+            guild::{GuildEntity, GuildRepository, MemberEntity, MemberRepository},
+            user::{UserEntity, UserRepository},
+        },
+        Repository,
+    };
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::fmt::Debug;
+    use twilight_model::id::{GuildId, UserId};
+
+    assert_impl_all!(
+        RedisRepository<MemberEntity>:
+        MemberRepository<RedisBackend>,
+        Clone,
+        Debug,
+        Repository<MemberEntity, RedisBackend>,
+        Send,
+        Sync,
+    );
+    assert_impl_all!(RedisRepository<GuildEntity>: GuildRepository<RedisBackend>, Send, Sync);
+    assert_impl_all!(RedisRepository<UserEntity>: UserRepository<RedisBackend>, Send, Sync);
+    assert_obj_safe!(RedisRepository<UserEntity>);
+
+    #[test]
+    fn test_member_keys() {
+        let member = MemberEntity {
+            deaf: false,
+            guild_id: GuildId(1),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids: Vec::new(),
+            user_id: UserId(2),
+        };
+
+        assert_eq!("1:2", MemberEntity::key_id((GuildId(1), UserId(2))));
+        assert_eq!(
+            vec![
+                ("g:1:members".to_owned(), "2".to_owned()),
+                ("u:2:guilds".to_owned(), "1".to_owned()),
+            ],
+            member.indexes()
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! Integration tests running against a local `redis-server`.
+//!
+//! Each test starts its own server on a free port. The tests are ignored by
+//! default since they need the `redis-server` binary; run them with
+//! `cargo test -p rarity-cache-redis -- --ignored`.
+
+use futures_util::stream::TryStreamExt;
+use rarity_cache::entity::{
+    channel::{
+        TextChannelEntity, TextChannelRepository, VoiceChannelEntity, VoiceChannelRepository,
+        WebhookEntity, WebhookRepository,
+    },
+    guild::{GuildRepository, MemberEntity, MemberField, MemberRepository, RoleEntity},
+    invite::{InviteEntity, InviteRepository},
+    user::{CurrentUserRepository, UserEntity, UserRepository},
+    voice::VoiceStateEntity,
+};
+use rarity_cache_redis::{config::Format, RedisBackend, RedisCache, Repository};
+use std::{
+    collections::HashMap,
+    net::{TcpListener, TcpStream},
+    process::{Child, Command, Stdio},
+    thread,
+    time::Duration,
+};
+use twilight_model::{
+    channel::{
+        message::{Message, MessageType},
+        Attachment, ChannelType, WebhookType,
+    },
+    gateway::{
+        event::Event,
+        payload::{MessageCreate, UserUpdate},
+    },
+    guild::Permissions,
+    id::{AttachmentId, ChannelId, GuildId, MessageId, RoleId, UserId, WebhookId},
+    user::{CurrentUser, User},
+};
+
+struct Server {
+    address: String,
+    process: Child,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+fn server() -> Server {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port();
+
+    let process = Command::new("redis-server")
+        .args([
+            "--port",
+            &port.to_string(),
+            "--save",
+            "",
+            "--appendonly",
+            "no",
+        ])
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("failed to start redis-server");
+
+    let server = Server {
+        address: format!("127.0.0.1:{port}"),
+        process,
+    };
+
+    for _ in 0..100 {
+        if TcpStream::connect(&server.address).is_ok() {
+            return server;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    panic!("redis-server didn't start listening");
+}
+
+fn member(guild_id: u64, user_id: u64) -> MemberEntity {
+    MemberEntity {
+        deaf: false,
+        guild_id: GuildId(guild_id),
+        hoisted_role_id: None,
+        joined_at: None,
+        mute: false,
+        nick: None,
+        premium_since: None,
+        role_ids: Vec::new(),
+        user_id: UserId(user_id),
+    }
+}
+
+fn role(guild_id: u64, id: u64) -> RoleEntity {
+    RoleEntity {
+        color: 0,
+        guild_id: GuildId(guild_id),
+        hoist: false,
+        id: RoleId(id),
+        managed: false,
+        mentionable: false,
+        name: "role".to_owned(),
+        permissions: Permissions::SEND_MESSAGES,
+        position: 0,
+    }
+}
+
+fn text_channel(guild_id: Option<u64>, id: u64) -> TextChannelEntity {
+    TextChannelEntity {
+        guild_id: guild_id.map(GuildId),
+        id: ChannelId(id),
+        kind: ChannelType::GuildText,
+        last_message_id: None,
+        last_pin_timestamp: None,
+        name: "general".to_owned(),
+        nsfw: false,
+        permission_overwrites: Vec::new(),
+        parent_id: None,
+        position: 0,
+        rate_limit_per_user: None,
+        topic: None,
+    }
+}
+
+fn user(id: u64) -> User {
+    User {
+        avatar: None,
+        bot: false,
+        discriminator: "0001".to_owned(),
+        email: None,
+        flags: None,
+        id: UserId(id),
+        locale: None,
+        mfa_enabled: None,
+        name: "test".to_owned(),
+        premium_type: None,
+        public_flags: None,
+        system: None,
+        verified: None,
+    }
+}
+
+fn message(id: u64, channel_id: u64, attachment_id: u64) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        attachments: vec![Attachment {
+            filename: "image.png".to_owned(),
+            height: None,
+            id: AttachmentId(attachment_id),
+            proxy_url: "https://media.example.com/image.png".to_owned(),
+            size: 1024,
+            url: "https://cdn.example.com/image.png".to_owned(),
+            width: None,
+        }],
+        author: user(1),
+        channel_id: ChannelId(channel_id),
+        content: "ping".to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: None,
+        guild_id: None,
+        id: MessageId(id),
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: HashMap::new(),
+        pinned: false,
+        reactions: Vec::new(),
+        reference: None,
+        stickers: Vec::new(),
+        timestamp: "2020-01-01T00:00:00+00:00".to_owned(),
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_upsert_get_remove() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    assert!(cache.users.get(UserId(1)).await?.is_none());
+
+    let entity = UserEntity::from(user(1));
+    cache.users.upsert(entity.clone()).await?;
+    cache.users.upsert(UserEntity::from(user(2))).await?;
+    assert_eq!(Some(entity), cache.users.get(UserId(1)).await?);
+
+    let mut ids = cache
+        .users
+        .list()
+        .await?
+        .map_ok(|user| user.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    ids.sort();
+    assert_eq!(vec![UserId(1), UserId(2)], ids);
+
+    cache.users.remove(UserId(1)).await?;
+    assert!(cache.users.get(UserId(1)).await?.is_none());
+    assert_eq!(
+        1,
+        cache
+            .users
+            .list()
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .len()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_guild_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    cache.members.upsert(member(1, 4)).await?;
+    cache.members.upsert(member(5, 4)).await?;
+    cache.roles.upsert(role(1, 2)).await?;
+    cache.text_channels.upsert(text_channel(Some(1), 3)).await?;
+
+    let member_ids = cache
+        .guilds
+        .member_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![UserId(4)], member_ids);
+
+    let members = cache
+        .guilds
+        .members(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![member(1, 4)], members);
+
+    let role_ids = cache
+        .guilds
+        .role_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![RoleId(2)], role_ids);
+
+    let channels = cache
+        .guilds
+        .channels(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(1, channels.len());
+
+    let mut guild_ids = cache
+        .users
+        .guild_ids(UserId(4))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    guild_ids.sort();
+    assert_eq!(vec![GuildId(1), GuildId(5)], guild_ids);
+
+    // moving the channel to another guild moves it between indexes
+    cache.text_channels.upsert(text_channel(Some(6), 3)).await?;
+    let channel_ids = cache
+        .guilds
+        .channel_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(channel_ids.is_empty());
+    let channel_ids = cache
+        .guilds
+        .channel_ids(GuildId(6))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![ChannelId(3)], channel_ids);
+
+    cache.members.remove((GuildId(1), UserId(4))).await?;
+    let member_ids = cache
+        .guilds
+        .member_ids(GuildId(1))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert!(member_ids.is_empty());
+    let guild_ids = cache
+        .users
+        .guild_ids(UserId(4))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![GuildId(5)], guild_ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_role_hierarchy() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    let mut high = role(1, 2);
+    high.position = 2;
+    let mut low = role(1, 3);
+    low.color = 0x00ff_0000;
+    low.position = 1;
+    let mut member = member(1, 4);
+    member.role_ids = vec![RoleId(3), RoleId(2)];
+
+    cache
+        .roles
+        .upsert_bulk(vec![high.clone(), low].into_iter())
+        .await?;
+    cache.members.upsert(member).await?;
+
+    assert_eq!(
+        Some(high),
+        cache.members.highest_role(GuildId(1), UserId(4)).await?
+    );
+    assert_eq!(
+        Some(0x00ff_0000),
+        cache.members.color(GuildId(1), UserId(4)).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_voice_channel_voice_states() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    cache
+        .voice_channels
+        .upsert(VoiceChannelEntity {
+            bitrate: 64000,
+            guild_id: Some(GuildId(1)),
+            id: ChannelId(2),
+            kind: ChannelType::GuildVoice,
+            name: "voice".to_owned(),
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            position: 0,
+            user_limit: None,
+        })
+        .await?;
+    cache.members.upsert(member(1, 3)).await?;
+
+    for (user_id, channel_id) in &[(3, 2), (4, 5)] {
+        cache
+            .voice_states
+            .upsert(VoiceStateEntity {
+                channel_id: Some(ChannelId(*channel_id)),
+                deaf: false,
+                guild_id: GuildId(1),
+                mute: false,
+                self_deaf: false,
+                self_mute: false,
+                self_stream: false,
+                session_id: "session".to_owned(),
+                suppress: false,
+                user_id: UserId(*user_id),
+            })
+            .await?;
+    }
+
+    let states = cache
+        .voice_channels
+        .voice_states(ChannelId(2))
+        .await?
+        .map_ok(|state| state.user_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![UserId(3)], states);
+
+    let members = cache
+        .voice_channels
+        .members(ChannelId(2))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![member(1, 3)], members);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_remove_cascade() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    cache.text_channels.upsert(text_channel(None, 2)).await?;
+
+    for (id, attachment_id) in &[(3, 4), (5, 6)] {
+        let event = Event::MessageCreate(Box::new(MessageCreate(message(*id, 2, *attachment_id))));
+        cache.update(&event).await?;
+    }
+
+    let mut message_ids = cache
+        .text_channels
+        .message_ids(ChannelId(2))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    message_ids.sort();
+    assert_eq!(vec![MessageId(3), MessageId(5)], message_ids);
+
+    cache.text_channels.remove_cascade(ChannelId(2)).await?;
+
+    assert!(cache.text_channels.get(ChannelId(2)).await?.is_none());
+    assert!(cache.messages.get(MessageId(3)).await?.is_none());
+    assert!(cache.messages.get(MessageId(5)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(4)).await?.is_none());
+    assert!(cache.attachments.get(AttachmentId(6)).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_namespaces_and_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+
+    let mut builder = RedisBackend::builder(server.address.clone());
+    builder.format(Format::PackedCbor).namespace("first");
+    let first = RedisCache::with_backend(builder.build()?);
+
+    let mut builder = RedisBackend::builder(server.address.clone());
+    builder.namespace("second");
+    let second = RedisCache::with_backend(builder.build()?);
+
+    first.roles.upsert(role(1, 2)).await?;
+
+    assert_eq!(Some(role(1, 2)), first.roles.get(RoleId(2)).await?);
+    assert!(second.roles.get(RoleId(2)).await?.is_none());
+    assert!(second
+        .roles
+        .list()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_current_user() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    assert!(cache.current_user.current().await?.is_none());
+
+    cache
+        .update(&Event::UserUpdate(UserUpdate(CurrentUser {
+            avatar: None,
+            bot: true,
+            discriminator: "0001".to_owned(),
+            email: None,
+            id: UserId(1),
+            mfa_enabled: false,
+            name: "bot".to_owned(),
+            verified: true,
+        })))
+        .await?;
+    let user = cache.current_user.current().await?.expect("not cached");
+    assert_eq!("bot", user.name);
+
+    cache.members.upsert(member(2, 1)).await?;
+    assert!(cache
+        .current_user
+        .guild_ids()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_invite_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    let invite = |code: &str, channel_id| InviteEntity {
+        channel_id: ChannelId(channel_id),
+        code: code.to_owned(),
+        created_at: "2020-05-30T20:52:43.457000+00:00".to_owned(),
+        guild_id: GuildId(1),
+        inviter_id: None,
+        max_age: 0,
+        max_uses: 0,
+        target_user_type: None,
+        temporary: false,
+        uses: 0,
+    };
+    cache.invites.upsert(invite("a", 2)).await?;
+    cache.invites.upsert(invite("b", 3)).await?;
+
+    let mut codes = cache
+        .invites
+        .guild_invites(GuildId(1))
+        .await?
+        .map_ok(|invite| invite.code)
+        .try_collect::<Vec<_>>()
+        .await?;
+    codes.sort();
+    assert_eq!(vec!["a".to_owned(), "b".to_owned()], codes);
+
+    cache.invites.remove("a".to_owned()).await?;
+    assert!(cache
+        .invites
+        .channel_invites(ChannelId(2))
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_webhook_staleness() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    let webhook = |id| WebhookEntity {
+        avatar: None,
+        channel_id: ChannelId(2),
+        guild_id: Some(GuildId(1)),
+        id: WebhookId(id),
+        kind: WebhookType::Incoming,
+        name: Some("hook".to_owned()),
+        token: None,
+        user_id: None,
+    };
+    cache.text_channels.upsert(text_channel(Some(1), 2)).await?;
+    cache.webhooks.upsert(webhook(3)).await?;
+    cache.webhooks.set_stale(ChannelId(2), true).await?;
+    assert_eq!(Some(true), cache.webhooks.is_stale(ChannelId(2)).await?);
+
+    cache
+        .webhooks
+        .refresh(ChannelId(2), vec![webhook(4)])
+        .await?;
+    assert_eq!(Some(false), cache.webhooks.is_stale(ChannelId(2)).await?);
+
+    let ids = cache
+        .webhooks
+        .guild_webhooks(GuildId(1))
+        .await?
+        .map_ok(|webhook| webhook.id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(vec![WebhookId(4)], ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_update_field() -> Result<(), Box<dyn std::error::Error>> {
+    let server = server();
+    let cache = RedisCache::with_backend(RedisBackend::new(server.address.clone())?);
+
+    cache.members.upsert(member(1, 2)).await?;
+
+    let fields = vec![
+        MemberField::AddRoleId(RoleId(3)),
+        MemberField::Nick(Some("nick".to_owned())),
+    ];
+    assert!(
+        cache
+            .members
+            .update_field((GuildId(1), UserId(2)), fields)
+            .await?
+    );
+
+    let entity = cache.members.get((GuildId(1), UserId(2))).await?.unwrap();
+    assert_eq!(Some("nick"), entity.nick.as_deref());
+    assert_eq!(vec![RoleId(3)], entity.role_ids);
+
+    let field = MemberField::Mute(true);
+    assert!(
+        !cache
+            .members
+            .update_field((GuildId(1), UserId(3)), field)
+            .await?
+    );
+    assert!(cache.members.get((GuildId(1), UserId(3))).await?.is_none());
+
+    // Other commands still work after an update that didn't run a
+    // transaction.
+    cache.members.upsert(member(1, 3)).await?;
+    assert!(cache.members.get((GuildId(1), UserId(3))).await?.is_some());
+
+    Ok(())
+}
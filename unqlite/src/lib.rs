@@ -1,4 +1,7 @@
-use futures_util::future::{self, FutureExt};
+use futures_util::{
+    future::{self, FutureExt},
+    stream::{self, StreamExt},
+};
 use rarity_cache::{
     entity::{
         channel::{
@@ -9,116 +12,336 @@ use rarity_cache::{
             private_channel::{PrivateChannelEntity, PrivateChannelRepository},
             text_channel::{TextChannelEntity, TextChannelRepository},
             voice_channel::{VoiceChannelEntity, VoiceChannelRepository},
+            webhook::{WebhookEntity, WebhookRepository},
+            GuildChannelEntity,
         },
         gateway::presence::{PresenceEntity, PresenceRepository},
         guild::{
+            ban::{BanEntity, BanRepository},
             emoji::{EmojiEntity, EmojiRepository},
             member::{MemberEntity, MemberRepository},
             role::{RoleEntity, RoleRepository},
             GuildEntity, GuildRepository,
         },
-        user::{UserEntity, UserRepository},
+        invite::{InviteEntity, InviteRepository},
+        user::{CurrentUserEntity, CurrentUserRepository, UserEntity, UserRepository},
         voice::{VoiceStateEntity, VoiceStateRepository},
         Entity,
     },
-    repository::{GetEntityFuture, ListEntitiesFuture, RemoveEntityFuture, UpsertEntityFuture},
-    Backend,
-    Cache,
-    Repository,
+    repository::{
+        GetEntityFuture, ListEntitiesFuture, ListEntityIdsFuture, NoopRepository,
+        RemoveEntityFuture, UpsertEntityFuture,
+    },
+    Backend, Cache, Repository,
 };
 use serde::{de::DeserializeOwned, Serialize};
-use std::{marker::PhantomData, sync::Arc};
-use twilight_model::id::{AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId};
-use unqlite::{Error, KV, UnQLite};
+use serde_cbor::Error as CborError;
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use twilight_model::id::{
+    AttachmentId, ChannelId, EmojiId, GuildId, MessageId, RoleId, UserId, WebhookId,
+};
+use unqlite::{Cursor, Error as UnqliteError, UnQLite, KV};
 
 pub type UnqliteCache = Cache<UnqliteBackend>;
 
-pub trait UnqliteEntity: Entity {
+/// Error returned from backend operations.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UnqliteBackendError {
+    /// Deserializing a record failed.
+    Deserializing {
+        /// Reason for the error.
+        source: CborError,
+    },
+    /// Serializing a record failed.
+    Serializing {
+        /// Reason for the error.
+        source: CborError,
+    },
+    /// An UnQLite operation failed.
+    Unqlite {
+        /// Reason for the error.
+        source: UnqliteError,
+    },
+}
+
+impl Display for UnqliteBackendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Deserializing { .. } => f.write_str("failed to deserialize a record"),
+            Self::Serializing { .. } => f.write_str("failed to serialize a record"),
+            Self::Unqlite { source } => write!(f, "unqlite operation failed: {}", source),
+        }
+    }
+}
+
+impl Error for UnqliteBackendError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Deserializing { source } | Self::Serializing { source } => Some(source),
+            // UnQLite's error doesn't implement `Error` itself, so its inner
+            // error is the source.
+            Self::Unqlite { source } => match source {
+                UnqliteError::Custom(source) => Some(source),
+                UnqliteError::Other(source) => Some(source.as_ref()),
+            },
+        }
+    }
+}
+
+pub trait UnqliteEntity: DeserializeOwned + Entity + Serialize {
+    /// Prefix of the keys of all records of the entity type.
+    const PREFIX: &'static str;
+
     fn key(id: Self::Id) -> Vec<u8>;
+
+    /// Keys of the index records that the entity is a member of, along with
+    /// the ID to store in each of them.
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        Vec::new()
+    }
+}
+
+fn guild_channel_indexes(guild_id: Option<GuildId>, id: ChannelId) -> Vec<(Vec<u8>, u64)> {
+    guild_id
+        .map(|guild_id| (format!("ix:g:{}:channels", guild_id).into_bytes(), id.0))
+        .into_iter()
+        .collect()
 }
 
 impl UnqliteEntity for AttachmentEntity {
+    const PREFIX: &'static str = "at:";
+
     fn key(id: AttachmentId) -> Vec<u8> {
         format!("at:{}", id).into_bytes()
     }
 }
 
+impl UnqliteEntity for BanEntity {
+    const PREFIX: &'static str = "b:";
+
+    fn key((guild_id, user_id): (GuildId, UserId)) -> Vec<u8> {
+        format!("b:{}:{}", guild_id, user_id).into_bytes()
+    }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:g:{}:bans", self.guild_id).into_bytes(),
+            self.user_id.0,
+        )]
+    }
+}
+
 impl UnqliteEntity for CategoryChannelEntity {
+    const PREFIX: &'static str = "cc:";
+
     fn key(id: ChannelId) -> Vec<u8> {
         format!("cc:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
+}
+
+impl UnqliteEntity for CurrentUserEntity {
+    const PREFIX: &'static str = "uc:";
+
+    fn key(id: UserId) -> Vec<u8> {
+        format!("uc:{}", id).into_bytes()
+    }
 }
 
 impl UnqliteEntity for EmojiEntity {
+    const PREFIX: &'static str = "em:";
+
     fn key(id: EmojiId) -> Vec<u8> {
         format!("em:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:g:{}:emojis", self.guild_id).into_bytes(),
+            self.id.0,
+        )]
+    }
 }
 
 impl UnqliteEntity for GroupEntity {
+    const PREFIX: &'static str = "gr:";
+
     fn key(id: ChannelId) -> Vec<u8> {
         format!("gr:{}", id).into_bytes()
     }
 }
 
 impl UnqliteEntity for GuildEntity {
+    const PREFIX: &'static str = "g:";
+
     fn key(id: GuildId) -> Vec<u8> {
         format!("g:{}", id).into_bytes()
     }
 }
 
+/// Invite codes can't be stored in index records, so invites aren't indexed
+/// and are listed by guild and channel by filtering all invites.
+impl UnqliteEntity for InviteEntity {
+    const PREFIX: &'static str = "i:";
+
+    fn key(code: String) -> Vec<u8> {
+        format!("i:{}", code).into_bytes()
+    }
+}
+
 impl UnqliteEntity for MemberEntity {
+    const PREFIX: &'static str = "m:";
+
     fn key((guild_id, user_id): (GuildId, UserId)) -> Vec<u8> {
         format!("m:{}:{}", guild_id, user_id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![
+            (
+                format!("ix:g:{}:members", self.guild_id).into_bytes(),
+                self.user_id.0,
+            ),
+            (
+                format!("ix:u:{}:guilds", self.user_id).into_bytes(),
+                self.guild_id.0,
+            ),
+        ]
+    }
 }
 
 impl UnqliteEntity for MessageEntity {
+    const PREFIX: &'static str = "ms:";
+
     fn key(id: MessageId) -> Vec<u8> {
         format!("ms:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:c:{}:messages", self.channel_id).into_bytes(),
+            self.id.0,
+        )]
+    }
 }
 
 impl UnqliteEntity for PresenceEntity {
+    const PREFIX: &'static str = "pr:";
+
     fn key((guild_id, user_id): (GuildId, UserId)) -> Vec<u8> {
         format!("pr:{}:{}", guild_id, user_id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:g:{}:presences", self.guild_id).into_bytes(),
+            self.user_id.0,
+        )]
+    }
 }
 
 impl UnqliteEntity for PrivateChannelEntity {
+    const PREFIX: &'static str = "cp:";
+
     fn key(id: ChannelId) -> Vec<u8> {
         format!("cp:{}", id).into_bytes()
     }
 }
 
 impl UnqliteEntity for RoleEntity {
+    const PREFIX: &'static str = "r:";
+
     fn key(id: RoleId) -> Vec<u8> {
         format!("r:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:g:{}:roles", self.guild_id).into_bytes(),
+            self.id.0,
+        )]
+    }
 }
 
 impl UnqliteEntity for TextChannelEntity {
+    const PREFIX: &'static str = "ct:";
+
     fn key(id: ChannelId) -> Vec<u8> {
         format!("ct:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
 }
 
 impl UnqliteEntity for UserEntity {
+    const PREFIX: &'static str = "u:";
+
     fn key(id: UserId) -> Vec<u8> {
         format!("u:{}", id).into_bytes()
     }
 }
 
 impl UnqliteEntity for VoiceChannelEntity {
+    const PREFIX: &'static str = "cv:";
+
     fn key(id: ChannelId) -> Vec<u8> {
         format!("cv:{}", id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        guild_channel_indexes(self.guild_id, self.id)
+    }
 }
 
 impl UnqliteEntity for VoiceStateEntity {
+    const PREFIX: &'static str = "v:";
+
     fn key((guild_id, user_id): (GuildId, UserId)) -> Vec<u8> {
         format!("v:{}:{}", guild_id, user_id).into_bytes()
     }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        vec![(
+            format!("ix:g:{}:voice_states", self.guild_id).into_bytes(),
+            self.user_id.0,
+        )]
+    }
+}
+
+impl UnqliteEntity for WebhookEntity {
+    const PREFIX: &'static str = "wh:";
+
+    fn key(id: WebhookId) -> Vec<u8> {
+        format!("wh:{}", id).into_bytes()
+    }
+
+    fn indexes(&self) -> Vec<(Vec<u8>, u64)> {
+        let mut indexes = vec![(
+            format!("ix:c:{}:webhooks", self.channel_id).into_bytes(),
+            self.id.0,
+        )];
+
+        if let Some(guild_id) = self.guild_id {
+            indexes.push((
+                format!("ix:g:{}:webhooks", guild_id).into_bytes(),
+                self.id.0,
+            ));
+        }
+
+        indexes
+    }
 }
 
 pub struct UnqliteRepository<T>(UnqliteBackend, PhantomData<T>);
@@ -129,83 +352,191 @@ impl<T> UnqliteRepository<T> {
     }
 }
 
-impl<T: DeserializeOwned + Serialize + UnqliteEntity> Repository<T, UnqliteBackend> for UnqliteRepository<T> {
+impl<T: UnqliteEntity> Repository<T, UnqliteBackend> for UnqliteRepository<T> {
     fn backend(&self) -> UnqliteBackend {
         self.0.clone()
     }
 
-    fn get(&self, entity_id: T::Id) -> GetEntityFuture<'_, T, Error> {
-        let bytes: Vec<u8> = (self.0).0.kv_fetch(T::key(entity_id)).unwrap();
+    fn get(&self, entity_id: T::Id) -> GetEntityFuture<'_, T, UnqliteBackendError> {
+        future::ready(self.0.get(&T::key(entity_id))).boxed()
+    }
+
+    fn list(&self) -> ListEntitiesFuture<'_, T, UnqliteBackendError> {
+        let result = self.0.list::<T>();
 
-        future::ok(Some(serde_cbor::from_slice::<T>(&bytes).unwrap())).boxed()
+        future::ready(result.map(|entities| stream::iter(entities.into_iter().map(Ok)).boxed()))
+            .boxed()
     }
 
-    fn list(&self) -> ListEntitiesFuture<'_, T, Error> {
-        unimplemented!("not implemented by this backend");
+    fn remove(&self, entity_id: T::Id) -> RemoveEntityFuture<'_, UnqliteBackendError> {
+        future::ready(self.0.remove::<T>(entity_id)).boxed()
     }
 
-    fn remove(&self, entity_id: T::Id) -> RemoveEntityFuture<'_, Error> {
-        future::ready((self.0).0.kv_delete(T::key(entity_id))).boxed()
+    fn upsert(&self, entity: T) -> UpsertEntityFuture<'_, UnqliteBackendError> {
+        future::ready(self.0.upsert(&entity)).boxed()
     }
+}
 
-    fn upsert(&self, entity: T) -> UpsertEntityFuture<'_, Error> {
-        let bytes = serde_cbor::to_vec(&entity).unwrap();
+/// Stream the IDs in an index record, mapping them with the given function.
+fn index_ids<T: Send + 'static>(
+    backend: &UnqliteBackend,
+    key: String,
+    f: fn(u64) -> T,
+) -> ListEntityIdsFuture<'_, T, UnqliteBackendError> {
+    let result = backend.index(key.as_bytes());
 
-        future::ready((self.0).0.kv_store(T::key(entity.id()), bytes)).boxed()
-    }
+    future::ready(result.map(|ids| stream::iter(ids.into_iter().map(f).map(Ok)).boxed())).boxed()
+}
+
+/// Stream the entities whose IDs are in an index record, using the given
+/// function to create the key of each entity.
+fn index_entities<T: UnqliteEntity + 'static>(
+    backend: &UnqliteBackend,
+    key: String,
+    f: impl Fn(u64) -> Vec<u8>,
+) -> ListEntitiesFuture<'_, T, UnqliteBackendError> {
+    let result = backend.index(key.as_bytes()).and_then(|ids| {
+        ids.into_iter()
+            .filter_map(|id| backend.get::<T>(&f(id)).transpose())
+            .collect::<Result<Vec<_>, _>>()
+    });
+
+    future::ready(result.map(|entities| stream::iter(entities.into_iter().map(Ok)).boxed())).boxed()
 }
 
 impl AttachmentRepository<UnqliteBackend> for UnqliteRepository<AttachmentEntity> {}
 
+impl BanRepository<UnqliteBackend> for UnqliteRepository<BanEntity> {
+    fn bans(&self, guild_id: GuildId) -> ListEntitiesFuture<'_, BanEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:g:{}:bans", guild_id), |id| {
+            BanEntity::key((guild_id, UserId(id)))
+        })
+    }
+}
+
 impl CategoryChannelRepository<UnqliteBackend> for UnqliteRepository<CategoryChannelEntity> {}
 
+impl CurrentUserRepository<UnqliteBackend> for UnqliteRepository<CurrentUserEntity> {
+    fn guild_ids(&self) -> ListEntityIdsFuture<'_, GuildId, UnqliteBackendError> {
+        let result = self.0.list::<GuildEntity>();
+
+        future::ready(
+            result.map(|guilds| stream::iter(guilds.into_iter().map(|guild| Ok(guild.id))).boxed()),
+        )
+        .boxed()
+    }
+
+    fn guilds(&self) -> ListEntitiesFuture<'_, GuildEntity, UnqliteBackendError> {
+        let result = self.0.list::<GuildEntity>();
+
+        future::ready(result.map(|guilds| stream::iter(guilds.into_iter().map(Ok)).boxed())).boxed()
+    }
+}
+
 impl EmojiRepository<UnqliteBackend> for UnqliteRepository<EmojiEntity> {}
 
 impl GroupRepository<UnqliteBackend> for UnqliteRepository<GroupEntity> {}
 
 impl GuildRepository<UnqliteBackend> for UnqliteRepository<GuildEntity> {
-    fn channel_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, ChannelId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn channel_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, ChannelId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:channels", guild_id), ChannelId)
     }
 
-    fn channels(&self, _: GuildId) -> ListEntitiesFuture<'_, rarity_cache::entity::channel::GuildChannelEntity, Error> {
-        unimplemented!("not implemented by this backend");
+    fn channels(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, GuildChannelEntity, UnqliteBackendError> {
+        let backend = &self.0;
+
+        let result = backend
+            .index(format!("ix:g:{}:channels", guild_id).as_bytes())
+            .and_then(|ids| {
+                let mut channels = Vec::new();
+
+                for id in ids {
+                    let id = ChannelId(id);
+
+                    if let Some(channel) = backend.get(&TextChannelEntity::key(id))? {
+                        channels.push(GuildChannelEntity::Text(channel));
+                    } else if let Some(channel) = backend.get(&VoiceChannelEntity::key(id))? {
+                        channels.push(GuildChannelEntity::Voice(channel));
+                    } else if let Some(channel) = backend.get(&CategoryChannelEntity::key(id))? {
+                        channels.push(GuildChannelEntity::Category(channel));
+                    }
+                }
+
+                Ok(channels)
+            });
+
+        future::ready(result.map(|channels| stream::iter(channels.into_iter().map(Ok)).boxed()))
+            .boxed()
     }
 
-    fn emoji_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, EmojiId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn emoji_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, EmojiId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:emojis", guild_id), EmojiId)
     }
 
-    fn member_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, UserId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn member_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, UserId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:members", guild_id), UserId)
     }
 
-    fn members(&self, _: GuildId) -> ListEntitiesFuture<'_, MemberEntity, Error> {
-        unimplemented!("not implemented by this backend");
+    fn members(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, MemberEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:g:{}:members", guild_id), |id| {
+            MemberEntity::key((guild_id, UserId(id)))
+        })
     }
 
-    fn presence_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, UserId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn presence_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, UserId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:presences", guild_id), UserId)
     }
 
-    fn presences(&self, _: GuildId) -> ListEntitiesFuture<'_, PresenceEntity, Error> {
-        unimplemented!("not implemented by this backend");
+    fn presences(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, PresenceEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:g:{}:presences", guild_id), |id| {
+            PresenceEntity::key((guild_id, UserId(id)))
+        })
     }
 
-    fn role_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, RoleId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn role_ids(&self, guild_id: GuildId) -> ListEntityIdsFuture<'_, RoleId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:roles", guild_id), RoleId)
     }
 
-    fn voice_state_ids(&self, _: GuildId) -> rarity_cache::repository::ListEntityIdsFuture<'_, UserId, Error> {
-        unimplemented!("not implemented by this backend");
+    fn voice_state_ids(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntityIdsFuture<'_, UserId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:g:{}:voice_states", guild_id), UserId)
     }
 
-    fn voice_states(&self, _: GuildId)
-        -> ListEntitiesFuture<'_, VoiceStateEntity, Error> {
-        unimplemented!("not implemented by this backend");
+    fn voice_states(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, VoiceStateEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:g:{}:voice_states", guild_id), |id| {
+            VoiceStateEntity::key((guild_id, UserId(id)))
+        })
     }
 }
 
+impl InviteRepository<UnqliteBackend> for UnqliteRepository<InviteEntity> {}
+
 impl MemberRepository<UnqliteBackend> for UnqliteRepository<MemberEntity> {}
 
 impl MessageRepository<UnqliteBackend> for UnqliteRepository<MessageEntity> {}
@@ -216,28 +547,79 @@ impl PrivateChannelRepository<UnqliteBackend> for UnqliteRepository<PrivateChann
 
 impl RoleRepository<UnqliteBackend> for UnqliteRepository<RoleEntity> {}
 
-impl TextChannelRepository<UnqliteBackend> for UnqliteRepository<TextChannelEntity> {}
+impl TextChannelRepository<UnqliteBackend> for UnqliteRepository<TextChannelEntity> {
+    fn message_ids(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntityIdsFuture<'_, MessageId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:c:{}:messages", channel_id), MessageId)
+    }
+}
 
 impl VoiceChannelRepository<UnqliteBackend> for UnqliteRepository<VoiceChannelEntity> {}
 
 impl VoiceStateRepository<UnqliteBackend> for UnqliteRepository<VoiceStateEntity> {}
 
+impl WebhookRepository<UnqliteBackend> for UnqliteRepository<WebhookEntity> {
+    fn channel_webhooks(
+        &self,
+        channel_id: ChannelId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:c:{}:webhooks", channel_id), |id| {
+            WebhookEntity::key(WebhookId(id))
+        })
+    }
+
+    fn guild_webhooks(
+        &self,
+        guild_id: GuildId,
+    ) -> ListEntitiesFuture<'_, WebhookEntity, UnqliteBackendError> {
+        index_entities(&self.0, format!("ix:g:{}:webhooks", guild_id), |id| {
+            WebhookEntity::key(WebhookId(id))
+        })
+    }
+
+    fn set_stale(
+        &self,
+        channel_id: ChannelId,
+        stale: bool,
+    ) -> UpsertEntityFuture<'_, UnqliteBackendError> {
+        let result = update_index(&self.0.db(), b"ix:webhooks_stale", |ids| {
+            if stale {
+                ids.insert(channel_id.0)
+            } else {
+                ids.remove(&channel_id.0)
+            }
+        });
+
+        future::ready(result).boxed()
+    }
+
+    fn stale_channel_ids(&self) -> ListEntityIdsFuture<'_, ChannelId, UnqliteBackendError> {
+        index_ids(&self.0, "ix:webhooks_stale".to_owned(), ChannelId)
+    }
+}
+
 impl UserRepository<UnqliteBackend> for UnqliteRepository<UserEntity> {
-    fn guild_ids(&self, _: UserId) -> rarity_cache::repository::ListEntityIdsFuture<'_, GuildId, Error> {
-        unimplemented!("not implemented by this backend")
+    fn guild_ids(&self, user_id: UserId) -> ListEntityIdsFuture<'_, GuildId, UnqliteBackendError> {
+        index_ids(&self.0, format!("ix:u:{}:guilds", user_id), GuildId)
     }
 }
 
 /// `rarity-cache` backend for the [UnQLite] database.
 ///
+/// Records are stored as CBOR. Relationships between entities, such as the
+/// members of a guild, are persisted as index records under keys prefixed with
+/// `ix:`, which are kept up to date when entities are upserted and removed.
+///
 /// [UnQLite]: https://docs.rs/unqlite
 #[derive(Clone)]
-pub struct UnqliteBackend(Arc<UnQLite>);
+pub struct UnqliteBackend(Arc<Mutex<UnQLite>>);
 
 impl UnqliteBackend {
     /// Create a new `rarity-cache` UnQLite backend with a provided instance.
     pub fn new(unqlite: UnQLite) -> Self {
-        Self(Arc::new(unqlite))
+        Self(Arc::new(Mutex::new(unqlite)))
     }
 
     /// Shortcut for `UnQLite::create` and [`new`].
@@ -278,33 +660,176 @@ impl UnqliteBackend {
     fn repo<T>(&self) -> UnqliteRepository<T> {
         UnqliteRepository::new(self.clone())
     }
+
+    /// Lock the database.
+    ///
+    /// Operations that read and then write records, such as updating index
+    /// records, hold the lock for their duration so that they don't race
+    /// with each other.
+    fn db(&self) -> MutexGuard<'_, UnQLite> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn get<T: DeserializeOwned>(&self, key: &[u8]) -> Result<Option<T>, UnqliteBackendError> {
+        fetch(&self.db(), key)
+    }
+
+    fn index(&self, key: &[u8]) -> Result<BTreeSet<u64>, UnqliteBackendError> {
+        self.get(key).map(Option::unwrap_or_default)
+    }
+
+    /// Retrieve all records of an entity type.
+    ///
+    /// UnQLite's default key/value engine is a hash table, so records aren't
+    /// ordered by key and the cursor has to walk every record, keeping those
+    /// with the entity type's key prefix.
+    fn list<T: UnqliteEntity>(&self) -> Result<Vec<T>, UnqliteBackendError> {
+        let db = self.db();
+        let mut entities = Vec::new();
+        let mut entry = db.first();
+
+        while let Some(current) = entry {
+            let (key, value) = current.key_value();
+
+            if key.starts_with(T::PREFIX.as_bytes()) {
+                entities.push(deserialize(&value)?);
+            }
+
+            entry = current.next();
+        }
+
+        Ok(entities)
+    }
+
+    fn remove<T: UnqliteEntity>(&self, id: T::Id) -> Result<(), UnqliteBackendError> {
+        let db = self.db();
+        let key = T::key(id);
+
+        let old = fetch::<T>(&db, &key)?;
+        delete(&db, &key)?;
+
+        for (index, id) in old.map(|old| old.indexes()).unwrap_or_default() {
+            update_index(&db, &index, |ids| ids.remove(&id))?;
+        }
+
+        Ok(())
+    }
+
+    fn upsert<T: UnqliteEntity>(&self, entity: &T) -> Result<(), UnqliteBackendError> {
+        let db = self.db();
+        let key = T::key(entity.id());
+        let indexes = entity.indexes();
+
+        let old = fetch::<T>(&db, &key)?;
+        store(&db, &key, entity)?;
+
+        for (index, id) in old.map(|old| old.indexes()).unwrap_or_default() {
+            if !indexes.contains(&(index.clone(), id)) {
+                update_index(&db, &index, |ids| ids.remove(&id))?;
+            }
+        }
+
+        for (index, id) in indexes {
+            update_index(&db, &index, |ids| ids.insert(id))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn fetch<T: DeserializeOwned>(db: &UnQLite, key: &[u8]) -> Result<Option<T>, UnqliteBackendError> {
+    // A missing record is reported as an error by UnQLite, but without a way
+    // to tell it apart from other errors, so check for its existence first.
+    if !db.kv_contains(key) {
+        return Ok(None);
+    }
+
+    let bytes = db
+        .kv_fetch(key)
+        .map_err(|source| UnqliteBackendError::Unqlite { source })?;
+
+    deserialize(&bytes).map(Some)
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, UnqliteBackendError> {
+    serde_cbor::from_slice(bytes).map_err(|source| UnqliteBackendError::Deserializing { source })
+}
+
+fn store<T: Serialize>(db: &UnQLite, key: &[u8], value: &T) -> Result<(), UnqliteBackendError> {
+    let bytes =
+        serde_cbor::to_vec(value).map_err(|source| UnqliteBackendError::Serializing { source })?;
+
+    db.kv_store(key, bytes)
+        .map_err(|source| UnqliteBackendError::Unqlite { source })
+}
+
+fn delete(db: &UnQLite, key: &[u8]) -> Result<(), UnqliteBackendError> {
+    if !db.kv_contains(key) {
+        return Ok(());
+    }
+
+    db.kv_delete(key)
+        .map_err(|source| UnqliteBackendError::Unqlite { source })
+}
+
+/// Modify an index record, removing it once it's empty.
+fn update_index(
+    db: &UnQLite,
+    key: &[u8],
+    f: impl FnOnce(&mut BTreeSet<u64>) -> bool,
+) -> Result<(), UnqliteBackendError> {
+    let mut ids = fetch::<BTreeSet<u64>>(db, key)?.unwrap_or_default();
+
+    if !f(&mut ids) {
+        return Ok(());
+    }
+
+    if ids.is_empty() {
+        delete(db, key)
+    } else {
+        store(db, key, &ids)
+    }
 }
 
 impl Backend for UnqliteBackend {
-    type Error = Error;
+    type Error = UnqliteBackendError;
     type AttachmentRepository = UnqliteRepository<AttachmentEntity>;
+    type BanRepository = UnqliteRepository<BanEntity>;
     type CategoryChannelRepository = UnqliteRepository<CategoryChannelEntity>;
+    type CurrentUserRepository = UnqliteRepository<CurrentUserEntity>;
     type EmojiRepository = UnqliteRepository<EmojiEntity>;
     type GroupRepository = UnqliteRepository<GroupEntity>;
     type GuildRepository = UnqliteRepository<GuildEntity>;
+    type InviteRepository = UnqliteRepository<InviteEntity>;
     type MemberRepository = UnqliteRepository<MemberEntity>;
     type MessageRepository = UnqliteRepository<MessageEntity>;
     type PresenceRepository = UnqliteRepository<PresenceEntity>;
     type PrivateChannelRepository = UnqliteRepository<PrivateChannelEntity>;
     type RoleRepository = UnqliteRepository<RoleEntity>;
     type TextChannelRepository = UnqliteRepository<TextChannelEntity>;
+    /// Typing states expire within seconds, so they aren't persisted.
+    type TypingRepository = NoopRepository<Self>;
     type UserRepository = UnqliteRepository<UserEntity>;
     type VoiceChannelRepository = UnqliteRepository<VoiceChannelEntity>;
     type VoiceStateRepository = UnqliteRepository<VoiceStateEntity>;
+    type WebhookRepository = UnqliteRepository<WebhookEntity>;
 
     fn attachments(&self) -> Self::AttachmentRepository {
         self.repo()
     }
 
+    fn bans(&self) -> Self::BanRepository {
+        self.repo()
+    }
+
     fn category_channels(&self) -> Self::CategoryChannelRepository {
         self.repo()
     }
 
+    fn current_user(&self) -> Self::CurrentUserRepository {
+        self.repo()
+    }
+
     fn emojis(&self) -> Self::EmojiRepository {
         self.repo()
     }
@@ -317,6 +842,10 @@ impl Backend for UnqliteBackend {
         self.repo()
     }
 
+    fn invites(&self) -> Self::InviteRepository {
+        self.repo()
+    }
+
     fn members(&self) -> Self::MemberRepository {
         self.repo()
     }
@@ -341,6 +870,10 @@ impl Backend for UnqliteBackend {
         self.repo()
     }
 
+    fn typing(&self) -> Self::TypingRepository {
+        NoopRepository::new(self.clone())
+    }
+
     fn users(&self) -> Self::UserRepository {
         self.repo()
     }
@@ -352,4 +885,144 @@ impl Backend for UnqliteBackend {
     fn voice_states(&self) -> Self::VoiceStateRepository {
         self.repo()
     }
+
+    fn webhooks(&self) -> Self::WebhookRepository {
+        self.repo()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UnqliteBackend, UnqliteBackendError, UnqliteCache, UnqliteError};
+    use futures_util::stream::TryStreamExt;
+    use rarity_cache::{
+        entity::{
+            guild::{GuildRepository, MemberEntity},
+            user::{UserEntity, UserRepository},
+        },
+        Backend, Repository,
+    };
+    use static_assertions::assert_impl_all;
+    use std::{error::Error, fmt::Debug, io::Error as IoError};
+    use twilight_model::id::{GuildId, UserId};
+
+    assert_impl_all!(UnqliteBackendError: Debug, Error, Send, Sync);
+    assert_impl_all!(UnqliteBackend: Backend, Clone, Send, Sync);
+
+    fn cache() -> UnqliteCache {
+        UnqliteCache::with_backend(UnqliteBackend::new(UnqliteBackend::create_in_memory()))
+    }
+
+    fn member(guild_id: u64, user_id: u64) -> MemberEntity {
+        MemberEntity {
+            deaf: false,
+            guild_id: GuildId(guild_id),
+            hoisted_role_id: None,
+            joined_at: None,
+            mute: false,
+            nick: None,
+            premium_since: None,
+            role_ids: Vec::new(),
+            user_id: UserId(user_id),
+        }
+    }
+
+    fn user(id: u64) -> UserEntity {
+        UserEntity {
+            avatar: None,
+            bot: false,
+            discriminator: "0001".to_owned(),
+            email: None,
+            flags: None,
+            id: UserId(id),
+            locale: None,
+            mfa_enabled: None,
+            name: "test".to_owned(),
+            premium_type: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        }
+    }
+
+    #[test]
+    fn test_error_source() {
+        let error = UnqliteBackendError::Unqlite {
+            source: UnqliteError::Other(Box::new(IoError::other("io"))),
+        };
+
+        assert_eq!("io", error.source().expect("has source").to_string());
+    }
+
+    #[tokio::test]
+    async fn test_get_missing() -> Result<(), Box<dyn Error>> {
+        let cache = cache();
+        assert!(cache.users.get(UserId(1)).await?.is_none());
+        cache.users.remove(UserId(1)).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list() -> Result<(), Box<dyn Error>> {
+        let cache = cache();
+        cache.users.upsert(user(1)).await?;
+        cache.users.upsert(user(2)).await?;
+        cache.members.upsert(member(3, 1)).await?;
+
+        let mut ids = cache
+            .users
+            .list()
+            .await?
+            .map_ok(|user| user.id)
+            .try_collect::<Vec<_>>()
+            .await?;
+        ids.sort();
+        assert_eq!(vec![UserId(1), UserId(2)], ids);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_indexes() -> Result<(), Box<dyn Error>> {
+        let cache = cache();
+        cache.members.upsert(member(1, 2)).await?;
+        cache.members.upsert(member(3, 2)).await?;
+
+        let members = cache
+            .guilds
+            .members(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![member(1, 2)], members);
+
+        let guild_ids = cache
+            .users
+            .guild_ids(UserId(2))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![GuildId(1), GuildId(3)], guild_ids);
+
+        cache.members.remove((GuildId(1), UserId(2))).await?;
+
+        let member_ids = cache
+            .guilds
+            .member_ids(GuildId(1))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert!(member_ids.is_empty());
+
+        let guild_ids = cache
+            .users
+            .guild_ids(UserId(2))
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(vec![GuildId(3)], guild_ids);
+
+        Ok(())
+    }
 }
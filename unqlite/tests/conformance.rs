@@ -0,0 +1,11 @@
+use rarity_cache::conformance::Suite;
+use rarity_cache_unqlite::UnqliteBackend;
+
+#[tokio::test]
+async fn test_conformance() -> Result<(), Box<dyn std::error::Error>> {
+    Suite::new(|| UnqliteBackend::new(UnqliteBackend::create_in_memory()))
+        .run()
+        .await?;
+
+    Ok(())
+}